@@ -1,36 +1,52 @@
 use rlua::Lua;
 
+pub mod edit_op;
 pub mod edit_state;
 pub mod interact_mode;
 pub mod presentation;
 
 use {
-    self::edit_state::EditState,
+    self::{
+        edit_op::{EditOp, RANDOM_FILL_CONFIRM_THRESHOLD},
+        edit_state::EditState,
+        presentation::ColorPreset,
+    },
     crate::{
         args::{Args, SourceArgs},
+        compression,
         config::Config,
+        damage_region::DamageRegion,
+        edit_log::EditLog,
         gui::Gui,
         hex_ui::HexUi,
         input::Input,
-        layout::{default_margin, do_auto_layout, Layout},
+        ips,
+        layout::{default_margin, do_auto_layout, Divider, Layout},
         meta::{
-            perspective::Perspective, region::Region, LayoutKey, Meta, NamedRegion, NamedView,
-            PerspectiveKey, PerspectiveMap, RegionMap, ViewKey,
+            find_most_specific_region_for_offset, perspective::Perspective, region::Region,
+            Bookmark, LayoutKey, Meta, NamedRegion, NamedView, PerspectiveKey, PerspectiveMap,
+            RegionMap, ViewKey,
         },
         meta_state::MetaState,
+        parse_radix::offset_from_filename,
         preferences::Preferences,
         shell::{msg_if_fail, msg_warn},
+        slice_ext::SliceExt,
         source::{Source, SourceAttributes, SourcePermissions, SourceProvider, SourceState},
-        view::{HexData, TextData, View, ViewKind},
+        template::Template,
+        view::{HexData, TextData, View, ViewKind, ViewportVec},
     },
     anyhow::{bail, Context},
     egui_sfml::sfml::graphics::Font,
     gamedebug_core::per_msg,
+    rand::{rngs::StdRng, thread_rng, RngCore, SeedableRng},
+    rlua::Function,
     slotmap::Key,
     std::{
         ffi::OsString,
         fs::{File, OpenOptions},
         io::{Read, Seek, SeekFrom, Write},
+        mem::{self, discriminant},
         path::{Path, PathBuf},
         sync::mpsc::Receiver,
         thread,
@@ -49,10 +65,129 @@ pub struct App {
     pub stream_read_recv: Option<Receiver<Vec<u8>>>,
     pub cfg: Config,
     last_reload: Instant,
+    last_auto_save: Instant,
     pub preferences: Preferences,
     pub hex_ui: HexUi,
     pub meta_state: MetaState,
     pub lua: Lua,
+    /// Operation confirmed by the unsaved changes dialog, to be run at the end of the frame
+    pub pending_file_op: Option<FileOp>,
+    /// File to compare against for "next/previous change" navigation
+    pub reference: Option<ReferenceFile>,
+    /// The most recently closed file, for "Reopen last closed"
+    pub last_closed: Option<ClosedFile>,
+    /// Two regions of the data that are compared against each other
+    pub region_comparison: Option<RegionComparison>,
+    /// Expected byte values the data is checked against
+    pub template: Option<Template>,
+    /// The open tabs. The slot of the active tab is empty, its state lives in `self`.
+    pub tabs: Vec<Session>,
+    pub active_tab: usize,
+    /// The most recently applied [`EditOp`], for "Repeat last operation"
+    pub last_op: Option<EditOp>,
+    /// Bumped whenever a different file becomes the current one (opening, closing or
+    /// switching tabs), so the gui can drop its state about the previous one
+    pub file_generation: u64,
+    /// Bumped whenever the data is replaced or resized, including reloads and file changes,
+    /// so work in progress on the data can be dropped
+    pub data_generation: u64,
+}
+
+/// A file that was closed, along with where the cursor was
+#[derive(Clone)]
+pub struct ClosedFile {
+    pub args: Args,
+    pub cursor: usize,
+}
+
+/// A baseline file that the current data is compared against
+pub struct ReferenceFile {
+    pub path: PathBuf,
+    pub data: Vec<u8>,
+}
+
+impl ReferenceFile {
+    /// Whether `data` differs from the reference at `offset`
+    pub fn differs_at(&self, offset: usize, data: &[u8]) -> bool {
+        self.data.get(offset) != data.get(offset)
+    }
+}
+
+/// Result of comparing two regions of the same data byte by byte
+pub struct RegionComparison {
+    pub a: Region,
+    pub b: Region,
+    /// Offset (relative to the region starts) of the first differing byte
+    pub first_diff: Option<usize>,
+    /// Number of differing bytes within the common length of the regions
+    pub diff_count: usize,
+}
+
+impl RegionComparison {
+    pub fn new(a: Region, b: Region, data: &[u8]) -> Self {
+        let (a_data, b_data) = (region_bytes(a, data), region_bytes(b, data));
+        let mut diffs = a_data.iter().zip(b_data).enumerate().filter(|(_, (x, y))| x != y);
+        let first_diff = diffs.next().map(|(i, _)| i);
+        let diff_count = first_diff.map_or(0, |_| 1 + diffs.count());
+        Self {
+            a,
+            b,
+            first_diff,
+            diff_count,
+        }
+    }
+    /// Whether the byte at `offset` differs from its counterpart in the other region
+    pub fn differs_at(&self, offset: usize, data: &[u8]) -> bool {
+        let common = self.a.len().min(self.b.len());
+        let counterpart = |from: Region, to: Region| {
+            let rel = offset - from.begin;
+            (rel < common).then_some(to.begin + rel)
+        };
+        let other = if self.a.contains(offset) {
+            counterpart(self.a, self.b)
+        } else if self.b.contains(offset) {
+            counterpart(self.b, self.a)
+        } else {
+            None
+        };
+        other.is_some_and(|&other| data.get(offset) != data.get(other))
+    }
+}
+
+/// The bytes of `data` within `region`, clamped to the length of `data`
+fn region_bytes(region: Region, data: &[u8]) -> &[u8] {
+    data.get(region.begin..data.len().min(region.end.saturating_add(1)))
+        .unwrap_or_default()
+}
+
+/// An operation that replaces or closes the current source, discarding unsaved changes
+pub enum FileOp {
+    /// Pick a file to open
+    Open,
+    Load(Args),
+    Reload,
+    Close,
+    ReopenClosed,
+    /// Close the current file, along with its tab if there are others
+    CloseTab,
+}
+
+/// The per-file state of a tab that isn't the active one
+#[derive(Default)]
+pub struct Session {
+    data: Vec<u8>,
+    edit_state: EditState,
+    args: Args,
+    source: Option<Source>,
+    stream_read_recv: Option<Receiver<Vec<u8>>>,
+    meta_state: MetaState,
+    select_a: Option<usize>,
+    select_b: Option<usize>,
+    current_layout: LayoutKey,
+    focused_view: Option<ViewKey>,
+    reference: Option<ReferenceFile>,
+    region_comparison: Option<RegionComparison>,
+    template: Option<Template>,
 }
 
 impl App {
@@ -70,20 +205,43 @@ impl App {
             stream_read_recv: None,
             cfg,
             last_reload: Instant::now(),
+            last_auto_save: Instant::now(),
             preferences: Preferences::default(),
             hex_ui: HexUi::default(),
             meta_state: MetaState::default(),
             lua: Lua::default(),
+            pending_file_op: None,
+            reference: None,
+            last_closed: None,
+            region_comparison: None,
+            template: None,
+            tabs: vec![Session::default()],
+            active_tab: 0,
+            last_op: None,
+            file_generation: 0,
+            data_generation: 0,
         };
+        this.preferences.bg_color = this.cfg.color_preset.bg_color();
+        msg_if_fail(this.reopen_edit_log(), "Failed to open edit log");
         msg_if_fail(this.load_file_args(args, font), "Failed to load file");
         Ok(this)
     }
+    /// (Re)open the edit log according to the configured path, or close it if unset
+    pub(crate) fn reopen_edit_log(&mut self) -> std::io::Result<()> {
+        self.edit_state.edit_log = None;
+        if let Some(path) = &self.cfg.edit_log_path {
+            self.edit_state.edit_log = Some(EditLog::open(path)?);
+        }
+        Ok(())
+    }
     pub fn reload(&mut self) -> anyhow::Result<()> {
         match &mut self.source {
             Some(src) => match &mut src.provider {
                 SourceProvider::File(file) => {
-                    self.data = read_contents(&self.args.src, file)?;
+                    self.data = read_contents(&self.args.src, file, self.cfg.max_read_size())?;
                     self.edit_state.dirty_region = None;
+                    self.edit_state.len_changed = false;
+                    self.take_pristine_snapshot();
                 }
                 SourceProvider::Stdin(_) => {
                     bail!("Can't reload streaming sources like standard input")
@@ -100,6 +258,7 @@ impl App {
             None => bail!("No file to reload"),
         }
         self.just_reloaded = true;
+        self.data_generation += 1;
         Ok(())
     }
     pub fn save(&mut self) -> anyhow::Result<()> {
@@ -150,12 +309,48 @@ impl App {
             None => &self.data,
         };
         file.write_all(data_to_write)?;
+        if self.edit_state.len_changed {
+            file.set_len((offset + self.data.len()) as u64)?;
+            self.edit_state.len_changed = false;
+        }
+        self.flush_to_disk()?;
         self.edit_state.dirty_region = None;
+        self.take_pristine_snapshot();
         if let Err(e) = self.save_temp_metafile_backup() {
             per_msg!("Failed to save metafile backup: {}", e);
         }
         Ok(())
     }
+    /// Write only the selected bytes back to the source, at their own offsets.
+    ///
+    /// Edits outside of the selection stay unsaved.
+    pub fn save_selection(&mut self) -> anyhow::Result<()> {
+        let Some(sel) = self.hex_ui.selection() else {
+            bail!("Nothing is selected");
+        };
+        if self.edit_state.len_changed {
+            bail!("The length of the data changed, the whole data has to be saved");
+        }
+        let remaining = self.edit_state.dirty_region;
+        // Saving snapshots everything as pristine, but only the selection will be
+        let pristine = self.edit_state.pristine.take();
+        self.edit_state.dirty_region = Some(sel);
+        let result = self.save();
+        self.edit_state.dirty_region =
+            remaining.filter(|reg| reg.begin < sel.begin || reg.end > sel.end);
+        self.edit_state.pristine = pristine;
+        if result.is_ok() && let Some(pristine) = &mut self.edit_state.pristine {
+            pristine[sel.begin..=sel.end].copy_from_slice(&self.data[sel.begin..=sel.end]);
+        }
+        result
+    }
+    /// Flush what was already written to the source to disk, without writing pending edits
+    pub fn flush_to_disk(&mut self) -> anyhow::Result<()> {
+        match &mut self.source {
+            Some(src) => Ok(src.provider.flush()?),
+            None => bail!("No source opened, nothing to flush"),
+        }
+    }
     pub fn save_temp_metafile_backup(&mut self) -> anyhow::Result<()> {
         // We set the last_meta_backup first, so if save fails, we don't get
         // a never ending stream of constant save failures.
@@ -164,6 +359,150 @@ impl App {
         per_msg!("Saved temp metafile backup");
         Ok(())
     }
+    /// Whether the source supports changing its length (inserting/deleting bytes)
+    pub(crate) fn can_resize(&self) -> bool {
+        let Some(src) = &self.source else { return false };
+        matches!(src.provider, SourceProvider::File(_))
+            && !src.attr.stream
+            && src.attr.permissions.write
+            && self.args.src.hard_seek.is_none()
+            && self.args.src.take.is_none()
+    }
+    /// Insert `bytes` at `offset`, shifting the rest of the data forward
+    pub(crate) fn insert_bytes(&mut self, offset: usize, bytes: &[u8]) -> anyhow::Result<()> {
+        if !self.can_resize() {
+            bail!("This source doesn't support inserting bytes");
+        }
+        if offset > self.data.len() {
+            bail!("Offset {} is out of bounds", offset);
+        }
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        let old_len = self.data.len();
+        self.data.splice(offset..offset, bytes.iter().copied());
+        #[expect(
+            clippy::cast_possible_wrap,
+            reason = "Inserting more than isize::MAX bytes is not possible"
+        )]
+        self.shift_offsets(offset, bytes.len() as isize);
+        // Appended bytes belong to the regions that reached the end of the data
+        if offset == old_len && let Some(last) = old_len.checked_sub(1) {
+            self.meta_state.meta.extend_regions_at_end(last, bytes.len());
+        }
+        self.mark_resized(offset);
+        Ok(())
+    }
+    /// Delete the bytes in `region`, shifting the rest of the data back
+    pub(crate) fn delete_range(&mut self, region: Region) -> anyhow::Result<()> {
+        if !self.can_resize() {
+            bail!("This source doesn't support deleting bytes");
+        }
+        if region.end >= self.data.len() {
+            bail!("Region {}..={} is out of bounds", region.begin, region.end);
+        }
+        if region.len() == self.data.len() {
+            bail!("Can't delete all the data");
+        }
+        self.data.drain(region.begin..=region.end);
+        #[expect(
+            clippy::cast_possible_wrap,
+            reason = "Deleting more than isize::MAX bytes is not possible"
+        )]
+        self.shift_offsets(region.begin, -(region.len() as isize));
+        self.mark_resized(region.begin);
+        self.edit_state.cursor = self.edit_state.cursor.min(self.data.len() - 1);
+        self.hex_ui.select_a = None;
+        self.hex_ui.select_b = None;
+        Ok(())
+    }
+    /// Replace the bytes in `region` with `bytes`.
+    ///
+    /// If the lengths differ, the rest of the data is shifted, which requires
+    /// the source to support resizing.
+    pub(crate) fn replace_range(&mut self, region: Region, bytes: &[u8]) -> anyhow::Result<()> {
+        if region.end >= self.data.len() {
+            bail!("Region {}..={} is out of bounds", region.begin, region.end);
+        }
+        if bytes.len() != region.len() && !self.can_resize() {
+            bail!(
+                "The new length ({}) differs from the old one ({}), \
+                 and this source doesn't support inserting or deleting bytes",
+                bytes.len(),
+                region.len()
+            );
+        }
+        let overwrite_len = bytes.len().min(region.len());
+        if overwrite_len != 0 {
+            let range = region.begin..region.begin + overwrite_len;
+            self.edit_state
+                .log_edit(region.begin, &self.data[range.clone()], &bytes[..overwrite_len]);
+            self.data[range.clone()].copy_from_slice(&bytes[..overwrite_len]);
+            self.edit_state.widen_dirty_region(DamageRegion::Range(range));
+        }
+        if bytes.len() > region.len() {
+            self.insert_bytes(region.end + 1, &bytes[region.len()..])?;
+        } else if bytes.len() < region.len() {
+            self.delete_range(Region {
+                begin: region.begin + bytes.len(),
+                end: region.end,
+            })?;
+        }
+        Ok(())
+    }
+    /// Apply the IPS patch at `path` to the data. The result is not saved automatically.
+    pub(crate) fn apply_ips_patch(&mut self, path: &Path) -> anyhow::Result<()> {
+        if !self.source.is_some_and(|src| src.attr.permissions.write) {
+            bail!("The source is read-only");
+        }
+        let records = ips::parse(&std::fs::read(path)?)?;
+        // Check everything up front, so a bad patch doesn't get half applied
+        if let Some(rec) = records.iter().find(|rec| rec.end() > self.data.len()) {
+            bail!(
+                "Patch record at {:X} (size {}) is out of bounds (data size {})",
+                rec.offset,
+                rec.data.len(),
+                self.data.len()
+            );
+        }
+        for rec in records.iter().filter(|rec| !rec.data.is_empty()) {
+            self.edit_state
+                .log_edit(rec.offset, &self.data[rec.offset..rec.end()], &rec.data);
+            self.data[rec.offset..rec.end()].copy_from_slice(&rec.data);
+            self.edit_state
+                .widen_dirty_region(DamageRegion::Range(rec.offset..rec.end()));
+        }
+        Ok(())
+    }
+    /// Write an IPS patch to `out` that turns the file at `original` into the current data
+    pub(crate) fn create_ips_patch(&self, original: &Path, out: &Path) -> anyhow::Result<()> {
+        let original = std::fs::read(original)?;
+        std::fs::write(out, ips::create(&original, &self.data)?)?;
+        Ok(())
+    }
+    /// Adjust offset based metadata after `delta` bytes were inserted or deleted at `at`
+    pub(crate) fn shift_offsets(&mut self, at: usize, delta: isize) {
+        self.meta_state.meta.shift_offsets(at, delta);
+    }
+    /// After a length change at `offset`, everything from there to the end needs to be written
+    fn mark_resized(&mut self, offset: usize) {
+        let last = self.data.len().saturating_sub(1);
+        let begin = offset.min(last);
+        self.edit_state.dirty_region = Some(Region {
+            begin: self
+                .edit_state
+                .dirty_region
+                .map_or(begin, |reg| reg.begin.min(begin)),
+            end: last,
+        });
+        self.edit_state.len_changed = true;
+        self.data_generation += 1;
+    }
+    /// Let the gui know that a different file became the current one
+    fn file_changed(&mut self) {
+        self.file_generation += 1;
+        self.data_generation += 1;
+    }
     pub fn search_focus(&mut self, offset: usize) {
         self.edit_state.cursor = offset;
         self.center_view_on_offset(offset);
@@ -212,6 +551,60 @@ impl App {
     pub(crate) fn double_cols(&mut self) {
         self.col_change_impl(|col| *col *= 2);
     }
+    /// Make the rows of the focused perspective as wide as the selection, to line up records
+    /// of that size
+    pub(crate) fn set_cols_from_selection(&mut self) {
+        let Some(sel) = self.hex_ui.selection() else { return };
+        self.col_change_impl(|col| *col = sel.len().max(1));
+        self.center_view_on_offset(sel.begin);
+    }
+    /// The selection, or if there is none, the rows visible in the focused view
+    pub(crate) fn selection_or_visible(&self) -> Option<Region> {
+        if let Some(sel) = self.hex_ui.selection() {
+            return Some(sel);
+        }
+        let view = &self.meta_state.meta.views[self.hex_ui.focused_view?].view;
+        let perspectives = &self.meta_state.meta.low.perspectives;
+        let per = perspectives.get(view.perspective)?;
+        let begin = view.offsets(perspectives, &self.meta_state.meta.low.regions).byte;
+        let rows = usize::try_from(view.rows()).ok()?;
+        let end = (begin + rows * per.cols).min(self.data.len()).checked_sub(1)?;
+        (begin <= end).then_some(Region { begin, end })
+    }
+    /// Center the focused view on the middle of the selection
+    pub(crate) fn center_view_on_selection(&mut self) {
+        let Some(sel) = self.hex_ui.selection() else {
+            msg_warn("Nothing is selected");
+            return;
+        };
+        self.center_view_on_offset(sel.begin + sel.len() / 2);
+    }
+    /// Set the column count of the focused view's perspective, and shrink its font if needed,
+    /// so that the whole selection fits into the view
+    pub(crate) fn zoom_to_fit_selection(&mut self, font: &Font) {
+        let Some(sel) = self.hex_ui.selection() else {
+            msg_warn("Nothing is selected");
+            return;
+        };
+        let Some(key) = self.hex_ui.focused_view else { return };
+        let cols = loop {
+            let view = &mut self.meta_state.meta.views[key].view;
+            // Leave a row to spare, as the selection doesn't necessarily start at a row start
+            let rows = usize::try_from(view.rows() - 1).unwrap_or(0).max(1);
+            let cols = (sel.len() + rows - 1) / rows;
+            let fits = usize::try_from(view.cols()).map_or(false, |avail| cols <= avail);
+            let block_size = (view.col_w, view.row_h);
+            if !fits {
+                view.zoom(-1, font);
+            }
+            // Stop when it fits, or the font can't get any smaller
+            if fits || (view.col_w, view.row_h) == block_size {
+                break cols;
+            }
+        };
+        self.col_change_impl(|col| *col = cols);
+        self.center_view_on_selection();
+    }
     pub fn cursor_history_back(&mut self) {
         if self.edit_state.cursor_history_back() {
             self.center_view_on_offset(self.edit_state.cursor);
@@ -240,6 +633,9 @@ impl App {
                     take: None,
                     read_only,
                     stream: false,
+                    ignore_read_only_exts: false,
+                    no_canonicalize: false,
+                    decompress: false,
                 },
                 recent: false,
                 meta: None,
@@ -252,6 +648,7 @@ impl App {
     pub fn new_file_readjust(&mut self, font: &Font) {
         self.meta_state.meta = Meta::default();
         self.meta_state.current_meta_path.clear();
+        self.meta_state.sidecar_load_failed = false;
         let def_region = self.meta_state.meta.low.regions.insert(NamedRegion {
             name: "default".into(),
             region: Region {
@@ -259,17 +656,22 @@ impl App {
                 end: self.data.len().saturating_sub(1),
             },
             desc: String::new(),
+            tint: None,
         });
         let default_perspective = self.meta_state.meta.low.perspectives.insert(Perspective {
             region: def_region,
             cols: 48,
             flip_row_order: false,
             name: "default".to_string(),
+            guide_cols: Vec::new(),
+            sector_size: 0,
         });
         let mut layout = Layout {
             name: "Default layout".into(),
             view_grid: vec![vec![]],
             margin: default_margin(),
+            row_weights: Vec::new(),
+            col_weights: Vec::new(),
         };
         for view in default_views(font, default_perspective) {
             let k = self.meta_state.meta.views.insert(view);
@@ -277,13 +679,171 @@ impl App {
         }
         let layout_key = self.meta_state.meta.layouts.insert(layout);
         App::switch_layout(&mut self.hex_ui, &self.meta_state.meta, layout_key);
+        self.meta_state.clean_meta = self.meta_state.meta.clone();
+    }
+
+    /// Whether there are unsaved changes that could be saved to the source
+    pub(crate) fn has_unsaved_changes(&self) -> bool {
+        self.edit_state.dirty_region.is_some()
+            && self
+                .source
+                .is_some_and(|src| src.attr.permissions.write && !src.attr.stream)
+    }
+
+    pub(crate) fn run_file_op(&mut self, op: FileOp, font: &Font) {
+        match op {
+            FileOp::Open => crate::shell::open_file(self, font),
+            FileOp::Load(args) => {
+                msg_if_fail(self.load_file_args(args, font), "Failed to load file");
+            }
+            FileOp::Reload => {
+                msg_if_fail(self.reload(), "Failed to reload");
+            }
+            FileOp::Close => self.close_file(),
+            FileOp::ReopenClosed => self.reopen_last_closed(font),
+            FileOp::CloseTab => self.close_tab(),
+        }
     }
 
     pub fn close_file(&mut self) {
+        if self.args.src.file.is_some() {
+            self.last_closed = Some(ClosedFile {
+                args: self.args.clone(),
+                cursor: self.edit_state.cursor,
+            });
+        }
+        msg_if_fail(self.save_sidecar_meta(), "Failed to save metafile sidecar");
         // We potentially had large data, free it instead of clearing the Vec
         self.data = Vec::new();
         self.args.src.file = None;
         self.source = None;
+        self.region_comparison = None;
+        self.template = None;
+        self.file_changed();
+    }
+
+    /// Exchange the per-file state of the app with that of `session`
+    fn swap_session(&mut self, session: &mut Session) {
+        mem::swap(&mut self.data, &mut session.data);
+        mem::swap(&mut self.edit_state, &mut session.edit_state);
+        // The edit log is shared by all tabs
+        mem::swap(&mut self.edit_state.edit_log, &mut session.edit_state.edit_log);
+        mem::swap(&mut self.args, &mut session.args);
+        mem::swap(&mut self.source, &mut session.source);
+        mem::swap(&mut self.stream_read_recv, &mut session.stream_read_recv);
+        mem::swap(&mut self.meta_state, &mut session.meta_state);
+        mem::swap(&mut self.hex_ui.select_a, &mut session.select_a);
+        mem::swap(&mut self.hex_ui.select_b, &mut session.select_b);
+        mem::swap(&mut self.hex_ui.current_layout, &mut session.current_layout);
+        mem::swap(&mut self.hex_ui.focused_view, &mut session.focused_view);
+        mem::swap(&mut self.reference, &mut session.reference);
+        mem::swap(&mut self.region_comparison, &mut session.region_comparison);
+        mem::swap(&mut self.template, &mut session.template);
+    }
+
+    /// Make the tab at `idx` the active one
+    pub(crate) fn switch_tab(&mut self, idx: usize) {
+        if idx == self.active_tab || idx >= self.tabs.len() {
+            return;
+        }
+        let mut tabs = mem::take(&mut self.tabs);
+        self.swap_session(&mut tabs[self.active_tab]);
+        self.swap_session(&mut tabs[idx]);
+        self.tabs = tabs;
+        self.active_tab = idx;
+        self.file_changed();
+    }
+
+    /// Switch to the next (or previous) tab, wrapping around
+    pub(crate) fn cycle_tab(&mut self, forward: bool) {
+        let len = self.tabs.len();
+        let idx = if forward {
+            (self.active_tab + 1) % len
+        } else {
+            (self.active_tab + len - 1) % len
+        };
+        self.switch_tab(idx);
+    }
+
+    /// Open a new, empty tab and switch to it
+    pub(crate) fn new_tab(&mut self) {
+        self.tabs.push(Session::default());
+        self.switch_tab(self.tabs.len() - 1);
+    }
+
+    /// Close the file of the active tab, and the tab itself if it's not the only one
+    fn close_tab(&mut self) {
+        self.close_file();
+        if self.tabs.len() < 2 {
+            return;
+        }
+        self.tabs.remove(self.active_tab);
+        let idx = self.active_tab.min(self.tabs.len() - 1);
+        // The leftover state of the closed tab is dropped along with `session`
+        let mut session = mem::take(&mut self.tabs[idx]);
+        self.swap_session(&mut session);
+        self.active_tab = idx;
+        self.file_changed();
+    }
+
+    /// Close the files of all tabs
+    pub(crate) fn close_all_tabs(&mut self) {
+        for idx in 0..self.tabs.len() {
+            self.switch_tab(idx);
+            self.close_file();
+        }
+    }
+
+    /// Title of the tab at `idx`, for the tab bar
+    pub(crate) fn tab_title(&self, idx: usize) -> String {
+        let args = if idx == self.active_tab {
+            &self.args
+        } else {
+            &self.tabs[idx].args
+        };
+        match &args.src.file {
+            Some(path) if path.as_os_str() == "-" => "stdin".into(),
+            Some(path) => path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            ),
+            None => "(empty)".into(),
+        }
+    }
+
+    /// The window title: the open source, marked with `*` if it has unsaved changes
+    pub(crate) fn window_title(&self) -> String {
+        let Some(src) = &self.source else {
+            return "Hexerator".into();
+        };
+        let name = match &self.args.src.file {
+            Some(path) if path.as_os_str() == "-" => "stdin".into(),
+            Some(path) => path.display().to_string(),
+            None => "(no file)".into(),
+        };
+        let status = if src.attr.stream {
+            " (stream)"
+        } else if !src.attr.permissions.write {
+            " (read-only)"
+        } else {
+            ""
+        };
+        let dirty = if self.has_unsaved_changes() { "*" } else { "" };
+        format!("{}{}{} - Hexerator", dirty, name, status)
+    }
+
+    fn reopen_last_closed(&mut self, font: &Font) {
+        let Some(closed) = self.last_closed.clone() else {
+            msg_warn("No recently closed file");
+            return;
+        };
+        if msg_if_fail(self.load_file_args(closed.args, font), "Failed to load file").is_none()
+            && closed.cursor < self.data.len()
+        {
+            self.center_view_on_offset(closed.cursor);
+            self.edit_state.cursor = closed.cursor;
+            self.hex_ui.flash_cursor();
+        }
     }
 
     pub(crate) fn restore_backup(&mut self) -> Result<(), anyhow::Error> {
@@ -380,14 +940,44 @@ impl App {
                 &self.meta_state.meta.low.regions,
             ) {
                 return Some((
-                    self.meta_state.meta.low.perspectives[view.view.perspective]
-                        .byte_offset_of_row_col(row, col, &self.meta_state.meta.low.regions),
+                    view.view.byte_offset_of_row_col(
+                        row,
+                        col,
+                        &self.meta_state.meta.low.perspectives,
+                        &self.meta_state.meta.low.regions,
+                    ),
                     view_key,
                 ));
             }
         }
         None
     }
+    /// Zoom the focused view in or out, keeping the cursor centered
+    pub(crate) fn zoom_focused_view(&mut self, delta: i16, font: &Font) {
+        let Some(key) = self.hex_ui.focused_view else { return };
+        let view = &mut self.meta_state.meta.views[key].view;
+        view.zoom(delta, font);
+        view.center_on_offset(
+            self.edit_state.cursor,
+            &self.meta_state.meta.low.perspectives,
+            &self.meta_state.meta.low.regions,
+        );
+    }
+
+    /// Move the layout divider being dragged to follow the mouse
+    pub(crate) fn drag_divider(&mut self, mp: ViewportVec) {
+        let Some((divider, last)) = &mut self.hex_ui.divider_drag else { return };
+        let Some(layout) = self.meta_state.meta.layouts.get_mut(self.hex_ui.current_layout) else {
+            return;
+        };
+        let delta = match divider {
+            Divider::Row(_) => mp.y - last.y,
+            Divider::Col { .. } => mp.x - last.x,
+        };
+        layout.drag_divider(&self.meta_state.meta.views, *divider, delta);
+        *last = mp;
+    }
+
     pub fn view_idx_at_pos(&self, x: i16, y: i16) -> Option<ViewKey> {
         let layout = &self.meta_state.meta.layouts[self.hex_ui.current_layout];
         for view_key in layout.iter() {
@@ -405,24 +995,40 @@ impl App {
         if !temp {
             self.meta_state.current_meta_path = path;
             self.meta_state.clean_meta = self.meta_state.meta.clone();
+            self.meta_state.sidecar_load_failed = false;
         }
         Ok(())
     }
 
     pub(crate) fn load_file_args(&mut self, mut args: Args, font: &Font) -> anyhow::Result<()> {
+        msg_if_fail(self.save_sidecar_meta(), "Failed to save metafile sidecar");
         if load_file_from_src_args(
             &mut args.src,
             &mut self.cfg,
             &mut self.source,
             &mut self.data,
         ) {
+            self.file_changed();
             if !self.preferences.keep_meta {
                 self.new_file_readjust(font);
                 if let Some(meta_path) = &args.meta {
                     self.consume_meta_from_file(meta_path.clone())?;
+                } else if self.cfg.sidecar_meta
+                    && let Some(path) = sidecar_meta_path(&args.src)
+                    && path.exists()
+                {
+                    self.load_sidecar_meta(path);
                 }
             }
+            if self.cfg.base_address_from_filename
+                && self.meta_state.meta.misc.display_base_address == 0
+                && let Some(path) = &args.src.file
+                && let Some(base) = offset_from_filename(path)
+            {
+                self.meta_state.meta.misc.display_base_address = base;
+            }
             self.args = args;
+            self.take_pristine_snapshot();
             if let Some(offset) = self.args.src.jump {
                 self.center_view_on_offset(offset);
                 self.edit_state.cursor = offset;
@@ -431,6 +1037,43 @@ impl App {
         }
         Ok(())
     }
+    /// Remember the current data as unmodified, if marking dirty bytes is enabled
+    pub(crate) fn take_pristine_snapshot(&mut self) {
+        self.edit_state.pristine = self.preferences.mark_dirty_bytes.then(|| self.data.clone());
+    }
+    fn load_sidecar_meta(&mut self, path: PathBuf) {
+        if let Err(e) = self.consume_meta_from_file(path.clone()) {
+            // Most likely written by an incompatible version.
+            // Leave it alone, and don't overwrite it on the next save.
+            self.meta_state.sidecar_load_failed = true;
+            msg_warn(&format!(
+                "Failed to load metafile sidecar {}: {:?}\n\
+                 It won't be saved to automatically until it's loaded or saved explicitly.",
+                path.display(),
+                e
+            ));
+        }
+    }
+    /// Save the metadata to the sidecar file of the current source if enabled, and
+    /// if there were any changes
+    pub(crate) fn save_sidecar_meta(&mut self) -> anyhow::Result<()> {
+        if !self.cfg.sidecar_meta || self.source.is_none() || self.meta_state.sidecar_load_failed
+        {
+            return Ok(());
+        }
+        let Some(path) = sidecar_meta_path(&self.args.src) else { return Ok(()) };
+        // Don't hijack changes to a metafile that was explicitly loaded from elsewhere
+        let cur_path = &self.meta_state.current_meta_path;
+        if !cur_path.as_os_str().is_empty() && *cur_path != path {
+            return Ok(());
+        }
+        if rmp_serde::to_vec(&self.meta_state.meta)?
+            == rmp_serde::to_vec(&self.meta_state.clean_meta)?
+        {
+            return Ok(());
+        }
+        self.save_meta_to_file(path, false)
+    }
     /// Called every frame
     pub(crate) fn update(&mut self) {
         if !self.hex_ui.current_layout.is_null() {
@@ -443,10 +1086,10 @@ impl App {
                 &self.meta_state.meta.low.regions,
             );
         }
-        if self.preferences.auto_save && self.edit_state.dirty_region.is_some() {
-            if let Err(e) = self.save() {
-                per_msg!("Save fail: {}", e);
-            }
+        if self.last_auto_save.elapsed().as_millis()
+            >= u128::from(self.preferences.auto_save_interval_ms)
+        {
+            self.auto_save();
         }
         if self.preferences.auto_reload
             && self.last_reload.elapsed().as_millis()
@@ -458,6 +1101,23 @@ impl App {
             self.last_reload = Instant::now();
         }
     }
+    /// Save the accumulated dirty region, if auto save is enabled
+    pub(crate) fn auto_save(&mut self) {
+        if self.preferences.auto_save && self.edit_state.dirty_region.is_some() {
+            if let Err(e) = self.save() {
+                per_msg!("Save fail: {}", e);
+            }
+            self.last_auto_save = Instant::now();
+        }
+    }
+    /// `offset` as it's displayed to the user, with the display base address added
+    pub(crate) fn display_offset(&self, offset: usize) -> u64 {
+        self.meta_state
+            .meta
+            .misc
+            .display_base_address
+            .wrapping_add(offset as u64)
+    }
     pub(crate) fn focused_view_select_all(&mut self) {
         if let Some(view) = self.hex_ui.focused_view {
             let p_key = self.meta_state.meta.views[view].view.perspective;
@@ -485,11 +1145,188 @@ impl App {
             }
         }
         gui.file_diff_result_window.diff_entries = diff_entries;
+        gui.file_diff_result_window.file_len = file_data.len();
         gui.file_diff_result_window.path = path;
+        gui.file_diff_result_window.highlighted = None;
         gui.file_diff_result_window.open.set(true);
         Ok(())
     }
 
+    pub(crate) fn load_reference_file(&mut self, path: PathBuf) -> anyhow::Result<()> {
+        let data = read_source_to_buf(&path, &self.args.src)?;
+        self.reference = Some(ReferenceFile { path, data });
+        Ok(())
+    }
+
+    /// Move the cursor to the next (or previous) byte that differs from the reference file
+    pub(crate) fn jump_to_reference_diff(&mut self, forward: bool) {
+        let Some(reference) = &self.reference else {
+            msg_warn("No reference file loaded");
+            return;
+        };
+        let cursor = self.edit_state.cursor;
+        let Some(off) = self.data.find_difference(&reference.data, cursor, forward) else {
+            msg_warn("No more differences from the reference file");
+            return;
+        };
+        self.center_view_on_offset(off);
+        self.edit_state.set_cursor(off);
+        self.hex_ui.flash_cursor();
+    }
+
+    /// Apply `op` to the selection (or the cursor), and remember it for repeating
+    pub(crate) fn apply_op(&mut self, op: EditOp) -> anyhow::Result<()> {
+        match &op {
+            EditOp::PatternFill(pattern) => self.modify_selection(|data, _| {
+                data.pattern_fill(pattern);
+                Ok(())
+            })?,
+            EditOp::RandomFill(seed) => self.modify_selection(|data, _| {
+                match seed {
+                    Some(seed) => StdRng::seed_from_u64(*seed).fill_bytes(data),
+                    None => thread_rng().fill_bytes(data),
+                }
+                Ok(())
+            })?,
+            EditOp::LuaFill(script) => self.modify_selection(|data, lua| {
+                lua.context(|ctx| {
+                    let f = ctx.load(script).eval::<Function>()?;
+                    for (i, b) in data.iter_mut().enumerate() {
+                        *b = f.call((i, *b))?;
+                    }
+                    Ok(())
+                })
+            })?,
+            EditOp::ToggleBit(bit) => self.toggle_bit(self.edit_state.cursor, *bit),
+        }
+        self.last_op = Some(op);
+        Ok(())
+    }
+
+    /// Run `f` on the selected bytes, logging the edit and marking them dirty
+    fn modify_selection(
+        &mut self,
+        f: impl FnOnce(&mut [u8], &Lua) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let sel = self.hex_ui.selection().context("No active selection")?;
+        let range = sel.begin..=sel.end;
+        let old = self.data[range.clone()].to_vec();
+        let res = f(&mut self.data[range.clone()], &self.lua);
+        // Even a failed script might have modified some bytes before failing
+        self.edit_state.log_edit(sel.begin, &old, &self.data[range.clone()]);
+        self.edit_state.widen_dirty_region(DamageRegion::RangeInclusive(range));
+        res
+    }
+
+    /// Apply the last applied [`EditOp`] again, at the current selection or cursor
+    pub(crate) fn repeat_last_op(&mut self) {
+        let Some(op) = self.last_op.clone() else { return };
+        if let EditOp::RandomFill(_) = op
+            && let Some(sel) = self.hex_ui.selection()
+            && sel.len() > RANDOM_FILL_CONFIRM_THRESHOLD
+        {
+            msg_warn("Large random fills have to be confirmed, use Edit > Random fill... instead");
+            return;
+        }
+        msg_if_fail(self.apply_op(op), "Failed to repeat operation");
+    }
+
+    /// Flip bit `bit` (0 = least significant) of the byte at `offset`
+    fn toggle_bit(&mut self, offset: usize, bit: u8) {
+        let Some(&old) = self.data.get(offset) else { return };
+        let new = old ^ (1 << bit);
+        self.edit_state.log_edit(offset, &[old], &[new]);
+        self.data[offset] = new;
+        self.edit_state.widen_dirty_region(DamageRegion::Single(offset));
+    }
+
+    /// Apply a color preset to the background and every view, and remember it
+    pub(crate) fn apply_color_preset(&mut self, preset: ColorPreset) {
+        self.preferences.bg_color = preset.bg_color();
+        for view in self.meta_state.meta.views.values_mut() {
+            preset.apply(&mut view.view.presentation);
+        }
+        self.cfg.color_preset = preset;
+    }
+
+    /// Move the cursor to the next (or previous) byte that isn't the skip value
+    pub(crate) fn skip_to_different_byte(&mut self, forward: bool) {
+        let skip = self.preferences.skip_value;
+        let Some(off) = self.data.find_other_than(self.edit_state.cursor, skip, forward) else {
+            msg_warn(&format!("No byte other than {:02X} found", skip));
+            return;
+        };
+        self.center_view_on_offset(off);
+        self.edit_state.set_cursor(off);
+        self.hex_ui.flash_cursor();
+    }
+
+    /// Jump the cursor to the beginning (or end) of the innermost region containing it
+    pub(crate) fn jump_to_region_boundary(&mut self, end: bool) {
+        let regions = &self.meta_state.meta.low.regions;
+        let Some(key) = find_most_specific_region_for_offset(regions, self.edit_state.cursor) else {
+            msg_warn("Cursor is not inside any region");
+            return;
+        };
+        let reg = regions[key].region;
+        let off = if end { reg.end } else { reg.begin };
+        self.center_view_on_offset(off);
+        self.edit_state.set_cursor(off);
+        self.hex_ui.flash_cursor();
+    }
+
+    /// Move the cursor to the first (or last) byte of its row in the focused view
+    pub(crate) fn cursor_to_row_edge(&mut self, end: bool) {
+        let Some(key) = self.hex_ui.focused_view else { return };
+        let low = &self.meta_state.meta.low;
+        let per = &low.perspectives[self.meta_state.meta.views[key].view.perspective];
+        let (row, _) = per.row_col_of_byte_offset(self.edit_state.cursor, &low.regions);
+        let col = if end { per.cols.saturating_sub(1) } else { 0 };
+        let last = low.regions[per.region].region.end.min(self.data.len().saturating_sub(1));
+        let off = per.byte_offset_of_row_col(row, col, &low.regions).min(last);
+        self.edit_state.set_cursor_no_history(off);
+    }
+
+    /// The bookmarks inside the region containing the cursor, sorted by offset.
+    ///
+    /// These act as the fields of the region, for field navigation.
+    fn field_bookmarks(&self) -> Vec<&Bookmark> {
+        let meta = &self.meta_state.meta;
+        let cursor = self.edit_state.cursor;
+        let Some(key) = find_most_specific_region_for_offset(&meta.low.regions, cursor) else {
+            return Vec::new();
+        };
+        let reg = meta.low.regions[key].region;
+        let mut fields: Vec<&Bookmark> =
+            meta.bookmarks.iter().filter(|bm| reg.contains(bm.offset)).collect();
+        fields.sort_by_key(|bm| bm.offset);
+        fields
+    }
+
+    /// The field (bookmark) that the cursor is in, if any
+    pub(crate) fn field_at_cursor(&self) -> Option<&Bookmark> {
+        let cursor = self.edit_state.cursor;
+        self.field_bookmarks()
+            .into_iter()
+            .take_while(|bm| bm.offset <= cursor)
+            .last()
+    }
+
+    /// Jump to the next (or previous) field of the region containing the cursor
+    pub(crate) fn jump_to_field(&mut self, next: bool) {
+        let cursor = self.edit_state.cursor;
+        let fields = self.field_bookmarks();
+        let target = if next {
+            fields.iter().find(|bm| bm.offset > cursor)
+        } else {
+            fields.iter().rev().find(|bm| bm.offset < cursor)
+        };
+        let Some(off) = target.map(|bm| bm.offset) else { return };
+        self.center_view_on_offset(off);
+        self.edit_state.set_cursor(off);
+        self.hex_ui.flash_cursor();
+    }
+
     pub(crate) fn switch_layout(app_hex_ui: &mut HexUi, app_meta: &Meta, k: LayoutKey) {
         app_hex_ui.current_layout = k;
         // Set focused view to the first available view in the layout
@@ -534,6 +1371,32 @@ impl App {
         }
     }
 
+    /// Focus the next view in the layout that has a different editable kind than the focused one
+    /// (e.g. hex <-> text). The cursor stays where it is, so the same byte can be edited through
+    /// another representation.
+    pub(crate) fn toggle_edit_target(&mut self) {
+        let Some(focused_key) = self.hex_ui.focused_view else { return };
+        let layout = &self.meta_state.meta.layouts[self.hex_ui.current_layout];
+        let views = &self.meta_state.meta.views;
+        let focused_kind = discriminant(&views[focused_key].view.kind);
+        let keys: Vec<ViewKey> = layout.iter().collect();
+        let Some(pos) = keys.iter().position(|&k| k == focused_key) else { return };
+        let new_key = keys
+            .iter()
+            .cycle()
+            .skip(pos + 1)
+            .take(keys.len() - 1)
+            .find(|&&k| {
+                let kind = &views[k].view.kind;
+                !matches!(kind, ViewKind::Block | ViewKind::Bits(_))
+                    && discriminant(kind) != focused_kind
+            });
+        if let Some(&new_key) = new_key {
+            self.meta_state.meta.views[focused_key].view.cancel_editing();
+            self.hex_ui.focused_view = Some(new_key);
+        }
+    }
+
     pub(crate) fn load_proc_memory(
         &mut self,
         pid: sysinfo::Pid,
@@ -555,6 +1418,7 @@ impl App {
         self.meta_state.meta = meta;
         self.meta_state.clean_meta = self.meta_state.meta.clone();
         self.meta_state.current_meta_path = path;
+        self.meta_state.sidecar_load_failed = false;
         self.meta_state.meta.post_load_init();
         // Switch to first layout, if there is one
         if let Some(layout_key) = self.meta_state.meta.layouts.keys().next() {
@@ -589,6 +1453,9 @@ fn load_proc_memory_linux(
                 take: Some(size),
                 read_only: !is_write,
                 stream: false,
+                ignore_read_only_exts: false,
+                no_canonicalize: false,
+                decompress: false,
             },
             recent: false,
             meta: None,
@@ -600,7 +1467,7 @@ fn load_proc_memory_linux(
 pub fn read_source_to_buf(path: &Path, args: &SourceArgs) -> Result<Vec<u8>, anyhow::Error> {
     let mut f = std::fs::File::open(path)?;
     if let &Some(to) = &args.hard_seek {
-        f.seek(std::io::SeekFrom::Current(to as i64))?;
+        f.seek(std::io::SeekFrom::Start(u64::try_from(to)?))?;
     }
     #[expect(
         clippy::cast_possible_truncation,
@@ -618,6 +1485,19 @@ pub struct FileDiffEntry {
     pub offset: usize,
 }
 
+/// Path of the metadata file that is kept next to the source file.
+///
+/// Sources with a hard seek or take (e.g. process memory) don't get one, since their offsets
+/// depend on those arguments.
+pub fn sidecar_meta_path(src_args: &SourceArgs) -> Option<PathBuf> {
+    if src_args.hard_seek.is_some() || src_args.take.is_some() || src_args.stream {
+        return None;
+    }
+    let mut path = src_args.file.as_ref()?.clone().into_os_string();
+    path.push(".hexerator_meta");
+    Some(path.into())
+}
+
 pub fn temp_metafile_backup_path() -> PathBuf {
     std::env::temp_dir().join("hexerator_meta_backup.meta")
 }
@@ -679,10 +1559,17 @@ fn load_file_from_src_args(
             });
             true
         } else {
+            if !src_args.ignore_read_only_exts && cfg.is_read_only_path(file_arg) {
+                src_args.read_only = true;
+            }
+            // Saving would have to recompress, which isn't guaranteed to give the same bytes
+            if src_args.decompress {
+                src_args.read_only = true;
+            }
             let result: Result<(), anyhow::Error> = try {
-                let mut file = open_file(file_arg, src_args.read_only)?;
+                let mut file = open_file(file_arg, &mut src_args.read_only)?;
                 data.clear();
-                if let Some(path) = &mut src_args.file {
+                if !src_args.no_canonicalize && let Some(path) = &mut src_args.file {
                     match path.canonicalize() {
                         Ok(canon) => *path = canon,
                         Err(e) => msg_warn(&format!(
@@ -695,7 +1582,7 @@ fn load_file_from_src_args(
                 }
                 cfg.recent.use_(src_args.clone());
                 if !src_args.stream {
-                    *data = read_contents(&*src_args, &mut file)?;
+                    *data = read_contents(&*src_args, &mut file, cfg.max_read_size())?;
                 }
                 *source = Some(Source {
                     provider: SourceProvider::File(file),
@@ -723,16 +1610,61 @@ fn load_file_from_src_args(
     }
 }
 
-fn open_file(path: &Path, read_only: bool) -> Result<File, anyhow::Error> {
-    OpenOptions::new()
-        .read(true)
-        .write(!read_only)
-        .open(path)
-        .context("Failed to open file")
+/// Open `path`, falling back to read-only access if it can't be opened for writing,
+/// e.g. because another process holds it open.
+///
+/// `read_only` is set if the fallback was taken.
+fn open_file(path: &Path, read_only: &mut bool) -> Result<File, anyhow::Error> {
+    if !*read_only {
+        match OpenOptions::new().read(true).write(true).open(path) {
+            Ok(file) => return Ok(file),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(e).context("Failed to open file");
+            }
+            Err(e) => {
+                msg_warn(&format!(
+                    "Couldn't open {} for writing ({}), opening it read-only",
+                    path.display(),
+                    e
+                ));
+                *read_only = true;
+            }
+        }
+    }
+    let mut opts = OpenOptions::new();
+    opts.read(true);
+    // Don't get in the way of other processes using the file while it's open here.
+    // FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE
+    #[cfg(windows)]
+    std::os::windows::fs::OpenOptionsExt::share_mode(&mut opts, 0x7);
+    opts.open(path).map_err(|e| {
+        if is_sharing_violation(&e) {
+            anyhow::Error::new(e).context(
+                "The file is locked by another process, which doesn't allow even reading it",
+            )
+        } else {
+            anyhow::Error::new(e).context("Failed to open file")
+        }
+    })
+}
+
+/// Whether `e` means that another process holds the file open without sharing it
+fn is_sharing_violation(e: &std::io::Error) -> bool {
+    // ERROR_SHARING_VIOLATION, ERROR_LOCK_VIOLATION
+    cfg!(windows) && matches!(e.raw_os_error(), Some(32 | 33))
 }
 
-fn read_contents(args: &SourceArgs, file: &mut File) -> anyhow::Result<Vec<u8>> {
+fn read_contents(args: &SourceArgs, file: &mut File, max_size: u64) -> anyhow::Result<Vec<u8>> {
+    if args.decompress {
+        return read_decompressed(args, file, max_size);
+    }
     let seek = args.hard_seek.unwrap_or(0);
+    // Check up front, so huge files are refused instead of running out of memory
+    let mut len = file.metadata()?.len().saturating_sub(seek as u64);
+    if let Some(amount) = args.take {
+        len = len.min(amount as u64);
+    }
+    check_read_size(len, max_size)?;
     file.seek(SeekFrom::Start(seek as u64))?;
     let mut data = Vec::new();
     match args.take {
@@ -741,3 +1673,51 @@ fn read_contents(args: &SourceArgs, file: &mut File) -> anyhow::Result<Vec<u8>>
     };
     Ok(data)
 }
+
+/// Refuse to read `len` bytes if that's over `max_size` (0 means unlimited)
+fn check_read_size(len: u64, max_size: u64) -> anyhow::Result<()> {
+    if max_size != 0 && len > max_size {
+        bail!(
+            "File is too large to load into memory ({} MiB, limit is {} MiB).\n\
+             Use --take and --hard-seek to open a part of it, \
+             or raise the limit in the preferences.",
+            len / (1024 * 1024),
+            max_size / (1024 * 1024)
+        );
+    }
+    Ok(())
+}
+
+/// Read the whole file and decompress it, falling back to the raw bytes if that fails.
+///
+/// Hard seek and take apply to the decompressed data.
+fn read_decompressed(
+    args: &SourceArgs,
+    file: &mut File,
+    max_size: u64,
+) -> anyhow::Result<Vec<u8>> {
+    // The compressed file is read as a whole, so it's subject to the limit as well
+    check_read_size(file.metadata()?.len(), max_size)?;
+    file.seek(SeekFrom::Start(0))?;
+    let mut raw = Vec::new();
+    file.read_to_end(&mut raw)?;
+    let mut data = match compression::decompress(&raw, max_size) {
+        Ok(Some(data)) => data,
+        Ok(None) => {
+            msg_warn("File doesn't look gzip or zlib compressed. Showing the raw bytes.");
+            raw
+        }
+        Err(e) => {
+            msg_warn(&format!(
+                "Failed to decompress file: {:#}\nShowing the raw bytes.",
+                e
+            ));
+            raw
+        }
+    };
+    data.drain(..args.hard_seek.unwrap_or(0).min(data.len()));
+    if let Some(amount) = args.take {
+        data.truncate(amount);
+    }
+    Ok(data)
+}
@@ -0,0 +1,33 @@
+//! Mutating operations that can be repeated with "Repeat last operation"
+
+/// Random fills of more bytes than this have to be confirmed explicitly
+pub const RANDOM_FILL_CONFIRM_THRESHOLD: usize = 1024 * 1024;
+
+/// A data modifying operation, along with its parameters
+#[derive(Debug, Clone)]
+pub enum EditOp {
+    /// Fill the selection with a repeating byte pattern
+    PatternFill(Vec<u8>),
+    /// Fill the selection with random bytes, reproducibly if a seed is given
+    RandomFill(Option<u64>),
+    /// Replace each byte of the selection with the result of a Lua function
+    LuaFill(String),
+    /// Toggle a bit (0 = least significant) of the byte at the cursor
+    ToggleBit(u8),
+}
+
+impl EditOp {
+    /// Short human readable description, for menus
+    pub fn describe(&self) -> String {
+        match self {
+            Self::PatternFill(pattern) => {
+                let hex: Vec<String> = pattern.iter().map(|b| format!("{:02X}", b)).collect();
+                format!("Pattern fill ({})", hex.join(" "))
+            }
+            Self::RandomFill(None) => "Random fill".into(),
+            Self::RandomFill(Some(seed)) => format!("Random fill (seed {})", seed),
+            Self::LuaFill(_) => "Lua fill".into(),
+            Self::ToggleBit(bit) => format!("Toggle bit {}", bit),
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use {
-    crate::{damage_region::DamageRegion, meta::region::Region},
+    crate::{damage_region::DamageRegion, edit_log::EditLog, meta::region::Region, shell::msg_warn},
     gamedebug_core::per_msg,
 };
 
@@ -10,6 +10,12 @@ pub struct EditState {
     cursor_history: Vec<usize>,
     cursor_history_current: usize,
     pub dirty_region: Option<Region>,
+    /// Whether the length of the data changed since the last save (inserting/deleting bytes)
+    pub len_changed: bool,
+    /// When set, committed edits are appended to this log
+    pub edit_log: Option<EditLog>,
+    /// Copy of the data as of the last load/save, for marking modified bytes
+    pub pristine: Option<Vec<u8>>,
 }
 
 impl EditState {
@@ -55,6 +61,25 @@ impl EditState {
             false
         }
     }
+    /// Record a committed edit in the edit log, if there is one
+    pub(crate) fn log_edit(&mut self, offset: usize, old: &[u8], new: &[u8]) {
+        let Some(log) = &mut self.edit_log else { return };
+        if old == new {
+            return;
+        }
+        if let Err(e) = log.log(offset, old, new) {
+            msg_warn(&format!("Failed to write edit log, disabling it: {}", e));
+            self.edit_log = None;
+        }
+    }
+    /// Whether the byte at `offset` differs from the last loaded/saved data.
+    ///
+    /// Always false if there is no pristine copy to compare against.
+    pub(crate) fn is_modified(&self, offset: usize, data: &[u8]) -> bool {
+        let Some(pristine) = &self.pristine else { return false };
+        self.dirty_region.is_some_and(|reg| reg.contains(offset))
+            && pristine.get(offset) != data.get(offset)
+    }
     pub(crate) fn widen_dirty_region(&mut self, damage: DamageRegion) {
         match &mut self.dirty_region {
             Some(dirty_region) => {
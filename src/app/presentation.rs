@@ -1,5 +1,5 @@
 use {
-    crate::color::ColorMethod,
+    crate::color::{ColorMethod, Palette},
     egui_sfml::sfml::graphics::Color,
     serde::{Deserialize, Serialize},
     serde_with::{serde_as, FromInto},
@@ -16,6 +16,110 @@ pub struct Presentation {
     pub cursor_color: Color,
     #[serde_as(as = "FromInto<MyColor>")]
     pub cursor_active_color: Color,
+    /// Display hex digits in lowercase
+    #[serde(default)]
+    pub lowercase_hex: bool,
+    /// Background color of find results
+    #[serde_as(as = "FromInto<MyColor>")]
+    #[serde(default = "default_find_result_color")]
+    pub find_result_color: Color,
+}
+
+fn default_find_result_color() -> Color {
+    Color::rgb(40, 80, 120)
+}
+
+/// A built-in set of colors for the background and the views
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ColorPreset {
+    #[default]
+    Default,
+    HighContrast,
+    Light,
+    /// Blue/orange hues that stay distinguishable with red-green color blindness
+    Deuteranopia,
+}
+
+impl ColorPreset {
+    pub const ALL: [Self; 4] = [
+        Self::Default,
+        Self::HighContrast,
+        Self::Light,
+        Self::Deuteranopia,
+    ];
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Default => "Default",
+            Self::HighContrast => "High contrast (dark)",
+            Self::Light => "Light",
+            Self::Deuteranopia => "Deuteranopia friendly",
+        }
+    }
+    pub fn bg_color(self) -> [f32; 3] {
+        match self {
+            Self::Default | Self::HighContrast | Self::Deuteranopia => [0.0; 3],
+            Self::Light => [0.95; 3],
+        }
+    }
+    /// Set the colors of `presentation` to the ones of this preset
+    pub fn apply(self, presentation: &mut Presentation) {
+        let default = Presentation::default();
+        let (color_method, invert_color, sel, find, cursor, cursor_active) = match self {
+            Self::Default => (
+                default.color_method,
+                default.invert_color,
+                default.sel_color,
+                default.find_result_color,
+                default.cursor_color,
+                default.cursor_active_color,
+            ),
+            Self::HighContrast => (
+                ColorMethod::Mono,
+                false,
+                Color::rgb(0, 70, 180),
+                Color::rgb(130, 100, 0),
+                Color::rgb(200, 200, 0),
+                Color::YELLOW,
+            ),
+            // Inverted white is black text on the light background
+            Self::Light => (
+                ColorMethod::Mono,
+                true,
+                Color::rgb(180, 190, 230),
+                Color::rgb(240, 210, 140),
+                Color::rgb(110, 110, 110),
+                Color::BLACK,
+            ),
+            Self::Deuteranopia => (
+                ColorMethod::Custom(Box::new(deuteranopia_palette())),
+                false,
+                Color::rgb(0, 60, 110),
+                Color::rgb(110, 55, 0),
+                Color::rgb(160, 160, 160),
+                Color::WHITE,
+            ),
+        };
+        presentation.color_method = color_method;
+        presentation.invert_color = invert_color;
+        presentation.sel_color = sel;
+        presentation.find_result_color = find;
+        presentation.cursor_color = cursor;
+        presentation.cursor_active_color = cursor_active;
+    }
+}
+
+/// Sky blue for printable ascii, orange for other bytes, gray/white for 0x00/0xFF
+fn deuteranopia_palette() -> Palette {
+    let mut pal = Palette([[0; 3]; 256]);
+    for (byte, rgb) in (0..=255u8).zip(pal.0.iter_mut()) {
+        *rgb = match byte {
+            0x00 => [100, 100, 100],
+            0xFF => [255, 255, 255],
+            b if b.is_ascii_graphic() || b == b' ' => [86, 180, 233],
+            _ => [230, 159, 0],
+        };
+    }
+    pal
 }
 
 #[derive(Serialize, Deserialize)]
@@ -46,6 +150,8 @@ impl Default for Presentation {
             sel_color: Color::rgb(75, 75, 75),
             cursor_color: Color::rgb(160, 160, 160),
             cursor_active_color: Color::WHITE,
+            lowercase_hex: false,
+            find_result_color: default_find_result_color(),
         }
     }
 }
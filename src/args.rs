@@ -38,4 +38,16 @@ pub struct SourceArgs {
     /// Specify source as a streaming source (for example, standard streams).
     /// Sets read-only attribute.
     pub stream: bool,
+    /// Don't force read-only mode based on the configured read-only extensions
+    #[clap(long)]
+    #[serde(default)]
+    pub ignore_read_only_exts: bool,
+    /// Keep the file path as given, instead of resolving it to a canonical path
+    #[clap(long)]
+    #[serde(default)]
+    pub no_canonicalize: bool,
+    /// Decompress gzip/zlib compressed files on open. Sets read-only attribute.
+    #[clap(long)]
+    #[serde(default)]
+    pub decompress: bool,
 }
@@ -0,0 +1,76 @@
+//! Export of regions and bookmarks as `#define`s of a C header
+
+use {
+    crate::meta::Meta,
+    std::{collections::HashSet, fmt::Write},
+};
+
+/// Generate a C header defining the offsets (and sizes) of all regions and bookmarks
+pub fn generate(meta: &Meta, hex: bool) -> String {
+    let fmt = |n: usize| {
+        if hex {
+            format!("0x{:X}", n)
+        } else {
+            n.to_string()
+        }
+    };
+    let mut used = HashSet::new();
+    let mut out = String::from("#pragma once\n\n/* Regions */\n");
+    let mut regions: Vec<_> = meta.low.regions.values().collect();
+    regions.sort_by_key(|reg| (reg.region.begin, reg.region.end));
+    for reg in regions {
+        let name = unique_identifier(&reg.name, &mut used);
+        let _ = writeln!(out, "#define {}_OFFSET {}", name, fmt(reg.region.begin));
+        let _ = writeln!(out, "#define {}_SIZE {}", name, fmt(reg.region.len()));
+    }
+    out.push_str("\n/* Bookmarks */\n");
+    let mut bookmarks: Vec<_> = meta.bookmarks.iter().collect();
+    bookmarks.sort_by_key(|bm| bm.offset);
+    for bm in bookmarks {
+        let name = unique_identifier(&bm.label, &mut used);
+        let _ = writeln!(out, "#define {}_OFFSET {}", name, fmt(bm.offset));
+    }
+    out
+}
+
+/// Turn `name` into an upper case C identifier that isn't in `used` yet
+fn unique_identifier(name: &str, used: &mut HashSet<String>) -> String {
+    let base = c_identifier(name);
+    let mut ident = base.clone();
+    let mut n = 2;
+    while !used.insert(ident.clone()) {
+        ident = format!("{}_{}", base, n);
+        n += 1;
+    }
+    ident
+}
+
+/// Sanitize `name` into a valid upper case C identifier
+fn c_identifier(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !ident.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        ident.insert(0, '_');
+    }
+    ident
+}
+
+#[test]
+fn test_c_identifier() {
+    assert_eq!(c_identifier("header"), "HEADER");
+    assert_eq!(c_identifier("file size (le)"), "FILE_SIZE__LE_");
+    assert_eq!(c_identifier("3d data"), "_3D_DATA");
+    assert_eq!(c_identifier(""), "_");
+    let mut used = HashSet::new();
+    assert_eq!(unique_identifier("a b", &mut used), "A_B");
+    assert_eq!(unique_identifier("a-b", &mut used), "A_B_2");
+    assert_eq!(unique_identifier("a.b", &mut used), "A_B_3");
+}
@@ -13,6 +13,8 @@ pub enum ColorMethod {
     Vga13h,
     Grayscale,
     Custom(Box<Palette>),
+    HighNibble,
+    LowNibble,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -48,6 +50,8 @@ impl ColorMethod {
                 let [r, g, b] = pal.0[byte as usize];
                 Color::rgb(r, g, b)
             }
+            ColorMethod::HighNibble => NIBBLE_COLORS[usize::from(byte >> 4)],
+            ColorMethod::LowNibble => NIBBLE_COLORS[usize::from(byte & 0x0f)],
         };
         if invert {
             invert_color(color)
@@ -64,6 +68,8 @@ impl ColorMethod {
             ColorMethod::Vga13h => "VGA 13h",
             ColorMethod::Grayscale => "grayscale",
             ColorMethod::Custom(_) => "custom",
+            ColorMethod::HighNibble => "high nibble",
+            ColorMethod::LowNibble => "low nibble",
         }
     }
 }
@@ -91,6 +97,26 @@ fn rgb332_color(byte: u8) -> Color {
     Color::rgb((r >> 5) * 32, (g >> 2) * 32, b * 64)
 }
 
+/// 16 evenly spaced hues, one for each nibble value
+const NIBBLE_COLORS: [Color; 16] = [
+    Color::rgb(255, 0, 0),
+    Color::rgb(255, 96, 0),
+    Color::rgb(255, 191, 0),
+    Color::rgb(223, 255, 0),
+    Color::rgb(128, 255, 0),
+    Color::rgb(32, 255, 0),
+    Color::rgb(0, 255, 64),
+    Color::rgb(0, 255, 159),
+    Color::rgb(0, 255, 255),
+    Color::rgb(0, 159, 255),
+    Color::rgb(0, 64, 255),
+    Color::rgb(32, 0, 255),
+    Color::rgb(128, 0, 255),
+    Color::rgb(223, 0, 255),
+    Color::rgb(255, 0, 191),
+    Color::rgb(255, 0, 96),
+];
+
 const VGA_13H_PALETTE: [u32; 256] = [
     0x000000, 0x0000a8, 0x00a800, 0x00a8a8, 0xa80000, 0xa800a8, 0xa85400, 0xa8a8a8, 0x545454,
     0x5454fc, 0x54fc54, 0x54fcfc, 0xfc5454, 0xfc54fc, 0xfcfc54, 0xfcfcfc, 0x000000, 0x141414,
@@ -0,0 +1,76 @@
+//! Transparent decompression of gzip and zlib compressed files
+
+use {
+    anyhow::bail,
+    flate2::read::{MultiGzDecoder, ZlibDecoder},
+    std::io::Read,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Compression {
+    Gzip,
+    Zlib,
+}
+
+impl Compression {
+    /// Detect the compression format from the magic bytes at the start of `data`
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        match *data {
+            [0x1f, 0x8b, ..] => Some(Self::Gzip),
+            // Deflate method, and the header checksum has to be a multiple of 31
+            [cmf, flg, ..] if cmf & 0x0f == 8 && u16::from_be_bytes([cmf, flg]) % 31 == 0 => {
+                Some(Self::Zlib)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Decompress `data` if it's gzip or zlib compressed.
+///
+/// Returns `Ok(None)` if `data` doesn't look compressed.
+/// Fails if the decompressed size would exceed `max_size` (0 means no limit).
+pub fn decompress(data: &[u8], max_size: u64) -> anyhow::Result<Option<Vec<u8>>> {
+    let Some(compression) = Compression::detect(data) else {
+        return Ok(None);
+    };
+    let reader: Box<dyn Read> = match compression {
+        Compression::Gzip => Box::new(MultiGzDecoder::new(data)),
+        Compression::Zlib => Box::new(ZlibDecoder::new(data)),
+    };
+    let limit = if max_size == 0 {
+        u64::MAX
+    } else {
+        max_size.saturating_add(1)
+    };
+    let mut out = Vec::new();
+    reader.take(limit).read_to_end(&mut out)?;
+    if max_size != 0 && out.len() as u64 > max_size {
+        bail!(
+            "Decompressed data is too large to load into memory (limit is {} MiB)",
+            max_size / (1024 * 1024)
+        );
+    }
+    Ok(Some(out))
+}
+
+#[test]
+fn test_decompress() {
+    use {
+        flate2::write::{GzEncoder, ZlibEncoder},
+        std::io::Write,
+    };
+    let payload = b"hello hello hello hello";
+    let mut gz = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    gz.write_all(payload).unwrap();
+    let gz = gz.finish().unwrap();
+    let mut zlib = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    zlib.write_all(payload).unwrap();
+    let zlib = zlib.finish().unwrap();
+    assert_eq!(Compression::detect(&gz), Some(Compression::Gzip));
+    assert_eq!(Compression::detect(&zlib), Some(Compression::Zlib));
+    assert_eq!(decompress(&gz, 0).unwrap().as_deref(), Some(&payload[..]));
+    assert_eq!(decompress(&zlib, 0).unwrap().as_deref(), Some(&payload[..]));
+    assert!(decompress(&gz, 8).is_err());
+    assert_eq!(decompress(payload, 0).unwrap(), None);
+}
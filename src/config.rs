@@ -1,15 +1,63 @@
 use {
-    crate::args::SourceArgs,
+    crate::{
+        app::presentation::ColorPreset, args::SourceArgs, gui::find_dialog::FindType,
+        keybinds::Keybinds,
+    },
     anyhow::Context,
     directories::ProjectDirs,
     recently_used_list::RecentlyUsedList,
     serde::{Deserialize, Serialize},
+    std::path::{Path, PathBuf},
 };
 
 #[derive(Serialize, Deserialize)]
 pub struct Config {
     pub recent: RecentlyUsedList<SourceArgs>,
     pub style: Style,
+    /// Files with these extensions are always opened as read-only
+    #[serde(default)]
+    pub read_only_exts: Vec<String>,
+    /// Pinned entries are never evicted from the recent list, and are listed first
+    #[serde(default)]
+    pub pinned_recent: Vec<SourceArgs>,
+    /// Automatically save/load metadata to/from a file next to the opened file
+    #[serde(default = "default_sidecar_meta")]
+    pub sidecar_meta: bool,
+    #[serde(default)]
+    pub keybinds: Keybinds,
+    /// When set, every committed edit is appended to this file
+    #[serde(default)]
+    pub edit_log_path: Option<PathBuf>,
+    /// Recently searched for needles in the find dialog
+    #[serde(default = "default_recent_needles")]
+    pub recent_needles: RecentlyUsedList<RecentNeedle>,
+    /// Refuse to load files bigger than this many MiB into memory (0 means no limit)
+    #[serde(default = "default_max_read_size_mib")]
+    pub max_read_size_mib: u64,
+    /// The last applied color preset
+    #[serde(default)]
+    pub color_preset: ColorPreset,
+    /// Set the display base address from an offset at the end of the file name on open
+    #[serde(default)]
+    pub base_address_from_filename: bool,
+    /// Byte order used for multi-byte values, unless overridden where they're used
+    #[serde(default)]
+    pub default_big_endian: bool,
+}
+
+/// A needle previously searched for in the find dialog, along with its data type
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct RecentNeedle {
+    pub find_type: FindType,
+    pub needle: String,
+}
+
+const fn default_sidecar_meta() -> bool {
+    true
+}
+
+const fn default_max_read_size_mib() -> u64 {
+    4096
 }
 
 #[derive(Serialize, Deserialize, Default)]
@@ -38,7 +86,14 @@ impl Default for FontSizes {
     }
 }
 
-const DEFAULT_RECENT_CAPACITY: usize = 16;
+const DEFAULT_RECENT_CAPACITY: usize = 20;
+const RECENT_NEEDLES_CAPACITY: usize = 30;
+
+fn default_recent_needles() -> RecentlyUsedList<RecentNeedle> {
+    let mut list = RecentlyUsedList::default();
+    list.set_capacity(RECENT_NEEDLES_CAPACITY);
+    list
+}
 
 impl Default for Config {
     fn default() -> Self {
@@ -47,6 +102,16 @@ impl Default for Config {
         Self {
             recent,
             style: Style::default(),
+            read_only_exts: Vec::new(),
+            pinned_recent: Vec::new(),
+            sidecar_meta: default_sidecar_meta(),
+            keybinds: Keybinds::default(),
+            edit_log_path: None,
+            recent_needles: default_recent_needles(),
+            max_read_size_mib: default_max_read_size_mib(),
+            color_preset: ColorPreset::default(),
+            base_address_from_filename: false,
+            default_big_endian: false,
         }
     }
 }
@@ -85,6 +150,64 @@ impl Config {
         std::fs::write(cfg_dir.join(FILENAME), &bytes)?;
         Ok(())
     }
+    /// Whether `path` should be opened read-only because of its extension
+    pub fn is_read_only_path(&self, path: &Path) -> bool {
+        let Some(ext) = path.extension() else { return false };
+        self.read_only_exts.iter().any(|pat| {
+            ext.eq_ignore_ascii_case(pat.trim_start_matches("*.").trim_start_matches('.'))
+        })
+    }
+    /// Maximum number of bytes to load into memory, or 0 for no limit
+    pub fn max_read_size(&self) -> u64 {
+        self.max_read_size_mib.saturating_mul(1024 * 1024)
+    }
+    pub fn is_pinned(&self, args: &SourceArgs) -> bool {
+        self.pinned_recent.contains(args)
+    }
+    pub fn toggle_pin(&mut self, args: &SourceArgs) {
+        if self.is_pinned(args) {
+            self.pinned_recent.retain(|pinned| pinned != args);
+        } else {
+            self.pinned_recent.push(args.clone());
+        }
+    }
+    /// Remove every recent entry that isn't pinned
+    pub fn clear_unpinned_recent(&mut self) {
+        let pinned = &self.pinned_recent;
+        self.recent.retain(|entry| pinned.contains(entry));
+    }
+}
+
+#[test]
+fn test_is_read_only_path() {
+    let cfg = Config {
+        read_only_exts: vec!["iso".into(), "*.img".into(), ".bin".into()],
+        ..Config::default()
+    };
+    assert!(cfg.is_read_only_path(Path::new("/tmp/disk.iso")));
+    assert!(cfg.is_read_only_path(Path::new("/tmp/disk.ISO")));
+    assert!(cfg.is_read_only_path(Path::new("disk.img")));
+    assert!(cfg.is_read_only_path(Path::new("rom.bin")));
+    assert!(!cfg.is_read_only_path(Path::new("save.dat")));
+    assert!(!cfg.is_read_only_path(Path::new("iso")));
+}
+
+#[test]
+fn test_pinned_recent() {
+    let mut cfg = Config::default();
+    let args = |name: &str| SourceArgs {
+        file: Some(name.into()),
+        ..SourceArgs::default()
+    };
+    cfg.recent.use_(args("a"));
+    cfg.recent.use_(args("b"));
+    cfg.toggle_pin(&args("a"));
+    assert!(cfg.is_pinned(&args("a")));
+    assert!(!cfg.is_pinned(&args("b")));
+    cfg.clear_unpinned_recent();
+    assert_eq!(cfg.recent.iter().collect::<Vec<_>>(), [&args("a")]);
+    cfg.toggle_pin(&args("a"));
+    assert!(cfg.pinned_recent.is_empty());
 }
 
 fn project_dirs() -> Option<ProjectDirs> {
@@ -0,0 +1,107 @@
+//! Export of regions, bookmarks and comments as a CSV "data map"
+
+use {
+    crate::meta::{Meta, ValueType},
+    std::{borrow::Cow, fmt::Write},
+};
+
+/// An entry of the data map. `end` is inclusive, like [`crate::meta::region::Region`].
+struct Entry<'a> {
+    begin: usize,
+    end: usize,
+    name: &'a str,
+    desc: &'a str,
+    kind: &'static str,
+}
+
+/// Generate a CSV with the columns offset, end, length, name, description and type,
+/// with a row for every region, bookmark and comment, in order of offset.
+///
+/// Offsets are displaced by the display base address, like the displayed offsets.
+pub fn generate(meta: &Meta, hex: bool) -> String {
+    let fmt = |n: usize| {
+        let n = meta.misc.display_base_address.wrapping_add(n as u64);
+        if hex {
+            format!("0x{:X}", n)
+        } else {
+            n.to_string()
+        }
+    };
+    let regions = meta.low.regions.values().map(|reg| Entry {
+        begin: reg.region.begin,
+        end: reg.region.end,
+        name: &reg.name,
+        desc: &reg.desc,
+        kind: "region",
+    });
+    let bookmarks = meta.bookmarks.iter().map(|bm| {
+        let len = match bm.value_type {
+            ValueType::U16 => 2,
+            ValueType::None | ValueType::U8 | ValueType::StringMap(_) => 1,
+        };
+        Entry {
+            begin: bm.offset,
+            end: bm.offset + len - 1,
+            name: &bm.label,
+            desc: &bm.desc,
+            kind: "bookmark",
+        }
+    });
+    let comments = meta.comments.iter().map(|(&offset, text)| Entry {
+        begin: offset,
+        end: offset,
+        name: "",
+        desc: text,
+        kind: "comment",
+    });
+    let mut entries: Vec<_> = regions.chain(bookmarks).chain(comments).collect();
+    entries.sort_by_key(|en| (en.begin, en.end));
+    let mut out = String::from("offset,end,length,name,description,type\n");
+    for en in entries {
+        let _ = writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            fmt(en.begin),
+            fmt(en.end),
+            (en.end + 1).saturating_sub(en.begin),
+            csv_field(en.name),
+            csv_field(en.desc),
+            en.kind
+        );
+    }
+    out
+}
+
+/// Quote `field` if it contains anything that would break the CSV structure
+fn csv_field(field: &str) -> Cow<str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\"")).into()
+    } else {
+        field.into()
+    }
+}
+
+#[test]
+fn test_data_map() {
+    use crate::meta::{region::Region, Bookmark};
+    let mut meta = Meta::default();
+    let key = meta.add_region_from_selection(Region { begin: 2, end: 5 });
+    meta.low.regions[key].name = "header".into();
+    meta.low.regions[key].desc = "magic, \"version\"".into();
+    meta.bookmarks.push(Bookmark {
+        offset: 3,
+        label: "size".into(),
+        desc: String::new(),
+        value_type: ValueType::U16,
+        big_endian: false,
+    });
+    meta.comments.insert(0, "start".into());
+    meta.misc.display_base_address = 0x100;
+    assert_eq!(
+        generate(&meta, true),
+        "offset,end,length,name,description,type\n\
+         0x100,0x100,1,,start,comment\n\
+         0x102,0x105,4,header,\"magic, \"\"version\"\"\",region\n\
+         0x103,0x104,2,size,,bookmark\n"
+    );
+}
@@ -0,0 +1,55 @@
+//! Appending committed edits to a log file, as an audit trail
+
+use std::{
+    fmt::Write as _,
+    fs::{File, OpenOptions},
+    io::Write as _,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+#[derive(Debug)]
+pub struct EditLog {
+    file: File,
+}
+
+impl EditLog {
+    /// Open the log at `path` for appending, creating it if it doesn't exist
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        Ok(Self {
+            file: OpenOptions::new().create(true).append(true).open(path)?,
+        })
+    }
+    /// Append an entry, and flush it, so it survives a crash
+    pub fn log(&mut self, offset: usize, old: &[u8], new: &[u8]) -> std::io::Result<()> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |dur| dur.as_secs());
+        writeln!(self.file, "{}", format_entry(offset, old, new, time))?;
+        self.file.flush()
+    }
+}
+
+fn format_entry(offset: usize, old: &[u8], new: &[u8], time: u64) -> String {
+    let hex = |bytes: &[u8]| {
+        bytes.iter().fold(String::new(), |mut s, b| {
+            let _ = write!(s, "{:02x}", b);
+            s
+        })
+    };
+    format!(
+        "offset=0x{:x} old={} new={} time={}",
+        offset,
+        hex(old),
+        hex(new),
+        time
+    )
+}
+
+#[test]
+fn test_format_entry() {
+    assert_eq!(
+        format_entry(0x1f, &[0, 0xab], &[1, 0xcd], 42),
+        "offset=0x1f old=00ab new=01cd time=42"
+    );
+}
@@ -11,20 +11,26 @@ use {
 mod advanced_open_window;
 mod bookmarks_window;
 mod bottom_panel;
+mod chunked_scan;
 mod debug_window;
+mod decode_selection_window;
 pub mod dialogs;
 mod external_command_window;
 mod file_diff_result_window;
-mod find_dialog;
+pub mod find_dialog;
 mod find_memory_pointers_window;
+mod find_pointers_to_window;
 pub mod inspect_panel;
 mod layouts_window;
 mod meta_diff_window;
 mod open_process_window;
-mod ops;
+pub mod ops;
 mod perspectives_window;
 mod preferences_window;
 mod regions_window;
+mod runs_window;
+mod strings_window;
+mod template_window;
 mod top_menu;
 mod top_panel;
 mod util;
@@ -34,11 +40,15 @@ mod window_open;
 use {
     self::{
         advanced_open_window::AdvancedOpenWindow, bookmarks_window::BookmarksWindow,
+        decode_selection_window::DecodeSelectionWindow, dialogs::CommentDialog,
         file_diff_result_window::FileDiffResultWindow, find_dialog::FindDialog,
-        find_memory_pointers_window::FindMemoryPointersWindow, inspect_panel::InspectPanel,
-        layouts_window::LayoutsWindow, meta_diff_window::MetaDiffWindow,
-        open_process_window::OpenProcessWindow, perspectives_window::PerspectivesWindow,
-        regions_window::RegionsWindow, views_window::ViewsWindow,
+        find_memory_pointers_window::FindMemoryPointersWindow,
+        find_pointers_to_window::{FindPointersToWindow, PtrWidth},
+        inspect_panel::InspectPanel, layouts_window::LayoutsWindow,
+        meta_diff_window::MetaDiffWindow, open_process_window::OpenProcessWindow,
+        perspectives_window::PerspectivesWindow, regions_window::RegionsWindow,
+        runs_window::RunsWindow, strings_window::StringsWindow, template_window::TemplateWindow,
+        views_window::ViewsWindow,
     },
     crate::{
         app::App,
@@ -52,7 +62,7 @@ use {
             TextStyle::{Body, Button, Heading, Monospace, Small},
             TopBottomPanel, Window,
         },
-        sfml::graphics::Font,
+        sfml::{graphics::Font, window::clipboard},
         SfEgui,
     },
 };
@@ -74,9 +84,20 @@ pub struct Gui {
     pub meta_diff_window: MetaDiffWindow,
     pub open_process_window: OpenProcessWindow,
     pub find_memory_pointers_window: FindMemoryPointersWindow,
+    pub find_pointers_to_window: FindPointersToWindow,
     pub advanced_open_window: AdvancedOpenWindow,
     pub external_command_window: ExternalCommandWindow,
     pub preferences_window: PreferencesWindow,
+    pub decode_selection_window: DecodeSelectionWindow,
+    pub strings_window: StringsWindow,
+    pub template_window: TemplateWindow,
+    pub runs_window: RunsWindow,
+    /// The [`App::file_generation`] the per-file state above belongs to
+    file_generation: u64,
+    /// The [`App::data_generation`] the offsets in the state above belong to
+    data_generation: u64,
+    /// Length of the data as of `data_generation`
+    data_len: usize,
 }
 
 pub struct ContextMenu {
@@ -107,6 +128,27 @@ impl Gui {
     pub fn add_dialog<D: Dialog + 'static>(&mut self, dialog: D) {
         self.dialogs.push(Box::new(dialog));
     }
+    /// Drop the state that refers to data that changed under it, or to the previous file
+    fn sync_file_state(&mut self, app: &App) {
+        let file_changed = self.file_generation != app.file_generation;
+        if self.data_generation != app.data_generation {
+            // The found offsets could be out of bounds, or point at different things now.
+            // A reload that keeps the length keeps the find results, so values in process
+            // memory can be narrowed down over several reloads.
+            if file_changed || self.data_len != app.data.len() {
+                self.find_dialog.clear_results();
+            }
+            self.strings_window.clear();
+            self.runs_window.clear();
+            self.data_generation = app.data_generation;
+            self.data_len = app.data.len();
+        }
+        if file_changed {
+            self.file_generation = app.file_generation;
+            self.dialogs.clear();
+            self.context_menu = None;
+        }
+    }
 }
 
 #[must_use = "Returns false if application should quit"]
@@ -117,6 +159,7 @@ pub fn do_egui(
     mouse_pos: ViewportVec,
     font: &Font,
 ) -> bool {
+    gui.sync_file_state(app);
     let result = sf_egui.do_frame(|ctx| {
         let mut open = gamedebug_core::enabled();
         let was_open = open;
@@ -148,9 +191,14 @@ pub fn do_egui(
             "Diff against clean meta", meta_diff_window,            MetaDiffWindow: app;
             "Open process",            open_process_window,         OpenProcessWindow: gui app font;
             "Find memory pointers",    find_memory_pointers_window, FindMemoryPointersWindow: gui app font;
+            "Find pointers to",        find_pointers_to_window,     FindPointersToWindow: gui app;
             "Advanced open",           advanced_open_window,        AdvancedOpenWindow: gui app font;
             "External command",        external_command_window,     ExternalCommandWindow: gui app;
             "Preferences",             preferences_window,          PreferencesWindow: gui app;
+            "Decoded selection",       decode_selection_window,     DecodeSelectionWindow: gui app;
+            "Strings",                 strings_window,              StringsWindow: gui app;
+            "Template check",          template_window,             TemplateWindow: gui app;
+            "Repeated byte runs",      runs_window,                 RunsWindow: gui app;
         }
         // Context menu
         if let Some(menu) = &gui.context_menu {
@@ -181,11 +229,44 @@ pub fn do_egui(
                                         label: format!("New @ offset {}", byte_off),
                                         desc: String::new(),
                                         value_type: ValueType::None,
+                                        big_endian: app.cfg.default_big_endian,
                                     });
                                     gui.bookmarks_window.open.set(true);
                                     gui.bookmarks_window.selected = Some(idx);
                                     close = true;
                                 }
+                                if ui.button("Copy offset").clicked() {
+                                    let off = app.display_offset(byte_off);
+                                    let base = app.preferences.copy_offset_base;
+                                    clipboard::set_string(&base.format(off));
+                                    close = true;
+                                }
+                                let comments = &app.meta_state.meta.comments;
+                                let comment_label = if comments.contains_key(&byte_off) {
+                                    "Edit comment..."
+                                } else {
+                                    "Add comment..."
+                                };
+                                if ui.button(comment_label).clicked() {
+                                    gui.add_dialog(CommentDialog::new(byte_off, app));
+                                    close = true;
+                                }
+                                ui.horizontal(|ui| {
+                                    ui.label("Find pointers to here");
+                                    let widths = [(PtrWidth::Four, "32"), (PtrWidth::Eight, "64")];
+                                    for (width, label) in widths {
+                                        if ui
+                                            .button(label)
+                                            .on_hover_text(format!("{} bit pointers", label))
+                                            .clicked()
+                                        {
+                                            gui.find_pointers_to_window.search_for_offset(
+                                                &app.data, byte_off, width,
+                                            );
+                                            close = true;
+                                        }
+                                    }
+                                });
                                 ui.separator();
                                 if ui.button("View properties...").clicked() {
                                     gui.views_window.selected = view;
@@ -200,37 +281,39 @@ pub fn do_egui(
             }
         }
         // Panels
-        let top_re =
+        if app.preferences.show_top_panel {
             TopBottomPanel::top("top_panel").show(ctx, |ui| top_panel::ui(ui, gui, app, font));
-        let bot_re = TopBottomPanel::bottom("bottom_panel")
-            .show(ctx, |ui| bottom_panel::ui(ui, app, mouse_pos));
-        let right_re = egui::SidePanel::right("right_panel")
-            .show(ctx, |ui| inspect_panel::ui(ui, app, gui, mouse_pos))
-            .response;
-        let padding = 2;
-        app.hex_ui.hex_iface_rect.x = padding;
-        #[expect(
-            clippy::cast_possible_truncation,
-            reason = "Window size can't exceed i16"
-        )]
-        {
-            app.hex_ui.hex_iface_rect.y = top_re.response.rect.bottom() as ViewportScalar + padding;
         }
-        #[expect(
-            clippy::cast_possible_truncation,
-            reason = "Window size can't exceed i16"
-        )]
+        if app.preferences.show_bottom_panel {
+            TopBottomPanel::bottom("bottom_panel")
+                .show(ctx, |ui| bottom_panel::ui(ui, app, mouse_pos));
+        }
+        if app.preferences.show_inspect_panel {
+            egui::SidePanel::right("right_panel")
+                .show(ctx, |ui| inspect_panel::ui(ui, app, gui, mouse_pos));
+        }
+        // Comment tooltip for the hovered byte
+        if !ctx.is_pointer_over_area()
+            && let Some((off, _)) = app.byte_offset_at_pos(mouse_pos.x, mouse_pos.y)
+            && let Some(comment) = app.meta_state.meta.comments.get(&off)
         {
-            app.hex_ui.hex_iface_rect.w = right_re.rect.left() as ViewportScalar - padding * 2;
+            egui::show_tooltip_at_pointer(ctx, egui::Id::new("comment_tooltip"), |ui| {
+                ui.label(comment);
+            });
         }
+        // The hex interface gets whatever area the panels leave free
+        let free = ctx.available_rect();
+        let padding = 2;
         #[expect(
             clippy::cast_possible_truncation,
             reason = "Window size can't exceed i16"
         )]
         {
-            app.hex_ui.hex_iface_rect.h = (bot_re.response.rect.top() as ViewportScalar
-                - app.hex_ui.hex_iface_rect.y)
-                - padding * 2;
+            app.hex_ui.hex_iface_rect.x = free.left() as ViewportScalar + padding;
+            app.hex_ui.hex_iface_rect.y = free.top() as ViewportScalar + padding;
+            app.hex_ui.hex_iface_rect.w = free.width() as ViewportScalar - padding * 2;
+            app.hex_ui.hex_iface_rect.h =
+                (free.bottom() as ViewportScalar - app.hex_ui.hex_iface_rect.y) - padding * 2;
         }
         let mut dialogs: Vec<_> = std::mem::take(&mut gui.dialogs);
         dialogs.retain_mut(|dialog| {
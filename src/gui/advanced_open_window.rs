@@ -1,6 +1,9 @@
 use {
-    super::{window_open::WindowOpen, Gui},
-    crate::{app::App, args::Args, shell::msg_if_fail},
+    super::{ops::request_file_op, window_open::WindowOpen, Gui},
+    crate::{
+        app::{App, FileOp},
+        args::Args,
+    },
     egui_sfml::{egui, sfml::graphics::Font},
 };
 
@@ -76,6 +79,13 @@ impl AdvancedOpenWindow {
         );
         ui.checkbox(&mut args.src.read_only, "read-only")
             .on_hover_text("Open file as read-only");
+        ui.checkbox(
+            &mut args.src.ignore_read_only_exts,
+            "ignore read-only extensions",
+        )
+        .on_hover_text("Don't force read-only mode based on the file extension");
+        ui.checkbox(&mut args.src.no_canonicalize, "don't canonicalize path")
+            .on_hover_text("Keep the file path as given, for example to preserve symlinks");
         if ui
             .checkbox(&mut args.src.stream, "stream")
             .on_hover_text(
@@ -86,6 +96,16 @@ impl AdvancedOpenWindow {
         {
             args.src.read_only = args.src.stream;
         }
+        if ui
+            .checkbox(&mut args.src.decompress, "decompress on open")
+            .on_hover_text(
+                "Show the decompressed contents of gzip/zlib compressed files.\n\
+             Sets read-only attribute",
+            )
+            .changed()
+        {
+            args.src.read_only = args.src.decompress;
+        }
         ui.heading("Meta");
         match &args.meta {
             Some(file) => {
@@ -105,11 +125,9 @@ impl AdvancedOpenWindow {
             .add_enabled(args.src.file.is_some(), egui::Button::new("Load"))
             .clicked()
         {
-            msg_if_fail(
-                app.load_file_args(args.clone(), font),
-                "Failed to load file",
-            );
+            let args = args.clone();
             win.open.set(false);
+            request_file_op(gui, app, FileOp::Load(args), font);
         }
     }
 }
@@ -25,6 +25,13 @@ pub struct BookmarksWindow {
 impl BookmarksWindow {
     pub fn ui(ui: &mut Ui, gui: &mut Gui, app: &mut App) {
         let win = &mut gui.bookmarks_window;
+        // Bookmarks can be removed from under us, e.g. by deleting bytes
+        if win
+            .selected
+            .is_some_and(|&idx| idx >= app.meta_state.meta.bookmarks.len())
+        {
+            win.selected = None;
+        }
         ui.add(egui::TextEdit::singleline(&mut win.name_filter_string).hint_text("Filter by name"));
         let mut action = Action::None;
         TableBuilder::new(ui)
@@ -48,11 +55,13 @@ impl BookmarksWindow {
                 // Sort by offset
                 let mut keys: Vec<usize> = (0..app.meta_state.meta.bookmarks.len()).collect();
                 keys.sort_by_key(|&idx| app.meta_state.meta.bookmarks[idx].offset);
+                let filter = win.name_filter_string.to_lowercase();
                 keys.retain(|&k| {
-                    win.name_filter_string.is_empty()
+                    filter.is_empty()
                         || app.meta_state.meta.bookmarks[k]
                             .label
-                            .contains(&win.name_filter_string)
+                            .to_lowercase()
+                            .contains(&filter)
                 });
                 body.rows(20.0, keys.len(), |idx, mut row| {
                     let idx = keys[idx];
@@ -69,7 +78,10 @@ impl BookmarksWindow {
                     });
                     row.col(|ui| {
                         if ui
-                            .link(app.meta_state.meta.bookmarks[idx].offset.to_string())
+                            .link(
+                                app.display_offset(app.meta_state.meta.bookmarks[idx].offset)
+                                    .to_string(),
+                            )
                             .clicked()
                         {
                             action = Action::Goto(app.meta_state.meta.bookmarks[idx].offset);
@@ -81,7 +93,9 @@ impl BookmarksWindow {
                             ValueType::None => {}
                             ValueType::U8 => match app.data.get_mut(bm.offset) {
                                 Some(byte) => {
+                                    let old = *byte;
                                     if ui.add(egui::DragValue::new(byte)).changed() {
+                                        app.edit_state.log_edit(bm.offset, &[old], &[*byte]);
                                         app.edit_state
                                             .widen_dirty_region(DamageRegion::Single(bm.offset));
                                     }
@@ -90,41 +104,78 @@ impl BookmarksWindow {
                                     ui.label("??");
                                 }
                             },
-                            ValueType::U16Le => {
+                            ValueType::U16 => {
                                 let result: anyhow::Result<()> = try {
                                     match app.data.get(bm.offset..bm.offset + 2) {
                                         Some(slice) => {
-                                            let mut val = u16::from_le_bytes(slice.try_into()?);
-                                            if ui.add(egui::DragValue::new(&mut val)).changed() {
-                                                app.data[bm.offset..bm.offset + 2]
-                                                    .copy_from_slice(&val.to_le_bytes());
-                                                app.edit_state.widen_dirty_region(
-                                                    DamageRegion::Range(bm.offset..bm.offset + 2),
-                                                );
-                                            }
+                                            let bytes: [u8; 2] = slice.try_into()?;
+                                            let mut val = if bm.big_endian {
+                                                u16::from_be_bytes(bytes)
+                                            } else {
+                                                u16::from_le_bytes(bytes)
+                                            };
+                                            ui.horizontal(|ui| {
+                                                if ui
+                                                    .add(egui::DragValue::new(&mut val))
+                                                    .changed()
+                                                {
+                                                    let new = if bm.big_endian {
+                                                        val.to_be_bytes()
+                                                    } else {
+                                                        val.to_le_bytes()
+                                                    };
+                                                    app.edit_state
+                                                        .log_edit(bm.offset, &bytes, &new);
+                                                    app.data[bm.offset..bm.offset + 2]
+                                                        .copy_from_slice(&new);
+                                                    app.edit_state.widen_dirty_region(
+                                                        DamageRegion::Range(
+                                                            bm.offset..bm.offset + 2,
+                                                        ),
+                                                    );
+                                                }
+                                                ui.label(
+                                                    egui::RichText::new(format!(
+                                                        "{:02x} {:02x}",
+                                                        bytes[0], bytes[1]
+                                                    ))
+                                                    .monospace(),
+                                                )
+                                                .on_hover_text("Raw bytes");
+                                            });
                                         }
                                         None => {
                                             ui.label("??");
                                         }
                                     }
                                 };
-                                msg_if_fail(result, "Failed u16-le conversion");
+                                msg_if_fail(result, "Failed u16 conversion");
                             }
-                            ValueType::StringMap(list) => {
-                                let val = &mut app.data[bm.offset];
-                                let mut s = String::new();
-                                let label = list.get(val).unwrap_or_else(|| {
-                                    s = format!("[unmapped: {}]", val);
-                                    &s
-                                });
-                                egui::ComboBox::new("val_combo", "")
-                                    .selected_text(label)
-                                    .show_ui(ui, |ui| {
-                                        for (k, v) in list {
-                                            ui.selectable_value(val, *k, v);
-                                        }
+                            ValueType::StringMap(list) => match app.data.get_mut(bm.offset) {
+                                Some(val) => {
+                                    let old = *val;
+                                    let mut s = String::new();
+                                    let label = list.get(val).unwrap_or_else(|| {
+                                        s = format!("[unmapped: {}]", val);
+                                        &s
                                     });
-                            }
+                                    egui::ComboBox::new("val_combo", "")
+                                        .selected_text(label)
+                                        .show_ui(ui, |ui| {
+                                            for (k, v) in list {
+                                                ui.selectable_value(val, *k, v);
+                                            }
+                                        });
+                                    if *val != old {
+                                        app.edit_state.log_edit(bm.offset, &[old], &[*val]);
+                                        app.edit_state
+                                            .widen_dirty_region(DamageRegion::Single(bm.offset));
+                                    }
+                                }
+                                None => {
+                                    ui.label("??");
+                                }
+                            },
                         }
                     });
                     row.col(|ui| {
@@ -181,8 +232,8 @@ impl BookmarksWindow {
                     ui.selectable_value(&mut mark.value_type, ValueType::U8, ValueType::U8.label());
                     ui.selectable_value(
                         &mut mark.value_type,
-                        ValueType::U16Le,
-                        ValueType::U16Le.label(),
+                        ValueType::U16,
+                        ValueType::U16.label(),
                     );
                     let val = ValueType::StringMap(Default::default());
                     if ui
@@ -195,8 +246,13 @@ impl BookmarksWindow {
                         mark.value_type = val;
                     }
                 });
-            #[expect(clippy::single_match, reason = "Want to add more variants in future")]
             match &mut mark.value_type {
+                ValueType::U16 => {
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut mark.big_endian, false, "Little endian");
+                        ui.radio_value(&mut mark.big_endian, true, "Big endian");
+                    });
+                }
                 ValueType::StringMap(list) => {
                     let text_edit_finished = ui
                         .add(
@@ -232,6 +288,7 @@ impl BookmarksWindow {
                 label: format!("New bookmark at {}", app.edit_state.cursor),
                 desc: String::new(),
                 value_type: ValueType::None,
+                big_endian: app.cfg.default_big_endian,
             })
         }
         match action {
@@ -250,7 +307,7 @@ impl ValueType {
         match self {
             ValueType::None => "none",
             ValueType::U8 => "u8",
-            ValueType::U16Le => "u16-le",
+            ValueType::U16 => "u16",
             ValueType::StringMap(_) => "string list",
         }
     }
@@ -1,6 +1,9 @@
 use {
     crate::{
         app::{interact_mode::InteractMode, App},
+        keybinds::KeyAction,
+        magic,
+        meta::region::Region,
         view::ViewportVec,
     },
     egui_sfml::egui::{
@@ -9,6 +12,9 @@ use {
     slotmap::Key,
 };
 
+/// Largest selection (in bytes) that sum/min/max/average are shown for
+const MAX_STATS_LEN: usize = 64 * 1024;
+
 pub fn ui(ui: &mut Ui, app: &mut App, mouse_pos: ViewportVec) {
     ui.horizontal(|ui| {
         let job = key_label(ui, "F1", "View");
@@ -27,6 +33,26 @@ pub fn ui(ui: &mut Ui, app: &mut App, mouse_pos: ViewportVec) {
             app.hex_ui.interact_mode = InteractMode::Edit;
         }
         ui.separator();
+        if app.has_unsaved_changes() {
+            ui.colored_label(Color32::YELLOW, "modified")
+                .on_hover_text("There are unsaved changes");
+            ui.separator();
+        } else if let Some(src) = &app.source && !src.attr.permissions.write {
+            ui.label("read-only");
+            ui.separator();
+        }
+        if let Some(sig) = magic::detect(&app.data) {
+            if ui
+                .link(sig.name)
+                .on_hover_text("Detected file format. Click to add a region for its header.")
+                .clicked()
+            {
+                let end = sig.header_len.min(app.data.len()) - 1;
+                let key = app.meta_state.meta.add_region_from_selection(Region { begin: 0, end });
+                app.meta_state.meta.low.regions[key].name = format!("{} header", sig.name);
+            }
+            ui.separator();
+        }
         let data_len = app.data.len();
         if data_len != 0 {
             if let Some(view_key) = app.hex_ui.focused_view {
@@ -57,11 +83,55 @@ pub fn ui(ui: &mut Ui, app: &mut App, mouse_pos: ViewportVec) {
             }
         }
         ui.separator();
-        ui.label(format!(
-            "cursor: {} ({:x})",
-            app.edit_state.cursor, app.edit_state.cursor
-        ));
+        let cursor = app.display_offset(app.edit_state.cursor);
+        ui.label(format!("cursor: {} ({:x})", cursor, cursor));
+        if app.preferences.show_cursor_value
+            && let Some(&byte) = app.data.get(app.edit_state.cursor)
+        {
+            ui.label(format!("value: 0x{:02X} {} 0b{:08b} 0o{:03o}", byte, byte, byte, byte))
+                .on_hover_text("The byte at the cursor in hex, decimal, binary and octal");
+        }
+        if let Some(field) = app.field_at_cursor() {
+            let binds = &app.cfg.keybinds;
+            ui.label(format!("field: {}", field.label)).on_hover_text(format!(
+                "{}/{}: previous/next field (bookmark) in this region",
+                binds.label(KeyAction::PrevField),
+                binds.label(KeyAction::NextField)
+            ));
+        }
+        if let Some(sel) = app.hex_ui.selection() {
+            ui.separator();
+            ui.label(format!("selection: {} bytes", sel.len()));
+            ui.add(
+                DragValue::new(&mut app.hex_ui.record_size)
+                    .clamp_range(1..=usize::MAX)
+                    .prefix("record size: "),
+            );
+            let record_size = app.hex_ui.record_size.max(1);
+            ui.label(format!(
+                "{} records + {} bytes",
+                sel.len() / record_size,
+                sel.len() % record_size
+            ));
+            if sel.len() <= MAX_STATS_LEN
+                && let Some(bytes) = app.data.get(sel.begin..=sel.end)
+                && let (Some(min), Some(max)) = (bytes.iter().min(), bytes.iter().max())
+            {
+                let sum: u64 = bytes.iter().map(|&b| u64::from(b)).sum();
+                #[expect(
+                    clippy::cast_precision_loss,
+                    reason = "The sum of at most MAX_STATS_LEN bytes fits well into an f64"
+                )]
+                let avg = sum as f64 / bytes.len() as f64;
+                ui.label(format!(
+                    "sum: {} min: {} max: {} avg: {:.2}",
+                    sum, min, max, avg
+                ))
+                .on_hover_text("Of the selected bytes, as unsigned 8 bit values");
+            }
+        }
         if !app.hex_ui.current_layout.is_null() && let Some((offset, _view_idx)) = app.byte_offset_at_pos(mouse_pos.x, mouse_pos.y) {
+            let offset = app.display_offset(offset);
             ui.label(format!("mouse: {} ({:x})", offset, offset));
         }
     });
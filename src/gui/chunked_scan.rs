@@ -0,0 +1,122 @@
+//! Scanning the data a chunk per frame, for the windows that list what they find in it
+
+use {
+    crate::{app::App, meta::region::Region},
+    egui_sfml::egui,
+    std::ops::Range,
+};
+
+/// Scanning state that carries over from one chunk to the next
+pub trait Scanner {
+    /// What kind of thing the scanner finds
+    type Kind;
+    /// Scan `data[range]`, adding what was found to `out`.
+    ///
+    /// `data` is all of the data, so the scanner can look past the end of the chunk.
+    fn scan(&mut self, data: &[u8], range: Range<usize>, out: &mut Vec<Found<Self::Kind>>);
+    /// Add what was still being scanned when the end of the data (at `end`) was reached
+    fn finish(&mut self, end: usize, out: &mut Vec<Found<Self::Kind>>);
+}
+
+/// Something found by a scan, along with where it is
+#[derive(Debug, PartialEq, Eq)]
+pub struct Found<K> {
+    pub region: Region,
+    pub kind: K,
+}
+
+/// Add `start..end` to `out` if it's at least `min_len` bytes long
+pub fn push_found<K>(out: &mut Vec<Found<K>>, start: usize, end: usize, min_len: usize, kind: K) {
+    if end.saturating_sub(start) >= min_len.max(1) {
+        out.push(Found {
+            region: Region {
+                begin: start,
+                end: end - 1,
+            },
+            kind,
+        });
+    }
+}
+
+/// A scan in progress
+pub struct ChunkedScan<S> {
+    scanner: S,
+    pos: usize,
+    /// The [`App::data_generation`] the scan was started at
+    data_generation: u64,
+}
+
+impl<S: Scanner> ChunkedScan<S> {
+    pub fn new(scanner: S, app: &App) -> Self {
+        Self {
+            scanner,
+            pos: 0,
+            data_generation: app.data_generation,
+        }
+    }
+    /// Scan up to `chunk_size` more bytes of `data`.
+    ///
+    /// Returns whether the end of the data was reached.
+    fn advance(&mut self, data: &[u8], chunk_size: usize, out: &mut Vec<Found<S::Kind>>) -> bool {
+        let end = self.pos.saturating_add(chunk_size).min(data.len());
+        self.scanner.scan(data, self.pos..end, out);
+        self.pos = end;
+        let done = end == data.len();
+        if done {
+            self.scanner.finish(end, out);
+        }
+        done
+    }
+}
+
+/// Advance `scan` by a chunk of `chunk_size` bytes, and show its progress along with a button
+/// to cancel it.
+///
+/// The scan is dropped once it's done or cancelled. If the data changed since it started, it's
+/// dropped along with what it found so far.
+///
+/// Returns whether the scan finished this frame.
+pub fn progress_ui<S: Scanner>(
+    ui: &mut egui::Ui,
+    scan: &mut Option<ChunkedScan<S>>,
+    app: &App,
+    chunk_size: usize,
+    out: &mut Vec<Found<S::Kind>>,
+) -> bool {
+    let Some(sc) = scan else { return false };
+    if sc.data_generation != app.data_generation {
+        *scan = None;
+        out.clear();
+        return false;
+    }
+    let done = sc.advance(&app.data, chunk_size, out);
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Precision loss is fine for a progress bar"
+    )]
+    let progress = sc.pos as f32 / app.data.len().max(1) as f32;
+    ui.horizontal(|ui| {
+        ui.add(egui::ProgressBar::new(progress).show_percentage());
+        if ui.button("Cancel").clicked() {
+            *scan = None;
+        }
+    });
+    if done {
+        *scan = None;
+    }
+    ui.ctx().request_repaint();
+    done
+}
+
+/// Run `scanner` over all of `data`, `chunk_size` bytes at a time
+#[cfg(test)]
+pub fn scan_all<S: Scanner>(scanner: S, data: &[u8], chunk_size: usize) -> Vec<Found<S::Kind>> {
+    let mut scan = ChunkedScan {
+        scanner,
+        pos: 0,
+        data_generation: 0,
+    };
+    let mut out = Vec::new();
+    while !scan.advance(data, chunk_size, &mut out) {}
+    out
+}
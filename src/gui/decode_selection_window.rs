@@ -0,0 +1,66 @@
+use {
+    super::{window_open::WindowOpen, Gui},
+    crate::{app::App, view::TextKind},
+    egui_sfml::{egui, sfml::window::clipboard},
+};
+
+/// Selections larger than this are truncated, to keep decoding every frame cheap
+const MAX_DECODE_LEN: usize = 1024 * 1024;
+
+/// Shows the selection decoded as text
+pub struct DecodeSelectionWindow {
+    pub open: WindowOpen,
+    text_kind: TextKind,
+}
+
+impl Default for DecodeSelectionWindow {
+    fn default() -> Self {
+        Self {
+            open: WindowOpen::default(),
+            text_kind: TextKind::Ascii,
+        }
+    }
+}
+
+impl DecodeSelectionWindow {
+    pub fn ui(ui: &mut egui::Ui, gui: &mut Gui, app: &mut App) {
+        let win = &mut gui.decode_selection_window;
+        let Some(sel) = app.hex_ui.selection() else {
+            ui.label("No selection");
+            win.open.post_ui();
+            return;
+        };
+        let mut text = String::new();
+        ui.horizontal(|ui| {
+            egui::ComboBox::new("decode_kind_combo", "Text kind")
+                .selected_text(win.text_kind.name())
+                .show_ui(ui, |ui| {
+                    for kind in [TextKind::Ascii, TextKind::Utf16Le, TextKind::Utf16Be] {
+                        let name = kind.name();
+                        ui.selectable_value(&mut win.text_kind, kind, name);
+                    }
+                });
+            let end = sel.end.min(sel.begin + MAX_DECODE_LEN - 1);
+            text = app
+                .data
+                .get(sel.begin..=end)
+                .map(|bytes| win.text_kind.decode(bytes))
+                .unwrap_or_default();
+            if ui.button("Copy").clicked() {
+                clipboard::set_string(&text);
+            }
+            if sel.len() > MAX_DECODE_LEN {
+                ui.label(format!("(only the first {} bytes are shown)", MAX_DECODE_LEN));
+            }
+        });
+        ui.separator();
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.add(
+                egui::TextEdit::multiline(&mut text.as_str())
+                    .desired_width(f32::INFINITY)
+                    .font(egui::TextStyle::Monospace),
+            );
+        });
+        win.open.post_ui();
+    }
+}
@@ -1,17 +1,23 @@
 use {
     super::Dialog,
     crate::{
-        app::App,
+        app::{
+            edit_op::{EditOp, RANDOM_FILL_CONFIRM_THRESHOLD},
+            App, FileOp,
+        },
+        c_header,
         color::ColorMethod,
-        damage_region::DamageRegion,
-        parse_radix::{parse_offset_maybe_relative, Relativity},
-        shell::{msg_fail, msg_if_fail, msg_warn},
+        data_map,
+        meta::region::Region,
+        offset_expr::{self, Vars},
+        parse_radix::parse_guess_radix,
+        shell::{msg_if_fail, msg_warn},
         slice_ext::SliceExt,
     },
     egui_easy_mark_standalone::easy_mark,
     egui_sfml::egui,
     rlua::Function,
-    std::time::Instant,
+    std::{borrow::Cow, time::Instant},
 };
 
 #[derive(Debug, Default)]
@@ -34,31 +40,101 @@ impl Dialog for JumpDialog {
         easy_mark(
             ui,
             "Accepts both decimal and hexadecimal.\nPrefix with `0x` to force hex.\n\
-             Prefix with `+` to add to current offset, `-` to subtract",
+             Prefix with `+` to add to current offset, `-` to subtract.\n\
+             Simple expressions like `0x1000 + 16*4` are supported, with the identifiers\n\
+             `base` (cursor), `sel` (selection start) and `end` (last offset)",
         );
         ui.checkbox(&mut self.relative, "Relative")
-            .on_hover_text("Relative to --hard-seek");
+            .on_hover_text("Relative to --hard-seek and the display base address");
         if ui.input().key_pressed(egui::Key::Enter) {
-            match parse_offset_maybe_relative(&self.string_buf) {
-                Ok((offset, relativity)) => {
-                    let offset = match relativity {
-                        Relativity::Absolute => {
-                            if let Some(hard_seek) = app.args.src.hard_seek {
-                                offset.saturating_sub(hard_seek)
-                            } else {
-                                offset
-                            }
-                        }
-                        Relativity::RelAdd => app.edit_state.cursor.saturating_add(offset),
-                        Relativity::RelSub => app.edit_state.cursor.saturating_sub(offset),
-                    };
+            // Unless relative, offsets are understood to be in terms of the whole file,
+            // displaced by the display base address
+            let shift = if self.relative { 0 } else { absolute_shift(app) };
+            let input = self.string_buf.trim();
+            let result = if input.starts_with(['+', '-']) {
+                eval_shifted(&format!("base {}", input), app, shift)
+            } else {
+                eval_shifted(input, app, shift)
+            };
+            match result {
+                Ok(offset) if offset >= app.data.len() => {
+                    msg_warn(&format!(
+                        "Invalid offset: {} is past the end of the data ({} bytes)",
+                        offset,
+                        app.data.len()
+                    ));
+                    true
+                }
+                Ok(offset) => {
                     app.edit_state.cursor = offset;
                     app.center_view_on_offset(offset);
                     app.hex_ui.flash_cursor();
                     false
                 }
                 Err(e) => {
-                    msg_fail(&e, "Failed to parse offset");
+                    msg_warn(&format!("Invalid offset: {}", e));
+                    true
+                }
+            }
+        } else {
+            !(ui.input().key_pressed(egui::Key::Escape))
+        }
+    }
+}
+
+/// How far offsets in terms of the whole file (displaced by the display base address)
+/// are from offsets into the data
+fn absolute_shift(app: &App) -> usize {
+    let base = app.meta_state.meta.misc.display_base_address;
+    let base = usize::try_from(base).unwrap_or(usize::MAX);
+    app.args.src.hard_seek.unwrap_or(0).saturating_add(base)
+}
+
+/// Evaluate the offset expression `input`, with the variables displaced by `shift`,
+/// and turn the result back into an offset into the data.
+///
+/// Like [`App::display_offset`], displaced offsets wrap around instead of overflowing.
+/// Results before the start of the data are an error.
+fn eval_shifted(input: &str, app: &App, shift: usize) -> anyhow::Result<usize> {
+    let vars = Vars {
+        base: app.edit_state.cursor.wrapping_add(shift),
+        sel: app.hex_ui.selection().map(|sel| sel.begin.wrapping_add(shift)),
+        end: app.data.len().saturating_sub(1).wrapping_add(shift),
+    };
+    offset_expr::eval(input, &vars)?
+        .checked_sub(shift)
+        .ok_or_else(|| anyhow::anyhow!("it's before the start of the data"))
+}
+
+#[derive(Debug, Default)]
+pub struct SelectToOffsetDialog {
+    string_buf: String,
+}
+
+impl Dialog for SelectToOffsetDialog {
+    fn title(&self) -> &str {
+        "Select to offset"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
+        ui.horizontal(|ui| {
+            ui.label("Offset");
+            ui.text_edit_singleline(&mut self.string_buf)
+                .request_focus();
+        });
+        ui.label("Selects from the cursor to this offset, as displayed (with the base address)");
+        if ui.input().key_pressed(egui::Key::Enter) {
+            match eval_shifted(self.string_buf.trim(), app, absolute_shift(app)) {
+                Ok(offset) => {
+                    let offset = offset.min(app.data.len().saturating_sub(1));
+                    let cursor = app.edit_state.cursor;
+                    app.hex_ui.select_a = Some(cursor.min(offset));
+                    app.hex_ui.select_b = Some(cursor.max(offset));
+                    app.center_view_on_offset(offset);
+                    false
+                }
+                Err(e) => {
+                    msg_warn(&format!("Invalid offset: {}", e));
                     true
                 }
             }
@@ -86,7 +162,14 @@ impl Dialog for AutoSaveReloadDialog {
         });
         ui.separator();
         ui.checkbox(&mut app.preferences.auto_save, "Auto save")
-            .on_hover_text("Save every time an editing action is finished");
+            .on_hover_text("Save when an editing action is finished");
+        ui.horizontal(|ui| {
+            ui.label("Min. interval (ms)")
+                .on_hover_text("Edits made within this interval are saved together");
+            ui.add(egui::DragValue::new(
+                &mut app.preferences.auto_save_interval_ms,
+            ));
+        });
         ui.separator();
         !(ui.button("Close (enter/esc)").clicked()
             || ui.input().key_pressed(egui::Key::Escape)
@@ -105,10 +188,10 @@ impl Dialog for PatternFillDialog {
     }
 
     fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
-        let Some(sel) = app.hex_ui.selection() else {
+        if app.hex_ui.selection().is_none() {
             ui.heading("No active selection");
             return true;
-        };
+        }
         ui.text_edit_singleline(&mut self.pattern_string)
             .request_focus();
         if ui.input().key_pressed(egui::Key::Enter) {
@@ -119,10 +202,7 @@ impl Dialog for PatternFillDialog {
                 .collect();
             match values {
                 Ok(values) => {
-                    let range = sel.begin..=sel.end;
-                    app.data[range.clone()].pattern_fill(&values);
-                    app.edit_state
-                        .widen_dirty_region(DamageRegion::RangeInclusive(range));
+                    msg_if_fail(app.apply_op(EditOp::PatternFill(values)), "Failed to fill");
                     false
                 }
                 Err(e) => {
@@ -136,6 +216,436 @@ impl Dialog for PatternFillDialog {
     }
 }
 
+#[derive(Debug, Default)]
+pub struct RandomFillDialog {
+    /// Seed for a reproducible fill, if any
+    seed: Option<u64>,
+    /// The large selection the user confirmed overwriting
+    confirmed: Option<Region>,
+    /// What's needed to undo the fill, once it's done
+    undo: Option<FillUndo>,
+}
+
+#[derive(Debug)]
+struct FillUndo {
+    region: Region,
+    /// The bytes from before the fill
+    old: Vec<u8>,
+    /// The bytes the fill wrote, to tell whether they were edited since
+    new: Vec<u8>,
+    /// The [`App::data_generation`] at the time of the fill
+    data_generation: u64,
+}
+
+impl Dialog for RandomFillDialog {
+    fn title(&self) -> &str {
+        "Random fill"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
+        let mut keep_open = true;
+        if let Some(undo) = &self.undo {
+            ui.label(format!("Filled {} bytes", undo.region.len()));
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        undo.data_generation == app.data_generation,
+                        egui::Button::new("Undo"),
+                    )
+                    .on_hover_text("Restore the bytes from before the fill")
+                    .on_disabled_hover_text("The data was reloaded or resized since the fill")
+                    .clicked()
+                {
+                    // Restoring over later edits would silently throw them away
+                    if app.data.get(undo.region.begin..=undo.region.end) == Some(&undo.new[..]) {
+                        let result = app.replace_range(undo.region, &undo.old);
+                        msg_if_fail(result, "Failed to undo fill");
+                    } else {
+                        msg_warn("The filled bytes were edited since, not restoring them");
+                    }
+                    keep_open = false;
+                }
+                if ui.button("Close").clicked() {
+                    keep_open = false;
+                }
+            });
+            return keep_open && !ui.input().key_pressed(egui::Key::Escape);
+        }
+        let Some(sel) = app.hex_ui.selection() else {
+            ui.heading("No active selection");
+            return !(ui.button("Close").clicked() || ui.input().key_pressed(egui::Key::Escape));
+        };
+        ui.label(format!(
+            "{} bytes at offset {} will be overwritten",
+            sel.len(),
+            app.display_offset(sel.begin)
+        ));
+        ui.horizontal(|ui| {
+            let mut use_seed = self.seed.is_some();
+            ui.checkbox(&mut use_seed, "Seed")
+                .on_hover_text("The same seed fills the same bytes every time");
+            if use_seed {
+                ui.add(egui::DragValue::new(self.seed.get_or_insert(0)));
+            } else {
+                self.seed = None;
+            }
+        });
+        let large = sel.len() > RANDOM_FILL_CONFIRM_THRESHOLD;
+        if large {
+            let mut confirmed = self.confirmed == Some(sel);
+            ui.checkbox(&mut confirmed, format!("Yes, overwrite all {} bytes", sel.len()));
+            self.confirmed = confirmed.then_some(sel);
+        }
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(!large || self.confirmed == Some(sel), egui::Button::new("Fill"))
+                .clicked()
+            {
+                let old = app.data[sel.begin..=sel.end].to_vec();
+                let result = app.apply_op(EditOp::RandomFill(self.seed));
+                if result.is_ok() {
+                    self.undo = Some(FillUndo {
+                        region: sel,
+                        old,
+                        new: app.data[sel.begin..=sel.end].to_vec(),
+                        data_generation: app.data_generation,
+                    });
+                }
+                msg_if_fail(result, "Failed to fill");
+            }
+            if ui.button("Cancel").clicked() {
+                keep_open = false;
+            }
+        });
+        keep_open && !ui.input().key_pressed(egui::Key::Escape)
+    }
+}
+
+#[derive(Debug)]
+pub struct DisplayBaseDialog {
+    input: String,
+}
+
+impl DisplayBaseDialog {
+    pub fn new(app: &App) -> Self {
+        Self {
+            input: format!("0x{:X}", app.meta_state.meta.misc.display_base_address),
+        }
+    }
+}
+
+impl Dialog for DisplayBaseDialog {
+    fn title(&self) -> &str {
+        "Display base address"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
+        ui.label("Added to displayed offsets, and subtracted from jump targets");
+        ui.text_edit_singleline(&mut self.input).request_focus();
+        let mut keep_open = true;
+        ui.horizontal(|ui| {
+            if ui.button("Set").clicked() || ui.input().key_pressed(egui::Key::Enter) {
+                match parse_guess_radix(self.input.trim()) {
+                    Ok(base) => {
+                        app.meta_state.meta.misc.display_base_address = base;
+                        keep_open = false;
+                    }
+                    Err(e) => msg_warn(&format!("Invalid address: {}", e)),
+                }
+            }
+            if ui.button("Cancel").clicked() || ui.input().key_pressed(egui::Key::Escape) {
+                keep_open = false;
+            }
+        });
+        keep_open
+    }
+}
+
+#[derive(Debug)]
+pub struct CommentDialog {
+    offset: usize,
+    text: String,
+}
+
+impl CommentDialog {
+    pub fn new(offset: usize, app: &App) -> Self {
+        Self {
+            offset,
+            text: app
+                .meta_state
+                .meta
+                .comments
+                .get(&offset)
+                .cloned()
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl Dialog for CommentDialog {
+    fn title(&self) -> &str {
+        "Comment"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
+        ui.label(format!("Comment for offset {}", self.offset));
+        ui.text_edit_singleline(&mut self.text).request_focus();
+        let comments = &mut app.meta_state.meta.comments;
+        let mut keep_open = true;
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() || ui.input().key_pressed(egui::Key::Enter) {
+                // Saving an empty comment removes it
+                if self.text.trim().is_empty() {
+                    comments.remove(&self.offset);
+                } else {
+                    comments.insert(self.offset, self.text.trim().to_owned());
+                }
+                keep_open = false;
+            }
+            if comments.contains_key(&self.offset) && ui.button("Remove").clicked() {
+                comments.remove(&self.offset);
+                keep_open = false;
+            }
+            if ui.button("Cancel").clicked() || ui.input().key_pressed(egui::Key::Escape) {
+                keep_open = false;
+            }
+        });
+        keep_open
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ExportCHeaderDialog {
+    hex: bool,
+}
+
+impl Dialog for ExportCHeaderDialog {
+    fn title(&self) -> &str {
+        "Export C header"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
+        ui.label("Writes the offsets and sizes of regions and bookmarks as #defines");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.hex, false, "Decimal");
+            ui.radio_value(&mut self.hex, true, "Hexadecimal");
+        });
+        let mut keep_open = true;
+        ui.horizontal(|ui| {
+            if ui.button("Export...").clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .add_filter("C header", &["h"])
+                    .save_file()
+            {
+                let header = c_header::generate(&app.meta_state.meta, self.hex);
+                msg_if_fail(std::fs::write(path, header), "Failed to export C header");
+                keep_open = false;
+            }
+            if ui.button("Cancel").clicked() || ui.input().key_pressed(egui::Key::Escape) {
+                keep_open = false;
+            }
+        });
+        keep_open
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ExportDataMapDialog {
+    hex: bool,
+}
+
+impl Dialog for ExportDataMapDialog {
+    fn title(&self) -> &str {
+        "Export data map"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
+        ui.label("Writes the regions, bookmarks and comments as a CSV table");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.hex, false, "Decimal");
+            ui.radio_value(&mut self.hex, true, "Hexadecimal");
+        });
+        let mut keep_open = true;
+        ui.horizontal(|ui| {
+            if ui.button("Export...").clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .add_filter("CSV", &["csv"])
+                    .save_file()
+            {
+                let csv = data_map::generate(&app.meta_state.meta, self.hex);
+                msg_if_fail(std::fs::write(path, csv), "Failed to export data map");
+                keep_open = false;
+            }
+            if ui.button("Cancel").clicked() || ui.input().key_pressed(egui::Key::Escape) {
+                keep_open = false;
+            }
+        });
+        keep_open
+    }
+}
+
+#[derive(Debug)]
+pub struct InsertBytesDialog {
+    count: usize,
+    pattern_string: String,
+}
+
+impl Default for InsertBytesDialog {
+    fn default() -> Self {
+        Self {
+            count: 1,
+            pattern_string: String::new(),
+        }
+    }
+}
+
+impl Dialog for InsertBytesDialog {
+    fn title(&self) -> &str {
+        "Insert bytes"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
+        ui.horizontal(|ui| {
+            ui.label("Count");
+            ui.add(egui::DragValue::new(&mut self.count));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Pattern");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.pattern_string).hint_text("00 (hex bytes)"),
+            );
+        });
+        ui.label(format!("Inserting at offset {}", app.edit_state.cursor));
+        if ui.button("Insert").clicked() || ui.input().key_pressed(egui::Key::Enter) {
+            let pattern: Result<Vec<u8>, _> = self
+                .pattern_string
+                .split_whitespace()
+                .map(|token| u8::from_str_radix(token, 16))
+                .collect();
+            match pattern {
+                Ok(pattern) => {
+                    let mut bytes = vec![0; self.count];
+                    if !pattern.is_empty() {
+                        bytes.pattern_fill(&pattern);
+                    }
+                    msg_if_fail(
+                        app.insert_bytes(app.edit_state.cursor, &bytes),
+                        "Failed to insert bytes",
+                    );
+                    false
+                }
+                Err(e) => {
+                    msg_warn(&format!("Pattern parse error: {}", e));
+                    true
+                }
+            }
+        } else {
+            !(ui.button("Cancel").clicked() || ui.input().key_pressed(egui::Key::Escape))
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SelectLengthPrefixedDialog {
+    /// Width of the length field in bytes
+    width: usize,
+    big_endian: bool,
+}
+
+impl SelectLengthPrefixedDialog {
+    pub fn new(big_endian: bool) -> Self {
+        Self {
+            width: 4,
+            big_endian,
+        }
+    }
+}
+
+impl Dialog for SelectLengthPrefixedDialog {
+    fn title(&self) -> &str {
+        "Select length-prefixed data"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
+        ui.horizontal(|ui| {
+            ui.label("Length field");
+            for width in [1, 2, 4, 8] {
+                ui.radio_value(&mut self.width, width, format!("{} bytes", width));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.big_endian, false, "Little endian");
+            ui.radio_value(&mut self.big_endian, true, "Big endian");
+        });
+        ui.label(format!(
+            "Reads the length at offset {}, and selects that many bytes after it",
+            app.edit_state.cursor
+        ));
+        if ui.button("Select").clicked() || ui.input().key_pressed(egui::Key::Enter) {
+            match length_prefixed_region(
+                &app.data,
+                app.edit_state.cursor,
+                self.width,
+                self.big_endian,
+            ) {
+                Ok(reg) => {
+                    app.hex_ui.select_a = Some(reg.begin);
+                    app.hex_ui.select_b = Some(reg.end);
+                    app.center_view_on_offset(reg.begin);
+                    app.edit_state.set_cursor(reg.begin);
+                    app.hex_ui.flash_cursor();
+                }
+                Err(e) => msg_warn(&e),
+            }
+        }
+        !(ui.button("Close").clicked() || ui.input().key_pressed(egui::Key::Escape))
+    }
+}
+
+/// Read a `width` byte length at `offset`, and return the region of that many bytes following it
+fn length_prefixed_region(
+    data: &[u8],
+    offset: usize,
+    width: usize,
+    big_endian: bool,
+) -> Result<Region, String> {
+    let Some(field) = data.get(offset..offset + width) else {
+        return Err("Length field is out of bounds".into());
+    };
+    let fold = |acc: u64, &b: &u8| (acc << 8) | u64::from(b);
+    let len = if big_endian {
+        field.iter().fold(0, fold)
+    } else {
+        field.iter().rev().fold(0, fold)
+    };
+    if len == 0 {
+        return Err("Length is zero".into());
+    }
+    let begin = offset + width;
+    let end = usize::try_from(len)
+        .ok()
+        .and_then(|len| begin.checked_add(len - 1))
+        .filter(|&end| end < data.len())
+        .ok_or_else(|| format!("Length {} goes past the end of the data", len))?;
+    Ok(Region { begin, end })
+}
+
+#[test]
+fn test_length_prefixed_region() {
+    let data = [0, 3, 0, 0xAA, 0xBB, 0xCC, 0xDD];
+    assert_eq!(
+        length_prefixed_region(&data, 1, 2, false),
+        Ok(Region { begin: 3, end: 5 })
+    );
+    assert_eq!(
+        length_prefixed_region(&data, 0, 2, true),
+        Ok(Region { begin: 2, end: 4 })
+    );
+    assert!(length_prefixed_region(&data, 3, 1, false).is_err());
+    assert!(length_prefixed_region(&data, 0, 1, false).is_err());
+    assert!(length_prefixed_region(&data, 5, 4, false).is_err());
+}
+
 #[derive(Debug, Default)]
 pub struct LuaFillDialog {
     result_info_string: String,
@@ -148,10 +658,10 @@ impl Dialog for LuaFillDialog {
     }
 
     fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
-        let Some(sel) = app.hex_ui.selection() else {
+        if app.hex_ui.selection().is_none() {
             ui.heading("No active selection");
             return true;
-        };
+        }
         let ctrl_enter = ui
             .input_mut()
             .consume_key(egui::Modifiers::CTRL, egui::Key::Enter);
@@ -174,24 +684,15 @@ impl Dialog for LuaFillDialog {
             });
         if ui.button("Execute").clicked() || ctrl_enter {
             let start_time = Instant::now();
-            app.lua.context(|ctx| {
-                let chunk = ctx.load(&app.meta_state.meta.misc.fill_lua_script);
-                let res: rlua::Result<()> = try {
-                    let f = chunk.eval::<Function>()?;
-                    for (i, b) in app.data[sel.begin..=sel.end].iter_mut().enumerate() {
-                        *b = f.call((i, *b))?;
-                    }
-                    app.edit_state.dirty_region = Some(sel);
-                };
-                if let Err(e) = res {
-                    self.result_info_string = e.to_string();
-                    self.err = true;
-                } else {
-                    self.result_info_string =
-                        format!("Script took {} ms", start_time.elapsed().as_millis());
-                    self.err = false;
-                }
-            });
+            let script = app.meta_state.meta.misc.fill_lua_script.clone();
+            if let Err(e) = app.apply_op(EditOp::LuaFill(script)) {
+                self.result_info_string = e.to_string();
+                self.err = true;
+            } else {
+                self.result_info_string =
+                    format!("Script took {} ms", start_time.elapsed().as_millis());
+                self.err = false;
+            }
         }
         let close = ui.button("Close").clicked();
         if app.edit_state.dirty_region.is_some() {
@@ -288,3 +789,115 @@ impl Dialog for LuaColorDialog {
         true
     }
 }
+
+/// Asks what to do with unsaved changes before running a [`FileOp`]
+pub struct UnsavedChangesDialog {
+    pub op: Option<FileOp>,
+}
+
+impl Dialog for UnsavedChangesDialog {
+    fn title(&self) -> &str {
+        "Unsaved changes"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
+        ui.label("There are unsaved changes. Save them first?");
+        let mut keep_open = true;
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                if msg_if_fail(app.save(), "Failed to save").is_none() {
+                    app.pending_file_op = self.op.take();
+                    keep_open = false;
+                }
+            }
+            if ui.button("Discard").clicked() {
+                app.pending_file_op = self.op.take();
+                keep_open = false;
+            }
+            if ui.button("Cancel").clicked() || ui.input().key_pressed(egui::Key::Escape) {
+                keep_open = false;
+            }
+        });
+        keep_open
+    }
+}
+
+#[derive(Debug)]
+pub struct EditAsTextDialog {
+    region: Region,
+    text: String,
+    /// The bytes weren't valid UTF-8, so some of them were replaced in `text`
+    lossy: bool,
+}
+
+impl EditAsTextDialog {
+    pub fn new(region: Region, data: &[u8]) -> Self {
+        let text = String::from_utf8_lossy(&data[region.begin..=region.end]);
+        Self {
+            region,
+            lossy: matches!(text, Cow::Owned(_)),
+            text: text.into_owned(),
+        }
+    }
+}
+
+impl Dialog for EditAsTextDialog {
+    fn title(&self) -> &str {
+        "Edit as text"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, app: &mut App) -> bool {
+        ui.label(format!(
+            "Editing {} bytes at offset {}",
+            self.region.len(),
+            self.region.begin
+        ));
+        if self.lossy {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                "The bytes aren't valid UTF-8. Invalid sequences were replaced with \u{FFFD}.",
+            );
+        }
+        egui::ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+            ui.add(
+                egui::TextEdit::multiline(&mut self.text)
+                    .code_editor()
+                    .desired_width(f32::INFINITY),
+            );
+        });
+        let new_len = self.text.len();
+        if new_len == self.region.len() {
+            ui.label(format!("{} bytes", new_len));
+        } else if app.can_resize() {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("{} bytes, the rest of the data will be shifted", new_len),
+            );
+        } else {
+            ui.colored_label(
+                egui::Color32::RED,
+                format!("{} bytes, but the length can't change for this source", new_len),
+            );
+        }
+        // The replacement characters would be written as their UTF-8 encoding,
+        // instead of the bytes they stand for
+        let has_replacements = self.lossy && self.text.contains('\u{FFFD}');
+        let mut keep_open = true;
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(!has_replacements, egui::Button::new("Apply"))
+                .on_disabled_hover_text("Replace the \u{FFFD} characters first")
+                .clicked()
+            {
+                match app.replace_range(self.region, self.text.as_bytes()) {
+                    Ok(()) => keep_open = false,
+                    Err(e) => msg_warn(&format!("Failed to apply text: {:#}", e)),
+                }
+            }
+            if ui.button("Cancel").clicked() {
+                keep_open = false;
+            }
+        });
+        keep_open
+    }
+}
@@ -1,14 +1,17 @@
 use {
     crate::{
-        app::{read_source_to_buf, FileDiffEntry},
+        app::{read_source_to_buf, App, FileDiffEntry},
         gui::window_open::WindowOpen,
-        meta::{find_most_specific_region_for_offset, Bookmark, RegionKey, ValueType},
+        keybinds::KeyAction,
+        meta::{
+            find_most_specific_region_for_offset, region::Region, Bookmark, RegionKey, ValueType,
+        },
         region_context_menu,
         shell::msg_if_fail,
         Gui,
     },
     egui_extras::Size,
-    egui_sfml::egui,
+    egui_sfml::egui::{self, Color32, RichText},
     std::{path::PathBuf, time::Instant},
 };
 
@@ -19,6 +22,14 @@ pub struct FileDiffResultWindow {
     pub auto_refresh: bool,
     pub auto_refresh_interval_ms: u32,
     pub last_refresh: Instant,
+    /// The range of differing bytes last navigated to with next/prev difference
+    pub highlighted: Option<Region>,
+    /// Show my bytes and the file's bytes side by side for the highlighted range
+    pub side_by_side: bool,
+    /// Length of the file being compared against
+    pub file_len: usize,
+    /// Mirror the cursor onto the other file, clamped to its length
+    pub sync_cursor: bool,
 }
 
 impl Default for FileDiffResultWindow {
@@ -30,10 +41,79 @@ impl Default for FileDiffResultWindow {
             auto_refresh: Default::default(),
             auto_refresh_interval_ms: Default::default(),
             last_refresh: Instant::now(),
+            highlighted: None,
+            side_by_side: false,
+            file_len: 0,
+            sync_cursor: false,
         }
     }
 }
+/// Number of surrounding bytes shown around a difference in the side by side comparison
+const SIDE_BY_SIDE_CONTEXT: usize = 8;
+/// Maximum number of bytes shown in the side by side comparison
+const SIDE_BY_SIDE_MAX: usize = 32;
+
 impl FileDiffResultWindow {
+    /// Go to the next range of differing bytes after the cursor
+    pub(crate) fn next_diff(&mut self, app: &mut App) {
+        let cursor = app.edit_state.cursor;
+        let range = diff_ranges(&self.diff_entries)
+            .into_iter()
+            .find(|r| r.begin > cursor);
+        self.goto_diff(app, range);
+    }
+    /// Go to the previous range of differing bytes before the cursor
+    pub(crate) fn prev_diff(&mut self, app: &mut App) {
+        let cursor = app.edit_state.cursor;
+        let range = diff_ranges(&self.diff_entries)
+            .into_iter()
+            .rev()
+            .find(|r| r.end < cursor);
+        self.goto_diff(app, range);
+    }
+    fn goto_diff(&mut self, app: &mut App, range: Option<Region>) {
+        let Some(range) = range else { return };
+        self.highlighted = Some(range);
+        app.center_view_on_offset(range.begin);
+        app.edit_state.set_cursor(range.begin);
+        app.hex_ui.flash_cursor();
+    }
+    /// The cursor mirrored onto the other file, clamped to its length
+    fn other_cursor(&self, cursor: usize) -> Option<usize> {
+        self.file_len.checked_sub(1).map(|last| cursor.min(last))
+    }
+    /// Whether `offset` is part of the currently highlighted difference
+    pub(crate) fn highlight_contains(&self, offset: usize) -> bool {
+        self.open.is() && self.highlighted.is_some_and(|r| r.contains(offset))
+    }
+    fn side_by_side_ui(&self, ui: &mut egui::Ui, data: &[u8], range: Region) {
+        let Some(last) = data.len().min(self.file_len).checked_sub(1) else { return };
+        let begin = range.begin.saturating_sub(SIDE_BY_SIDE_CONTEXT);
+        let end = (range.end + SIDE_BY_SIDE_CONTEXT)
+            .min(begin + SIDE_BY_SIDE_MAX - 1)
+            .min(last);
+        if begin > end {
+            return;
+        }
+        let file_val = |off: usize| {
+            self.diff_entries
+                .iter()
+                .find(|en| en.offset == off)
+                .map_or(data[off], |en| en.file_val)
+        };
+        egui::Grid::new("diff_side_by_side").show(ui, |ui| {
+            ui.label("Mine");
+            for off in begin..=end {
+                ui.label(byte_text(data[off], range.contains(off)));
+            }
+            ui.end_row();
+            ui.label("File");
+            for off in begin..=end {
+                ui.label(byte_text(file_val(off), range.contains(off)));
+            }
+            ui.end_row();
+        });
+    }
     pub(crate) fn ui(ui: &mut egui_sfml::egui::Ui, gui: &mut Gui, app: &mut crate::app::App) {
         if gui.file_diff_result_window.diff_entries.is_empty() {
             ui.label("No difference");
@@ -87,6 +167,7 @@ impl FileDiffResultWindow {
                     for en in &mut gui.file_diff_result_window.diff_entries {
                         en.file_val = file_data[en.offset];
                     }
+                    gui.file_diff_result_window.file_len = file_data.len();
                 };
                 msg_if_fail(result, "Refresh failed");
             }
@@ -99,6 +180,38 @@ impl FileDiffResultWindow {
                 &mut gui.file_diff_result_window.auto_refresh_interval_ms,
             ));
         });
+        ui.horizontal(|ui| {
+            let win = &mut gui.file_diff_result_window;
+            let prev_label = app.cfg.keybinds.label(KeyAction::DiffPrev);
+            let next_label = app.cfg.keybinds.label(KeyAction::DiffNext);
+            if ui.button("⏶ Prev").on_hover_text(prev_label).clicked() {
+                win.prev_diff(app);
+            }
+            if ui.button("⏷ Next").on_hover_text(next_label).clicked() {
+                win.next_diff(app);
+            }
+            ui.checkbox(&mut win.side_by_side, "Side by side");
+            ui.checkbox(&mut win.sync_cursor, "Sync cursor")
+                .on_hover_text("Mirror the cursor onto the other file, clamped to its length");
+        });
+        let win = &gui.file_diff_result_window;
+        let other_cursor = win
+            .sync_cursor
+            .then(|| win.other_cursor(app.edit_state.cursor))
+            .flatten();
+        if let Some(off) = other_cursor {
+            ui.label(format!("Other file cursor: {off}"));
+        }
+        let range = match other_cursor {
+            Some(off) => Some(Region {
+                begin: off,
+                end: off,
+            }),
+            None => win.highlighted,
+        };
+        if win.side_by_side && let Some(range) = range {
+            win.side_by_side_ui(ui, &app.data, range);
+        }
         ui.separator();
         let mut action = Action::None;
         egui_extras::TableBuilder::new(ui)
@@ -145,6 +258,7 @@ impl FileDiffResultWindow {
                                             label: "New bookmark".into(),
                                             desc: String::new(),
                                             value_type: ValueType::None,
+                                            big_endian: app.cfg.default_big_endian,
                                         });
                                         gui.bookmarks_window.open.set(true);
                                         gui.bookmarks_window.selected = Some(idx);
@@ -221,6 +335,50 @@ impl FileDiffResultWindow {
     }
 }
 
+fn byte_text(byte: u8, differs: bool) -> RichText {
+    let text = RichText::new(format!("{:02X}", byte)).monospace();
+    if differs {
+        text.color(Color32::from_rgb(255, 100, 100))
+    } else {
+        text
+    }
+}
+
+/// Group the (offset ordered) diff entries into contiguous ranges
+fn diff_ranges(entries: &[FileDiffEntry]) -> Vec<Region> {
+    let mut ranges: Vec<Region> = Vec::new();
+    for en in entries {
+        match ranges.last_mut() {
+            Some(last) if last.end + 1 == en.offset => last.end = en.offset,
+            _ => ranges.push(Region {
+                begin: en.offset,
+                end: en.offset,
+            }),
+        }
+    }
+    ranges
+}
+
+#[test]
+fn test_diff_ranges() {
+    let entries: Vec<_> = [1, 2, 3, 7, 9, 10]
+        .into_iter()
+        .map(|offset| FileDiffEntry {
+            my_val: 0,
+            file_val: 1,
+            offset,
+        })
+        .collect();
+    assert_eq!(
+        diff_ranges(&entries),
+        [
+            Region { begin: 1, end: 3 },
+            Region { begin: 7, end: 7 },
+            Region { begin: 9, end: 10 },
+        ]
+    );
+}
+
 enum Action {
     None,
     Goto(usize),
@@ -2,21 +2,57 @@ use {
     super::window_open::WindowOpen,
     crate::{
         app::App,
+        config::RecentNeedle,
         meta::{find_most_specific_region_for_offset, Bookmark, Meta, ValueType},
         parse_radix::parse_guess_radix,
         region_context_menu,
         shell::msg_warn,
+        timer::Timer,
     },
     egui_extras::{Size, StripBuilder, TableBuilder},
     egui_sfml::egui::{self, Align, Ui},
-    std::collections::HashSet,
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::HashSet,
+        ops::Range,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            mpsc::{Receiver, Sender, TryRecvError},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    },
 };
 
-#[derive(Default, Debug, PartialEq, Eq)]
+/// Bookmarking more results than this at once requires confirmation
+const BOOKMARK_ALL_CONFIRM_THRESHOLD: usize = 100;
+
+const ALIGNMENTS: [usize; 5] = [1, 2, 4, 8, 16];
+
+/// How many bytes the search worker scans before reporting progress
+const SEARCH_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// How many chunks are handed to the search worker ahead of time
+const MAX_CHUNKS_IN_FLIGHT: usize = 2;
+
+/// Number of results listed per page of the results table
+#[derive(Debug, Clone, Copy)]
+pub struct PageSize(pub usize);
+
+impl Default for PageSize {
+    fn default() -> Self {
+        Self(100)
+    }
+}
+
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum FindType {
     #[default]
     U8,
     Ascii,
+    /// A sequence of u8 values, each matching within a tolerance
+    U8SeqApprox,
 }
 
 impl FindType {
@@ -24,6 +60,7 @@ impl FindType {
         match self {
             FindType::U8 => "u8",
             FindType::Ascii => "ascii",
+            FindType::U8SeqApprox => "u8 sequence (approx.)",
         }
     }
 }
@@ -44,6 +81,89 @@ pub struct FindDialog {
     pub filter_results: bool,
     /// Used for increased/decreased unknown value search
     pub data_snapshot: Vec<u8>,
+    /// Order the results starting from the cursor, wrapping around to the beginning
+    pub from_cursor: bool,
+    /// Shows a notice while running, after the result cursor wrapped around
+    pub wrap_notice_timer: Timer,
+    /// Only accept matches at offsets that are a multiple of this. 0 is treated as 1.
+    pub alignment: usize,
+    /// How many matches the last search dropped because they were unaligned
+    pub unaligned_count: usize,
+    /// Only search within the current selection, if there is one
+    pub in_selection: bool,
+    /// How much each byte may differ from the needle value for approximate searches
+    pub tolerance: u8,
+    /// The page of the results table being shown
+    pub page: usize,
+    pub page_size: PageSize,
+    /// List all results on a single page
+    pub show_all: bool,
+    /// The search currently running in the background, if any
+    bg_search: Option<BgSearch>,
+}
+
+/// A needle search running on a worker thread
+struct BgSearch {
+    recv: Receiver<ChunkResult>,
+    /// Hands chunks to the worker. `None` once all of them were handed over.
+    send: Option<Sender<Chunk>>,
+    cancel: Arc<AtomicBool>,
+    scanned: usize,
+    total: usize,
+    /// Start of the next chunk to hand to the worker
+    next: usize,
+    /// End of the searched range
+    end: usize,
+    /// How far past its end a chunk needs to extend to find the matches straddling it
+    window_extra: usize,
+    /// Chunks handed to the worker that it hasn't reported back about
+    in_flight: usize,
+}
+
+impl BgSearch {
+    /// Hand the worker copies of the next chunks of `data`, keeping a few of them in flight.
+    ///
+    /// Copying a chunk at a time avoids copying (and freezing on) the whole range up front.
+    fn feed(&mut self, data: &[u8]) {
+        let Some(send) = &self.send else { return };
+        // The data could have shrunk since the search started
+        let end = self.end.min(data.len());
+        while self.in_flight < MAX_CHUNKS_IN_FLIGHT && self.next < end {
+            let chunk = copy_chunk(data, self.next, end, SEARCH_CHUNK_SIZE, self.window_extra);
+            self.next += chunk.len;
+            if send.send(chunk).is_err() {
+                break;
+            }
+            self.in_flight += 1;
+        }
+        if self.next >= end {
+            // Lets the worker finish once it's done with the chunks it has
+            self.send = None;
+        }
+    }
+}
+
+impl Drop for BgSearch {
+    fn drop(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+}
+
+/// A copy of a chunk of the data for the search worker to search
+struct Chunk {
+    /// Offset of the chunk in the data
+    start: usize,
+    /// Length of the chunk. `bytes` can extend past it, but only matches starting
+    /// within the chunk count.
+    len: usize,
+    bytes: Vec<u8>,
+}
+
+/// Matches found by the search worker in one chunk of the haystack
+struct ChunkResult {
+    /// Number of bytes scanned in this chunk
+    scanned: usize,
+    offsets: Vec<usize>,
 }
 
 impl FindDialog {
@@ -61,16 +181,121 @@ impl FindDialog {
                     FindType::Ascii,
                     FindType::Ascii.label(),
                 );
+                ui.selectable_value(
+                    &mut gui.find_dialog.find_type,
+                    FindType::U8SeqApprox,
+                    FindType::U8SeqApprox.label(),
+                );
             });
-        let re = ui.text_edit_singleline(&mut gui.find_dialog.input);
+        if gui.find_dialog.find_type == FindType::U8SeqApprox {
+            ui.horizontal(|ui| {
+                ui.label("Tolerance ±");
+                ui.add(egui::DragValue::new(&mut gui.find_dialog.tolerance));
+            })
+            .response
+            .on_hover_text("Values separated by spaces or commas, e.g. `10 0x20 30`");
+        }
+        let re = ui
+            .horizontal(|ui| {
+                let re = ui.text_edit_singleline(&mut gui.find_dialog.input);
+                ui.add_enabled_ui(app.cfg.recent_needles.iter().next().is_some(), |ui| {
+                    ui.menu_button("🕓", |ui| {
+                        for recent in app.cfg.recent_needles.iter() {
+                            let label = format!("[{}] {}", recent.find_type.label(), recent.needle);
+                            if ui.button(label).clicked() {
+                                gui.find_dialog.find_type = recent.find_type;
+                                gui.find_dialog.input = recent.needle.clone();
+                                ui.close_menu();
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text("Recent searches");
+                });
+                re
+            })
+            .inner;
         if gui.find_dialog.open.just_now() {
             re.request_focus();
         }
         if re.lost_focus() && ui.input().key_pressed(egui::Key::Enter) {
             do_search(app, gui);
         }
-        ui.checkbox(&mut gui.find_dialog.filter_results, "Filter results")
-            .on_hover_text("Base search on existing results");
+        if gui.find_dialog.bg_search.is_some() {
+            gui.find_dialog.poll_bg_search(app);
+        }
+        if let Some(search) = &gui.find_dialog.bg_search {
+            #[expect(
+                clippy::cast_precision_loss,
+                reason = "Precision loss is fine for a progress bar"
+            )]
+            let progress = search.scanned as f32 / search.total.max(1) as f32;
+            ui.horizontal(|ui| {
+                ui.add(egui::ProgressBar::new(progress).show_percentage());
+                if ui.button("Cancel").clicked() {
+                    gui.find_dialog.bg_search = None;
+                    finish_search(&mut gui.find_dialog, app);
+                }
+            });
+            ui.ctx().request_repaint();
+        }
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut gui.find_dialog.filter_results, "Filter results")
+                .on_hover_text("Base search on existing results");
+            ui.checkbox(&mut gui.find_dialog.from_cursor, "Search from cursor")
+                .on_hover_text("List results starting at the cursor, wrapping around");
+            ui.checkbox(&mut gui.find_dialog.in_selection, "In selection")
+                .on_hover_text("Only search within the selection. No effect without a selection.");
+        });
+        ui.horizontal(|ui| {
+            egui::ComboBox::new("align_combo", "Alignment")
+                .selected_text(gui.find_dialog.alignment.max(1).to_string())
+                .show_ui(ui, |ui| {
+                    for align in ALIGNMENTS {
+                        ui.selectable_value(
+                            &mut gui.find_dialog.alignment,
+                            align,
+                            align.to_string(),
+                        );
+                    }
+                });
+            if gui.find_dialog.unaligned_count != 0 {
+                ui.label(format!(
+                    "{} unaligned matches skipped",
+                    gui.find_dialog.unaligned_count
+                ));
+            }
+        });
+        let dia = &mut gui.find_dialog;
+        let per_page = if dia.show_all {
+            dia.results_vec.len()
+        } else {
+            dia.page_size.0
+        }
+        .max(1);
+        // Follow the result cursor when stepping through the results
+        if let Some(i) = dia.scroll_to {
+            dia.page = i / per_page;
+        }
+        let page_count = ((dia.results_vec.len() + per_page - 1) / per_page).max(1);
+        dia.page = dia.page.min(page_count - 1);
+        ui.horizontal(|ui| {
+            ui.add_enabled_ui(!dia.show_all, |ui| {
+                if ui.add_enabled(dia.page > 0, egui::Button::new("⏴")).clicked() {
+                    dia.page -= 1;
+                }
+                ui.label(format!("Page {}/{}", dia.page + 1, page_count));
+                if ui.add_enabled(dia.page + 1 < page_count, egui::Button::new("⏵")).clicked() {
+                    dia.page += 1;
+                }
+                ui.label("Per page");
+                ui.add(egui::DragValue::new(&mut dia.page_size.0).clamp_range(1..=100_000));
+            });
+            ui.checkbox(&mut dia.show_all, "Show all")
+                .on_hover_text("List all results on one page. Can be slow with many results.");
+        });
+        let page_start = dia.page * per_page;
+        let page_len = per_page.min(dia.results_vec.len() - page_start);
         StripBuilder::new(ui).size(Size::initial(400.0)).size(Size::exact(20.0)).vertical(|mut strip| {
             strip.cell(|ui| {
                 let mut action = Action::None;
@@ -94,8 +319,9 @@ impl FindDialog {
                 .body(|body| {
                     body.rows(
                         20.0,
-                        gui.find_dialog.results_vec.len(),
-                        |i, mut row| {
+                        page_len,
+                        |row_idx, mut row| {
+                            let i = page_start + row_idx;
                             let off = gui.find_dialog.results_vec[i];
                             let col1_re = row.col(|ui| {
                                 if ui.selectable_label(
@@ -151,6 +377,7 @@ impl FindDialog {
                                             label: "New bookmark".into(),
                                             desc: String::new(),
                                             value_type: ValueType::None,
+                                            big_endian: app.cfg.default_big_endian,
                                         });
                                         gui.bookmarks_window.open.set(true);
                                         gui.bookmarks_window.selected = Some(idx);
@@ -188,29 +415,126 @@ impl FindDialog {
             strip.cell(|ui| {
                 ui.horizontal(|ui| {
                     ui.set_enabled(!gui.find_dialog.results_vec.is_empty());
-                    if (ui.button("Previous (P)").clicked() || ui.input().key_pressed(egui::Key::P))
-                        && gui.find_dialog.result_cursor > 0
-                    {
-                        gui.find_dialog.result_cursor -= 1;
-                        let off = gui.find_dialog.results_vec[gui.find_dialog.result_cursor];
-                        app.search_focus(off);
-                        gui.find_dialog.scroll_to = Some(gui.find_dialog.result_cursor);
+                    if ui.button("Previous (P)").clicked() || ui.input().key_pressed(egui::Key::P) {
+                        gui.find_dialog.step(app, false);
                     }
                     ui.label((gui.find_dialog.result_cursor + 1).to_string());
-                    if (ui.button("Next (N)").clicked() || ui.input().key_pressed(egui::Key::N))
-                        && gui.find_dialog.result_cursor + 1 < gui.find_dialog.results_vec.len()
-                    {
-                        gui.find_dialog.result_cursor += 1;
-                        let off = gui.find_dialog.results_vec[gui.find_dialog.result_cursor];
-                        app.search_focus(off);
-                        gui.find_dialog.scroll_to = Some(gui.find_dialog.result_cursor);
+                    if ui.button("Next (N)").clicked() || ui.input().key_pressed(egui::Key::N) {
+                        gui.find_dialog.step(app, true);
                     }
                     ui.label(format!("{} results", gui.find_dialog.results_vec.len()));
+                    if ui.button("Bookmark all").on_hover_text("Add a bookmark for every result").clicked() {
+                        bookmark_all(gui, app);
+                    }
+                    if gui.find_dialog.wrap_notice_timer.overtime().is_some() {
+                        ui.colored_label(egui::Color32::YELLOW, "Search wrapped");
+                        ui.ctx().request_repaint();
+                    }
                 });
             });
         });
         gui.find_dialog.open.post_ui();
     }
+    /// Forget the results and cancel the running search, as they refer to another file
+    pub fn clear_results(&mut self) {
+        self.results_vec.clear();
+        self.results_set.clear();
+        self.result_cursor = 0;
+        self.scroll_to = None;
+        self.data_snapshot = Vec::new();
+        self.unaligned_count = 0;
+        self.page = 0;
+        self.bg_search = None;
+    }
+    /// Start searching for `needle` in `range` of `data` on a worker thread.
+    ///
+    /// The data can change while the search runs, so the worker is handed copies of it
+    /// chunk by chunk, as it progresses.
+    fn start_bg_search(&mut self, data: &[u8], range: Range<usize>, needle: Vec<u8>) {
+        let (chunk_tx, chunk_rx) = std::sync::mpsc::channel();
+        let (tx, rx) = std::sync::mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut search = BgSearch {
+            recv: rx,
+            send: Some(chunk_tx),
+            cancel: cancel.clone(),
+            scanned: 0,
+            total: range.len(),
+            next: range.start,
+            end: range.end,
+            window_extra: needle.len().saturating_sub(1),
+            in_flight: 0,
+        };
+        search.feed(data);
+        self.bg_search = Some(search);
+        thread::spawn(move || {
+            search_worker(&needle, &chunk_rx, &tx, &cancel);
+        });
+    }
+    /// Collect results streamed in by the background search
+    fn poll_bg_search(&mut self, app: &mut App) {
+        let Some(search) = &mut self.bg_search else { return };
+        loop {
+            match search.recv.try_recv() {
+                Ok(chunk) => {
+                    search.scanned += chunk.scanned;
+                    search.in_flight = search.in_flight.saturating_sub(1);
+                    let matches = chunk.offsets.into_iter();
+                    for offset in aligned(matches, self.alignment, &mut self.unaligned_count) {
+                        self.results_vec.push(offset);
+                        self.results_set.insert(offset);
+                    }
+                }
+                Err(TryRecvError::Empty) => {
+                    search.feed(&app.data);
+                    return;
+                }
+                Err(TryRecvError::Disconnected) => {
+                    self.bg_search = None;
+                    finish_search(self, app);
+                    return;
+                }
+            }
+        }
+    }
+    /// Go to the next (or previous) result, wrapping around at the ends
+    fn step(&mut self, app: &mut App, forward: bool) {
+        let len = self.results_vec.len();
+        if len == 0 {
+            return;
+        }
+        let (new, wrapped) = step_wrapping(self.result_cursor, len, forward);
+        if wrapped {
+            self.wrap_notice_timer = Timer::set(Duration::from_millis(1500));
+        }
+        self.result_cursor = new;
+        app.search_focus(self.results_vec[new]);
+        self.scroll_to = Some(new);
+    }
+}
+
+/// Step `cursor` forward or backward within `0..len`.
+///
+/// Returns the new cursor, and whether it wrapped around.
+fn step_wrapping(cursor: usize, len: usize, forward: bool) -> (usize, bool) {
+    if forward {
+        if cursor + 1 >= len {
+            (0, true)
+        } else {
+            (cursor + 1, false)
+        }
+    } else if cursor == 0 || cursor >= len {
+        (len.saturating_sub(1), true)
+    } else {
+        (cursor - 1, false)
+    }
+}
+
+/// Rotate sorted `results` so they start at the first offset at or after `cursor`
+fn order_from_cursor(results: &mut [usize], cursor: usize) {
+    results.sort_unstable();
+    let pivot = results.partition_point(|&off| off < cursor);
+    results.rotate_left(pivot);
 }
 
 enum Action {
@@ -220,27 +544,92 @@ enum Action {
     RemoveIdxFromResults(usize),
 }
 
+fn bookmark_all(gui: &mut crate::gui::Gui, app: &mut App) {
+    let results = &gui.find_dialog.results_vec;
+    if results.len() > BOOKMARK_ALL_CONFIRM_THRESHOLD
+        && !rfd::MessageDialog::new()
+            .set_level(rfd::MessageLevel::Warning)
+            .set_title("Bookmark all")
+            .set_description(&format!(
+                "This will create {} bookmarks. Continue?",
+                results.len()
+            ))
+            .set_buttons(rfd::MessageButtons::YesNo)
+            .show()
+    {
+        return;
+    }
+    let first_idx = app.meta_state.meta.bookmarks.len();
+    for (i, &off) in results.iter().enumerate() {
+        app.meta_state.meta.bookmarks.push(Bookmark {
+            offset: off,
+            label: format!("match {} @ {:X}", i + 1, off),
+            desc: String::new(),
+            value_type: ValueType::None,
+            big_endian: app.cfg.default_big_endian,
+        });
+    }
+    gui.bookmarks_window.open.set(true);
+    gui.bookmarks_window.selected = Some(first_idx);
+}
+
 fn do_search(app: &mut App, gui: &mut crate::gui::Gui) {
     let dia = &mut gui.find_dialog;
+    if !dia.input.is_empty() {
+        app.cfg.recent_needles.use_(RecentNeedle {
+            find_type: dia.find_type,
+            needle: dia.input.clone(),
+        });
+    }
     if !dia.filter_results {
         dia.results_vec.clear();
         dia.results_set.clear();
     }
+    dia.page = 0;
+    dia.unaligned_count = 0;
+    let scope = if dia.in_selection {
+        app.hex_ui.selection()
+    } else {
+        None
+    };
+    let range = match scope {
+        Some(sel) => sel.begin.min(app.data.len())..sel.end.saturating_add(1).min(app.data.len()),
+        None => 0..app.data.len(),
+    };
+    // Starting a new search cancels the one in progress
+    dia.bg_search = None;
     match dia.find_type {
-        FindType::U8 => find_u8(dia, app),
+        FindType::U8 => find_u8(dia, app, range.clone()),
         FindType::Ascii => {
-            for offset in memchr::memmem::find_iter(&app.data, &dia.input) {
-                dia.results_vec.push(offset);
-                dia.results_set.insert(offset);
-            }
+            let needle = dia.input.as_bytes().to_vec();
+            dia.start_bg_search(&app.data, range.clone(), needle);
         }
+        FindType::U8SeqApprox => find_u8_seq_approx(dia, app, range.clone()),
+    }
+    if dia.bg_search.is_some() {
+        // Finished when the worker is done
+        return;
     }
+    // Snapshot comparisons and filtering work on whole-file offsets, so trim them here
+    if scope.is_some() {
+        dia.results_vec.retain(|off| range.contains(off));
+        dia.results_set.retain(|off| range.contains(off));
+    }
+    finish_search(dia, app);
+}
+
+/// Order the results and go to the first one
+fn finish_search(dia: &mut FindDialog, app: &mut App) {
+    if dia.from_cursor {
+        order_from_cursor(&mut dia.results_vec, app.edit_state.cursor);
+    }
+    dia.result_cursor = 0;
     if let Some(&off) = dia.results_vec.first() {
         app.search_focus(off);
     }
 }
 
-fn find_u8(dia: &mut FindDialog, app: &mut App) {
+fn find_u8(dia: &mut FindDialog, app: &mut App, range: Range<usize>) {
     match dia.input.as_str() {
         "?" => {
             dia.data_snapshot = app.data.clone();
@@ -323,7 +712,7 @@ fn find_u8(dia: &mut FindDialog, app: &mut App) {
                         needle,
                     );
                 } else {
-                    u8_search(dia, app.data.iter().cloned().enumerate(), needle);
+                    dia.start_bg_search(&app.data, range.clone(), vec![needle]);
                 }
             }
             Err(e) => msg_warn(&format!("Parse fail: {}", e)),
@@ -332,10 +721,185 @@ fn find_u8(dia: &mut FindDialog, app: &mut App) {
 }
 
 fn u8_search(dialog: &mut FindDialog, haystack: impl Iterator<Item = (usize, u8)>, needle: u8) {
-    for (offset, byte) in haystack {
-        if byte == needle {
-            dialog.results_vec.push(offset);
-            dialog.results_set.insert(offset);
+    let matches = haystack.filter_map(|(offset, byte)| (byte == needle).then_some(offset));
+    for offset in aligned(matches, dialog.alignment, &mut dialog.unaligned_count) {
+        dialog.results_vec.push(offset);
+        dialog.results_set.insert(offset);
+    }
+}
+
+fn find_u8_seq_approx(dia: &mut FindDialog, app: &App, range: Range<usize>) {
+    let needle: Result<Vec<u8>, _> = dia
+        .input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(parse_guess_radix)
+        .collect();
+    let needle = match needle {
+        Ok(needle) if needle.is_empty() => {
+            msg_warn("Enter at least one value");
+            return;
+        }
+        Ok(needle) => needle,
+        Err(e) => {
+            msg_warn(&format!("Parse fail: {}", e));
+            return;
         }
+    };
+    let tolerance = dia.tolerance;
+    let matches: Vec<usize> = if dia.filter_results {
+        let prev = std::mem::take(&mut dia.results_vec);
+        dia.results_set.clear();
+        prev.into_iter()
+            .filter(|&off| {
+                app.data
+                    .get(off..off.saturating_add(needle.len()))
+                    .is_some_and(|win| approx_eq(win, &needle, tolerance))
+            })
+            .collect()
+    } else {
+        approx_matches(&app.data[range.clone()], &needle, tolerance)
+            .map(|off| range.start + off)
+            .collect()
+    };
+    for offset in aligned(matches.into_iter(), dia.alignment, &mut dia.unaligned_count) {
+        dia.results_vec.push(offset);
+        dia.results_set.insert(offset);
+    }
+}
+
+/// Whether every byte of `data` is within `tolerance` of the corresponding needle value
+fn approx_eq(data: &[u8], needle: &[u8], tolerance: u8) -> bool {
+    data.len() == needle.len()
+        && data.iter().zip(needle).all(|(&b, &n)| b.abs_diff(n) <= tolerance)
+}
+
+/// Offsets in `haystack` where `needle` matches approximately (see [`approx_eq`])
+fn approx_matches<'a>(
+    haystack: &'a [u8],
+    needle: &'a [u8],
+    tolerance: u8,
+) -> impl Iterator<Item = usize> + 'a {
+    haystack
+        .windows(needle.len().max(1))
+        .enumerate()
+        .filter_map(move |(i, win)| approx_eq(win, needle, tolerance).then_some(i))
+}
+
+/// Copy the chunk of `data` starting at `start`, at most `chunk_size` bytes long and ending
+/// before `end`.
+///
+/// The copy extends up to `extra` bytes past the chunk (but not past `end`), so matches
+/// straddling the chunk boundary are found.
+fn copy_chunk(data: &[u8], start: usize, end: usize, chunk_size: usize, extra: usize) -> Chunk {
+    let chunk_end = start.saturating_add(chunk_size.max(1)).min(end);
+    let window_end = chunk_end.saturating_add(extra).min(end);
+    Chunk {
+        start,
+        len: chunk_end - start,
+        bytes: data[start..window_end].to_vec(),
     }
 }
+
+/// Search the chunks received from `chunks` for `needle`, sending the matches of each chunk
+/// to `tx`.
+///
+/// Stops when there are no more chunks, `cancel` is set, or the receiving end is gone.
+fn search_worker(
+    needle: &[u8],
+    chunks: &Receiver<Chunk>,
+    tx: &Sender<ChunkResult>,
+    cancel: &AtomicBool,
+) {
+    let finder = memchr::memmem::Finder::new(needle);
+    for chunk in chunks {
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        let offsets = finder
+            .find_iter(&chunk.bytes)
+            .filter(|&off| off < chunk.len)
+            .map(|off| chunk.start + off)
+            .collect();
+        let result = ChunkResult {
+            scanned: chunk.len,
+            offsets,
+        };
+        if tx.send(result).is_err() {
+            return;
+        }
+    }
+}
+
+/// Filter out offsets that aren't a multiple of `alignment`, counting them in `skipped`
+fn aligned<'a>(
+    offsets: impl Iterator<Item = usize> + 'a,
+    alignment: usize,
+    skipped: &'a mut usize,
+) -> impl Iterator<Item = usize> + 'a {
+    let alignment = alignment.max(1);
+    offsets.filter(move |&off| {
+        let ok = off % alignment == 0;
+        if !ok {
+            *skipped += 1;
+        }
+        ok
+    })
+}
+
+#[test]
+fn test_search_worker_chunk_boundaries() {
+    let data = b"xxabcabcabcabxx";
+    // Search 2..13 in chunks of 4 bytes, like `BgSearch::feed` does
+    let search = |needle: &[u8]| {
+        let (chunk_tx, chunk_rx) = std::sync::mpsc::channel();
+        let mut pos = 2;
+        while pos < 13 {
+            let chunk = copy_chunk(data, pos, 13, 4, needle.len() - 1);
+            pos += chunk.len;
+            chunk_tx.send(chunk).unwrap();
+        }
+        drop(chunk_tx);
+        let (tx, rx) = std::sync::mpsc::channel();
+        search_worker(needle, &chunk_rx, &tx, &AtomicBool::new(false));
+        drop(tx);
+        rx.iter().collect::<Vec<ChunkResult>>()
+    };
+    let chunks = search(b"cab");
+    assert_eq!(chunks.iter().map(|c| c.scanned).sum::<usize>(), 11);
+    let offsets: Vec<usize> = chunks.into_iter().flat_map(|c| c.offsets).collect();
+    assert_eq!(offsets, [4, 7, 10]);
+    // The `cabx` at 10 sticks out of the searched range
+    assert!(search(b"cabx").iter().all(|c| c.offsets.is_empty()));
+}
+
+#[test]
+fn test_step_wrapping() {
+    assert_eq!(step_wrapping(0, 3, true), (1, false));
+    assert_eq!(step_wrapping(2, 3, true), (0, true));
+    assert_eq!(step_wrapping(1, 3, false), (0, false));
+    assert_eq!(step_wrapping(0, 3, false), (2, true));
+    let mut results = vec![30, 10, 20, 40];
+    order_from_cursor(&mut results, 25);
+    assert_eq!(results, [30, 40, 10, 20]);
+}
+
+#[test]
+fn test_aligned() {
+    let mut skipped = 0;
+    let offsets: Vec<_> = aligned([0, 3, 4, 6, 8, 12].into_iter(), 4, &mut skipped).collect();
+    assert_eq!(offsets, [0, 4, 8, 12]);
+    assert_eq!(skipped, 2);
+    let offsets: Vec<_> = aligned([1, 3].into_iter(), 0, &mut skipped).collect();
+    assert_eq!(offsets, [1, 3]);
+}
+
+#[test]
+fn test_approx_matches() {
+    let haystack = [10u8, 20, 30, 12, 19, 33, 0];
+    let matches: Vec<_> = approx_matches(&haystack, &[10, 20, 30], 3).collect();
+    assert_eq!(matches, [0, 3]);
+    let matches: Vec<_> = approx_matches(&haystack, &[10, 20, 30], 0).collect();
+    assert_eq!(matches, [0]);
+    assert_eq!(approx_matches(&haystack[..2], &[10, 20, 30], 255).count(), 0);
+}
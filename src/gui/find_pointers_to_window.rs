@@ -0,0 +1,218 @@
+use {
+    super::{window_open::WindowOpen, Gui},
+    crate::{
+        app::App,
+        meta::{Bookmark, ValueType},
+        parse_radix::parse_guess_radix,
+        shell::msg_warn,
+    },
+    egui_extras::{Size, TableBuilder},
+    egui_sfml::egui,
+};
+
+/// Find locations that contain a pointer to the cursor (or an arbitrary value)
+pub struct FindPointersToWindow {
+    pub open: WindowOpen,
+    /// Use the cursor offset as the target, instead of `target_string`
+    use_cursor: bool,
+    target_string: String,
+    /// Added to the target, e.g. the start address of a process memory range
+    base_string: String,
+    width: PtrWidth,
+    results: Vec<PtrMatch>,
+}
+
+impl Default for FindPointersToWindow {
+    fn default() -> Self {
+        Self {
+            open: WindowOpen::default(),
+            use_cursor: true,
+            target_string: String::new(),
+            base_string: String::new(),
+            width: PtrWidth::Eight,
+            results: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PtrWidth {
+    Four,
+    Eight,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct PtrMatch {
+    offset: usize,
+    big_endian: bool,
+}
+
+impl FindPointersToWindow {
+    pub fn ui(ui: &mut egui::Ui, gui: &mut Gui, app: &mut App) {
+        let win = &mut gui.find_pointers_to_window;
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut win.use_cursor, "Target cursor offset");
+            ui.add_enabled(
+                !win.use_cursor,
+                egui::TextEdit::singleline(&mut win.target_string).hint_text("Target value"),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.label("Base address");
+            ui.text_edit_singleline(&mut win.base_string);
+            if let Some(seek) = app.args.src.hard_seek
+                && ui
+                    .button("Use source start")
+                    .on_hover_text("Use the address the source was opened at (process memory)")
+                    .clicked()
+            {
+                win.base_string = format!("0x{:X}", seek);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Pointer width");
+            ui.radio_value(&mut win.width, PtrWidth::Four, "4 bytes");
+            ui.radio_value(&mut win.width, PtrWidth::Eight, "8 bytes");
+        });
+        if ui.button("Search").clicked() {
+            win.search(&app.data, app.edit_state.cursor);
+        }
+        ui.separator();
+        ui.label(format!("{} results", win.results.len()));
+        let mut action = Action::None;
+        TableBuilder::new(ui)
+            .column(Size::initial(140.0))
+            .column(Size::initial(60.0))
+            .column(Size::remainder())
+            .striped(true)
+            .header(20.0, |mut row| {
+                row.col(|ui| {
+                    ui.label("Location");
+                });
+                row.col(|ui| {
+                    ui.label("Endian");
+                });
+                row.col(|ui| {
+                    ui.label("");
+                });
+            })
+            .body(|body| {
+                body.rows(20.0, win.results.len(), |idx, mut row| {
+                    let en = &win.results[idx];
+                    row.col(|ui| {
+                        if ui.link(format!("{:X}", en.offset)).clicked() {
+                            action = Action::Goto(en.offset);
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.label(if en.big_endian { "BE" } else { "LE" });
+                    });
+                    row.col(|ui| {
+                        if ui.button("Add bookmark").clicked() {
+                            action = Action::AddBookmark(en.offset);
+                        }
+                    });
+                });
+            });
+        match action {
+            Action::None => {}
+            Action::Goto(off) => {
+                app.center_view_on_offset(off);
+                app.edit_state.set_cursor(off);
+                app.hex_ui.flash_cursor();
+            }
+            Action::AddBookmark(off) => {
+                let idx = app.meta_state.meta.bookmarks.len();
+                app.meta_state.meta.bookmarks.push(Bookmark {
+                    offset: off,
+                    label: format!("Pointer @ {:X}", off),
+                    desc: String::new(),
+                    value_type: ValueType::None,
+                    big_endian: false,
+                });
+                gui.bookmarks_window.open.set(true);
+                gui.bookmarks_window.selected = Some(idx);
+            }
+        }
+        gui.find_pointers_to_window.open.post_ui();
+    }
+    /// Search for pointers to `offset` (a cross reference), and show the results
+    pub fn search_for_offset(&mut self, data: &[u8], offset: usize, width: PtrWidth) {
+        self.use_cursor = false;
+        self.target_string = format!("0x{:X}", offset);
+        self.width = width;
+        self.search(data, offset);
+        self.open.set(true);
+    }
+    fn search(&mut self, data: &[u8], cursor: usize) {
+        match self.target(cursor) {
+            Ok(target) => self.results = find_pointers(data, target, self.width),
+            Err(e) => msg_warn(&e),
+        }
+    }
+    /// The value to search for, with the base address applied
+    fn target(&self, cursor: usize) -> Result<u64, String> {
+        let value = if self.use_cursor {
+            cursor as u64
+        } else {
+            parse_guess_radix(self.target_string.trim())
+                .map_err(|e| format!("Invalid target value: {}", e))?
+        };
+        let base: u64 = if self.base_string.trim().is_empty() {
+            0
+        } else {
+            parse_guess_radix(self.base_string.trim())
+                .map_err(|e| format!("Invalid base address: {}", e))?
+        };
+        value.checked_add(base).ok_or_else(|| "Target overflows".into())
+    }
+}
+
+enum Action {
+    None,
+    Goto(usize),
+    AddBookmark(usize),
+}
+
+/// Find all little and big endian occurrences of `target` as a `width` sized integer
+fn find_pointers(data: &[u8], target: u64, width: PtrWidth) -> Vec<PtrMatch> {
+    let (le, be) = match width {
+        PtrWidth::Four => {
+            let Ok(target) = u32::try_from(target) else { return Vec::new() };
+            (target.to_le_bytes().to_vec(), target.to_be_bytes().to_vec())
+        }
+        PtrWidth::Eight => (target.to_le_bytes().to_vec(), target.to_be_bytes().to_vec()),
+    };
+    let mut results: Vec<PtrMatch> = memchr::memmem::find_iter(data, &le)
+        .map(|offset| PtrMatch {
+            offset,
+            big_endian: false,
+        })
+        .collect();
+    // A palindromic value would match at the same offsets twice
+    if be != le {
+        results.extend(
+            memchr::memmem::find_iter(data, &be).map(|offset| PtrMatch {
+                offset,
+                big_endian: true,
+            }),
+        );
+    }
+    results.sort_by_key(|m| m.offset);
+    results
+}
+
+#[test]
+fn test_find_pointers() {
+    let data = [0x10, 0x20, 0, 0, 0, 0, 0, 0, 0, 0, 0x20, 0x10];
+    let m = |offset, big_endian| PtrMatch { offset, big_endian };
+    assert_eq!(
+        find_pointers(&data, 0x2010, PtrWidth::Four),
+        [m(0, false), m(8, true)]
+    );
+    assert_eq!(
+        find_pointers(&data, 0x2010, PtrWidth::Eight),
+        [m(0, false), m(4, true)]
+    );
+    assert!(find_pointers(&data, u64::MAX, PtrWidth::Four).is_empty());
+}
@@ -1,11 +1,12 @@
 use {
     crate::{
-        app::{interact_mode::InteractMode, App},
+        app::{edit_state::EditState, interact_mode::InteractMode, App},
         damage_region::DamageRegion,
+        parse_radix::parse_guess_radix,
         shell::{msg_if_fail, msg_warn},
         view::ViewportVec,
     },
-    anyhow::bail,
+    anyhow::{bail, Context},
     egui_sfml::{
         egui::{self, Ui},
         sfml::window::clipboard,
@@ -36,12 +37,41 @@ pub struct InspectPanel {
     input_thingies: [Box<dyn InputThingyTrait>; 11],
     /// True if an input thingy was changed by the user. Should update the others
     changed_one: bool,
-    big_endian: bool,
+    /// Overrides the configured default byte order, if set
+    big_endian: Option<bool>,
+    /// The byte order used on the previous frame, to refresh when the default changes
+    prev_frame_big_endian: bool,
     format: Format,
     /// If true, go to offset action is relative to the hard seek argument
     offset_relative: bool,
+    /// Subtracted from decoded values when ctrl+clicking them to follow them as pointers
+    pointer_base: String,
     /// The value of the cursor on the previous frame. Used to determine when the cursor changes
     pub prev_frame_inspect_offset: usize,
+    /// Inspectors that stay on their offset, shown below the main one
+    pins: Vec<PinnedInspector>,
+}
+
+/// A read-only inspector frozen on an offset, independent of the cursor
+struct PinnedInspector {
+    offset: usize,
+    input_thingies: [Box<dyn InputThingyTrait>; 11],
+}
+
+fn input_thingies() -> [Box<dyn InputThingyTrait>; 11] {
+    [
+        Box::new(InputThingy::<i8>::default()),
+        Box::new(InputThingy::<u8>::default()),
+        Box::new(InputThingy::<i16>::default()),
+        Box::new(InputThingy::<u16>::default()),
+        Box::new(InputThingy::<i32>::default()),
+        Box::new(InputThingy::<u32>::default()),
+        Box::new(InputThingy::<i64>::default()),
+        Box::new(InputThingy::<u64>::default()),
+        Box::new(InputThingy::<f32>::default()),
+        Box::new(InputThingy::<f64>::default()),
+        Box::new(InputThingy::<Ascii>::default()),
+    ]
 }
 
 impl std::fmt::Debug for InspectPanel {
@@ -53,24 +83,15 @@ impl std::fmt::Debug for InspectPanel {
 impl Default for InspectPanel {
     fn default() -> Self {
         Self {
-            input_thingies: [
-                Box::new(InputThingy::<i8>::default()),
-                Box::new(InputThingy::<u8>::default()),
-                Box::new(InputThingy::<i16>::default()),
-                Box::new(InputThingy::<u16>::default()),
-                Box::new(InputThingy::<i32>::default()),
-                Box::new(InputThingy::<u32>::default()),
-                Box::new(InputThingy::<i64>::default()),
-                Box::new(InputThingy::<u64>::default()),
-                Box::new(InputThingy::<f32>::default()),
-                Box::new(InputThingy::<f64>::default()),
-                Box::new(InputThingy::<Ascii>::default()),
-            ],
+            input_thingies: input_thingies(),
             changed_one: false,
-            big_endian: false,
+            big_endian: None,
+            prev_frame_big_endian: false,
             format: Format::Decimal,
             offset_relative: false,
+            pointer_base: String::new(),
             prev_frame_inspect_offset: 0,
+            pins: Vec::new(),
         }
     }
 }
@@ -79,9 +100,11 @@ trait InputThingyTrait {
     fn update(&mut self, data: &[u8], offset: usize, be: bool, format: Format);
     fn label(&self) -> &'static str;
     fn buf_mut(&mut self) -> &mut String;
+    /// Write the value in the input to `data`, recording the edit in `edit_state`
     fn write_data(
         &self,
         data: &mut [u8],
+        edit_state: &mut EditState,
         offset: usize,
         be: bool,
         format: Format,
@@ -103,11 +126,12 @@ impl<T: BytesManip> InputThingyTrait for InputThingy<T> {
     fn write_data(
         &self,
         data: &mut [u8],
+        edit_state: &mut EditState,
         offset: usize,
         be: bool,
         format: Format,
     ) -> Option<DamageRegion> {
-        T::convert_and_write(&self.string, data, offset, be, format)
+        T::convert_and_write(&self.string, data, edit_state, offset, be, format)
     }
 }
 
@@ -311,6 +335,7 @@ impl<T: NumBytesManip> BytesManip for T {
     fn convert_and_write(
         buf: &str,
         data: &mut [u8],
+        edit_state: &mut EditState,
         offset: usize,
         be: bool,
         format: Format,
@@ -325,6 +350,7 @@ impl<T: NumBytesManip> BytesManip for T {
                 let range = offset..offset + bytes.as_ref().len();
                 match data.get_mut(range.clone()) {
                     Some(slice) => {
+                        edit_state.log_edit(offset, slice, bytes.as_ref());
                         slice.copy_from_slice(bytes.as_ref());
                         Some(DamageRegion::Range(range))
                     }
@@ -357,6 +383,7 @@ impl BytesManip for Ascii {
     fn convert_and_write(
         buf: &str,
         data: &mut [u8],
+        edit_state: &mut EditState,
         offset: usize,
         _be: bool,
         _format: Format,
@@ -365,6 +392,7 @@ impl BytesManip for Ascii {
         let range = offset..offset + len;
         match data.get_mut(range.clone()) {
             Some(slice) => {
+                edit_state.log_edit(offset, slice, buf.as_bytes());
                 slice.copy_from_slice(buf.as_bytes());
                 Some(DamageRegion::Range(range))
             }
@@ -396,6 +424,7 @@ trait BytesManip {
     fn convert_and_write(
         buf: &str,
         data: &mut [u8],
+        edit_state: &mut EditState,
         offset: usize,
         be: bool,
         format: Format,
@@ -441,15 +470,17 @@ pub fn ui(ui: &mut Ui, app: &mut App, gui: &mut crate::gui::Gui, mouse_pos: View
     if app.data.is_empty() {
         return;
     }
+    let big_endian = gui.inspect_panel.big_endian.unwrap_or(app.cfg.default_big_endian);
     if offset != gui.inspect_panel.prev_frame_inspect_offset
         || app.just_reloaded
         || gui.inspect_panel.changed_one
+        || big_endian != gui.inspect_panel.prev_frame_big_endian
     {
         for thingy in &mut gui.inspect_panel.input_thingies {
             thingy.update(
                 &app.data[..],
                 offset,
-                gui.inspect_panel.big_endian,
+                big_endian,
                 gui.inspect_panel.format,
             );
         }
@@ -458,29 +489,45 @@ pub fn ui(ui: &mut Ui, app: &mut App, gui: &mut crate::gui::Gui, mouse_pos: View
     let mut actions = Vec::new();
     for thingy in &mut gui.inspect_panel.input_thingies {
         ui.horizontal(|ui| {
-            ui.label(thingy.label());
+            let format = gui.inspect_panel.format;
+            if ui
+                .link(thingy.label())
+                .on_hover_text(
+                    "Click to go to the value as an offset\n\
+                     Ctrl+click to subtract the pointer base first",
+                )
+                .clicked()
+            {
+                let subtract_base = ui.input().modifiers.ctrl;
+                let result: anyhow::Result<()> = try {
+                    let mut offset = parse_offset(thingy.buf_mut(), format)?;
+                    if subtract_base {
+                        let base = gui.inspect_panel.pointer_base.trim();
+                        let base: usize = if base.is_empty() {
+                            0
+                        } else {
+                            parse_guess_radix(base).context("Invalid pointer base")?
+                        };
+                        offset = offset
+                            .checked_sub(base)
+                            .context("Value is below the pointer base")?;
+                    }
+                    actions.push(Action::GoToOffset(offset));
+                };
+                msg_if_fail(result, "Failed to go to offset");
+            }
             if ui.button("📋").on_hover_text("copy to clipboard").clicked() {
                 clipboard::set_string(&*thingy.buf_mut());
             }
             if ui.button("⬇").on_hover_text("go to offset").clicked() {
                 let result: anyhow::Result<()> = try {
-                    let offset = match gui.inspect_panel.format {
-                        Format::Decimal => thingy.buf_mut().parse()?,
-                        Format::Hex => usize::from_str_radix(thingy.buf_mut(), 16)?,
-                        Format::Bin => usize::from_str_radix(thingy.buf_mut(), 2)?,
-                    };
-                    actions.push(Action::GoToOffset(offset));
+                    actions.push(Action::GoToOffset(parse_offset(thingy.buf_mut(), format)?));
                 };
                 msg_if_fail(result, "Failed to go to offset");
             }
             if ui.button("➡").on_hover_text("jump forward").clicked() {
                 let result: anyhow::Result<()> = try {
-                    let offset = match gui.inspect_panel.format {
-                        Format::Decimal => thingy.buf_mut().parse()?,
-                        Format::Hex => usize::from_str_radix(thingy.buf_mut(), 16)?,
-                        Format::Bin => usize::from_str_radix(thingy.buf_mut(), 2)?,
-                    };
-                    actions.push(Action::JumpForward(offset));
+                    actions.push(Action::JumpForward(parse_offset(thingy.buf_mut(), format)?));
                 };
                 msg_if_fail(result, "Failed to jump forward");
             }
@@ -490,8 +537,9 @@ pub fn ui(ui: &mut Ui, app: &mut App, gui: &mut crate::gui::Gui, mouse_pos: View
         {
             if let Some(range) = thingy.write_data(
                 &mut app.data,
+                &mut app.edit_state,
                 offset,
-                gui.inspect_panel.big_endian,
+                big_endian,
                 gui.inspect_panel.format,
             ) {
                 gui.inspect_panel.changed_one = true;
@@ -500,13 +548,24 @@ pub fn ui(ui: &mut Ui, app: &mut App, gui: &mut crate::gui::Gui, mouse_pos: View
         }
     }
     ui.horizontal(|ui| {
+        let mut be = big_endian;
         if ui
-            .checkbox(&mut gui.inspect_panel.big_endian, "Big endian")
+            .checkbox(&mut be, "Big endian")
+            .on_hover_text("Overrides the default byte order from the preferences")
             .clicked()
         {
+            gui.inspect_panel.big_endian = Some(be);
             // Changing this should refresh everything
             gui.inspect_panel.changed_one = true;
         }
+        if gui.inspect_panel.big_endian.is_some()
+            && ui
+                .small_button("↺")
+                .on_hover_text("Use the default byte order")
+                .clicked()
+        {
+            gui.inspect_panel.big_endian = None;
+        }
         let prev_fmt = gui.inspect_panel.format;
         egui::ComboBox::new("format_combo", "format")
             .selected_text(gui.inspect_panel.format.label())
@@ -533,6 +592,60 @@ pub fn ui(ui: &mut Ui, app: &mut App, gui: &mut crate::gui::Gui, mouse_pos: View
             gui.inspect_panel.changed_one = true;
         }
     });
+    ui.horizontal(|ui| {
+        ui.label("Pointer base");
+        ui.add(
+            egui::TextEdit::singleline(&mut gui.inspect_panel.pointer_base)
+                .hint_text("e.g. 0x400000")
+                .desired_width(100.0),
+        )
+        .on_hover_text("Subtracted from a value when ctrl+clicking its label");
+    });
+    if ui
+        .button("📌 Pin")
+        .on_hover_text("Keep showing the values at this offset, wherever the cursor goes")
+        .clicked()
+    {
+        gui.inspect_panel.pins.push(PinnedInspector {
+            offset,
+            input_thingies: input_thingies(),
+        });
+    }
+    let mut unpin = None;
+    let mut pin_goto = None;
+    for (i, pin) in gui.inspect_panel.pins.iter_mut().enumerate() {
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label(format!("📌 {} (0x{:x})", pin.offset, pin.offset));
+            if ui.button("⬇").on_hover_text("go to offset").clicked() {
+                pin_goto = Some(pin.offset);
+            }
+            if ui.button("🗙").on_hover_text("Unpin").clicked() {
+                unpin = Some(i);
+            }
+        });
+        egui::Grid::new(("pinned_inspector", i)).show(ui, |ui| {
+            for thingy in &mut pin.input_thingies {
+                thingy.update(
+                    &app.data,
+                    pin.offset,
+                    big_endian,
+                    gui.inspect_panel.format,
+                );
+                ui.label(thingy.label());
+                ui.label(thingy.buf_mut().as_str());
+                ui.end_row();
+            }
+        });
+    }
+    if let Some(i) = unpin {
+        gui.inspect_panel.pins.remove(i);
+    }
+    if let Some(off) = pin_goto {
+        app.edit_state.set_cursor(off);
+        app.center_view_on_offset(off);
+        app.hex_ui.flash_cursor();
+    }
 
     for action in actions {
         match action {
@@ -555,6 +668,16 @@ pub fn ui(ui: &mut Ui, app: &mut App, gui: &mut crate::gui::Gui, mouse_pos: View
         }
     }
     gui.inspect_panel.prev_frame_inspect_offset = offset;
+    gui.inspect_panel.prev_frame_big_endian = big_endian;
+}
+
+/// Parse a value shown in `format` as an offset
+fn parse_offset(buf: &str, format: Format) -> anyhow::Result<usize> {
+    Ok(match format {
+        Format::Decimal => buf.parse()?,
+        Format::Hex => usize::from_str_radix(buf, 16)?,
+        Format::Bin => usize::from_str_radix(buf, 2)?,
+    })
 }
 
 fn edit_offset(app: &mut App, gui: &mut crate::gui::Gui, ui: &mut Ui) -> usize {
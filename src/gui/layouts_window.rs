@@ -2,7 +2,7 @@ use {
     super::window_open::WindowOpen,
     crate::{
         app::App,
-        layout::{default_margin, Layout},
+        layout::{default_margin, Direction, Layout},
         meta::{LayoutKey, MetaLow, NamedView, ViewKey, ViewMap},
         view::{HexData, View, ViewKind},
     },
@@ -24,7 +24,8 @@ impl LayoutsWindow {
         app: &mut crate::app::App,
     ) {
         let win = &mut gui.layouts_window;
-        if win.open.just_now() {
+        // Switching tabs can leave a layout of another file selected
+        if win.open.just_now() || !app.meta_state.meta.layouts.contains_key(win.selected) {
             win.selected = app.hex_ui.current_layout;
         }
         for (k, v) in &app.meta_state.meta.layouts {
@@ -57,10 +58,18 @@ impl LayoutsWindow {
                 .collect();
             egui::Grid::new("view_grid").show(ui, |ui| {
                 let mut swap = None;
+                let mut move_view = None;
+                let mut move_row = None;
+                let n_rows = layout.view_grid.len();
+                let mut row_idx = 0;
                 layout.view_grid.retain_mut(|row| {
                     let mut retain_row = true;
+                    let row_len = row.len();
+                    let mut col_idx = 0;
                     row.retain_mut(|view_key| {
                         let mut retain = true;
+                        let (is_first, is_last) = (col_idx == 0, col_idx + 1 == row_len);
+                        col_idx += 1;
                         let view = &app.meta_state.meta.views[*view_key];
                         if win.swap_a == *view_key {
                             if ui.selectable_label(true, &view.name).clicked() {
@@ -85,6 +94,20 @@ impl LayoutsWindow {
                                     win.swap_a = *view_key;
                                     ui.close_menu();
                                 }
+                                if ui
+                                    .add_enabled(!is_first, egui::Button::new("⬅ Move left"))
+                                    .clicked()
+                                {
+                                    move_view = Some((*view_key, Direction::Prev));
+                                    ui.close_menu();
+                                }
+                                if ui
+                                    .add_enabled(!is_last, egui::Button::new("➡ Move right"))
+                                    .clicked()
+                                {
+                                    move_view = Some((*view_key, Direction::Next));
+                                    ui.close_menu();
+                                }
                                 if ui.button("🗑 Remove").clicked() {
                                     retain = false;
                                     ui.close_menu();
@@ -120,9 +143,24 @@ impl LayoutsWindow {
                         .on_hover_text("Add view")
                         .on_disabled_hover_text("No views to add (all added)");
                     });
+                    if ui
+                        .add_enabled(row_idx > 0, egui::Button::new("⏶"))
+                        .on_hover_text("Move row up")
+                        .clicked()
+                    {
+                        move_row = Some((row_idx, Direction::Prev));
+                    }
+                    if ui
+                        .add_enabled(row_idx + 1 < n_rows, egui::Button::new("⏷"))
+                        .on_hover_text("Move row down")
+                        .clicked()
+                    {
+                        move_row = Some((row_idx, Direction::Next));
+                    }
                     if ui.button("🗑").on_hover_text("Delete row").clicked() {
                         retain_row = false;
                     }
+                    row_idx += 1;
                     ui.end_row();
                     if row.is_empty() {
                         retain_row = false;
@@ -141,6 +179,12 @@ impl LayoutsWindow {
                         }
                     }
                 }
+                if let Some((key, dir)) = move_view {
+                    layout.move_view_in_row(key, dir);
+                }
+                if let Some((idx, dir)) = move_row {
+                    layout.move_row(idx, dir);
+                }
                 ui.add_enabled_ui(!unused_views.is_empty(), |ui| {
                     ui.menu_button("✚", |ui| {
                         for &k in &unused_views {
@@ -167,6 +211,16 @@ impl LayoutsWindow {
                 ui.label("Margin");
                 ui.add(egui::DragValue::new(&mut layout.margin).clamp_range(3..=64));
             });
+            if ui
+                .add_enabled(
+                    !layout.row_weights.is_empty() || !layout.col_weights.is_empty(),
+                    egui::Button::new("Reset sizes"),
+                )
+                .on_hover_text("Size views automatically again, after dragging their dividers")
+                .clicked()
+            {
+                layout.reset_weights();
+            }
         }
         ui.separator();
         if ui.button("New layout").clicked() {
@@ -174,6 +228,8 @@ impl LayoutsWindow {
                 name: "New layout".into(),
                 view_grid: Vec::new(),
                 margin: default_margin(),
+                row_weights: Vec::new(),
+                col_weights: Vec::new(),
             });
             win.selected = key;
             App::switch_layout(&mut app.hex_ui, &app.meta_state.meta, key);
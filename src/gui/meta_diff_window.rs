@@ -1,13 +1,14 @@
 use {
     super::window_open::WindowOpen,
     crate::{
+        app::App,
         layout::Layout,
         meta::{
-            perspective::Perspective, LayoutKey, NamedRegion, NamedView, PerspectiveKey, RegionKey,
-            ViewKey,
+            perspective::Perspective, Bookmark, Bookmarks, LayoutKey, NamedRegion, NamedView,
+            PerspectiveKey, RegionKey, ViewKey,
         },
     },
-    itertools::{EitherOrBoth, Itertools},
+    egui_sfml::egui::Button,
     slotmap::SlotMap,
     std::fmt::Debug,
 };
@@ -17,17 +18,53 @@ pub struct MetaDiffWindow {
     pub open: WindowOpen,
 }
 impl MetaDiffWindow {
-    pub(crate) fn ui(ui: &mut egui_sfml::egui::Ui, app: &mut crate::app::App) {
-        let this = &mut app.meta_state.meta;
-        let clean = &app.meta_state.clean_meta;
-        ui.heading("Regions");
-        diff_slotmap(ui, &mut this.low.regions, &clean.low.regions);
-        ui.heading("Perspectives");
-        diff_slotmap(ui, &mut this.low.perspectives, &clean.low.perspectives);
-        ui.heading("Views");
-        diff_slotmap(ui, &mut this.views, &clean.views);
-        ui.heading("Layouts");
-        diff_slotmap(ui, &mut this.layouts, &clean.layouts);
+    pub(crate) fn ui(ui: &mut egui_sfml::egui::Ui, app: &mut App) {
+        let meta_state = &mut app.meta_state;
+        ui.horizontal(|ui| {
+            if ui
+                .button("Restore all")
+                .on_hover_text("Discard all changes")
+                .clicked()
+            {
+                meta_state.meta = meta_state.clean_meta.clone();
+            }
+            if ui
+                .button("Accept all")
+                .on_hover_text("Consider the current state clean")
+                .clicked()
+            {
+                meta_state.clean_meta = meta_state.meta.clone();
+            }
+        });
+        ui.separator();
+        let this = &mut meta_state.meta;
+        let clean = &meta_state.clean_meta;
+        egui_sfml::egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.heading("Regions");
+            let used: Vec<_> = this.low.perspectives.values().map(|per| per.region).collect();
+            diff_slotmap(ui, &mut this.low.regions, &clean.low.regions, &used);
+            ui.heading("Perspectives");
+            let used: Vec<_> = this.views.values().map(|view| view.view.perspective).collect();
+            diff_slotmap(ui, &mut this.low.perspectives, &clean.low.perspectives, &used);
+            ui.heading("Views");
+            let used: Vec<_> = this.layouts.values().flat_map(|layout| layout.iter()).collect();
+            diff_slotmap(ui, &mut this.views, &clean.views, &used);
+            ui.heading("Layouts");
+            diff_slotmap(ui, &mut this.layouts, &clean.layouts, &[]);
+            ui.heading("Bookmarks");
+            diff_bookmarks(ui, &mut this.bookmarks, &clean.bookmarks);
+        });
+        // Reverting can remove the focused view or current layout
+        let meta = &app.meta_state.meta;
+        if let Some(key) = app.hex_ui.focused_view && !meta.views.contains_key(key) {
+            app.hex_ui.focused_view = None;
+        }
+        if !meta.layouts.contains_key(app.hex_ui.current_layout) {
+            match meta.layouts.keys().next() {
+                Some(key) => App::switch_layout(&mut app.hex_ui, meta, key),
+                None => app.hex_ui.clear_meta_refs(),
+            }
+        }
     }
 }
 
@@ -94,45 +131,136 @@ impl SlotmapDiffItem for Layout {
     }
 }
 
+/// `used` are the keys other items refer to. New items with these keys can't be reverted
+/// (removed), as that would leave the references dangling.
 fn diff_slotmap<I: SlotmapDiffItem>(
     ui: &mut egui_sfml::egui::Ui,
     this: &mut SlotMap<I::Key, I>,
     clean: &SlotMap<I::Key, I>,
+    used: &[I::Key],
 ) {
+    // The clean meta is a clone of the current one, so keys of unchanged items match
     let mut this_keys: Vec<_> = this.keys().collect();
     this_keys.sort_by_key(|&k| this[k].sort_key());
-    let mut clean_keys: Vec<_> = clean.keys().collect();
-    clean_keys.sort_by_key(|&k| clean[k].sort_key());
+    let mut removed_keys: Vec<_> = clean.keys().filter(|&k| !this.contains_key(k)).collect();
+    removed_keys.sort_by_key(|&k| clean[k].sort_key());
     let mut any_changed = false;
-    for zip_item in this_keys.into_iter().zip_longest(clean_keys) {
-        match zip_item {
-            EitherOrBoth::Both(this_key, clean_key) => {
-                if this_key != clean_key {
-                    ui.label("-");
-                    any_changed = true;
-                    continue;
-                }
-                let this_item = &this[this_key];
-                let clean_item = &clean[clean_key];
-                if this_item != clean_item {
-                    any_changed = true;
+    let mut revert = None;
+    for this_key in this_keys {
+        let this_item = &this[this_key];
+        match clean.get(this_key) {
+            Some(clean_item) if clean_item == this_item => {}
+            Some(clean_item) => {
+                any_changed = true;
+                ui.horizontal(|ui| {
                     ui.label(format!(
-                        "{}: {:?}\n=>\n{:?}",
+                        "Modified {}: {:?}\n=>\n{:?}",
                         this_item.label(),
-                        this_item,
-                        clean_item
+                        clean_item,
+                        this_item
                     ));
-                }
+                    if ui.button("↺ Revert").clicked() {
+                        revert = Some(this_key);
+                    }
+                });
             }
-            EitherOrBoth::Left(this_key) => {
+            None => {
                 any_changed = true;
-                ui.label(format!("New {}", this[this_key].label()));
+                ui.horizontal(|ui| {
+                    ui.label(format!("New {}", this_item.label()));
+                    if ui
+                        .add_enabled(!used.contains(&this_key), Button::new("↺ Revert"))
+                        .on_hover_text("Remove it")
+                        .on_disabled_hover_text("It's still in use")
+                        .clicked()
+                    {
+                        revert = Some(this_key);
+                    }
+                });
             }
-            EitherOrBoth::Right(clean_key) => {
-                any_changed = true;
-                ui.label(format!("Deleted {}", clean[clean_key].label()));
+        }
+    }
+    for clean_key in removed_keys {
+        any_changed = true;
+        ui.label(format!("Deleted {}", clean[clean_key].label()))
+            .on_hover_text("Deleted items can only be brought back with Restore");
+    }
+    if let Some(key) = revert {
+        match clean.get(key) {
+            Some(clean_item) => this[key] = clean_item.clone(),
+            None => {
+                this.remove(key);
+            }
+        }
+    }
+    if any_changed {
+        let new_used = this.keys().any(|key| !clean.contains_key(key) && used.contains(&key));
+        if ui
+            .add_enabled(!new_used, Button::new("Restore"))
+            .on_disabled_hover_text("Some new items are still in use")
+            .clicked()
+        {
+            *this = clean.clone();
+        }
+    } else {
+        ui.label("No changes");
+    }
+}
+
+/// A bookmark diff entry to revert
+enum BookmarkRevert {
+    /// A bookmark of the clean state, modified or deleted since
+    Clean(usize),
+    /// A bookmark that was added since
+    New(usize),
+}
+
+fn diff_bookmarks(ui: &mut egui_sfml::egui::Ui, this: &mut Bookmarks, clean: &Bookmarks) {
+    let (pairs, matched) = pair_bookmarks(this, clean);
+    let mut any_changed = false;
+    let mut revert = None;
+    for (clean_idx, (clean_bm, pair)) in clean.iter().zip(&pairs).enumerate() {
+        let (label, text) = match *pair {
+            Some(idx) if this[idx] == *clean_bm => continue,
+            Some(idx) => (
+                "Modified",
+                format!("{:?}\n=>\n{:?}", clean_bm, this[idx]),
+            ),
+            None => ("Deleted", clean_bm.label.clone()),
+        };
+        any_changed = true;
+        ui.horizontal(|ui| {
+            ui.label(format!("{} #{}: {}", label, clean_idx, text));
+            if ui.button("↺ Revert").clicked() {
+                revert = Some(BookmarkRevert::Clean(clean_idx));
+            }
+        });
+    }
+    for (idx, this_bm) in this.iter().enumerate() {
+        if matched[idx] {
+            continue;
+        }
+        any_changed = true;
+        ui.horizontal(|ui| {
+            ui.label(format!("New #{}: {}", idx, this_bm.label));
+            if ui.button("↺ Revert").clicked() {
+                revert = Some(BookmarkRevert::New(idx));
             }
+        });
+    }
+    match revert {
+        Some(BookmarkRevert::Clean(clean_idx)) => match pairs[clean_idx] {
+            Some(idx) => this[idx] = clean[clean_idx].clone(),
+            None => {
+                // Put it back in front of the bookmark that followed it
+                let at = pairs[clean_idx + 1..].iter().flatten().next().copied();
+                this.insert(at.unwrap_or(this.len()), clean[clean_idx].clone());
+            }
+        },
+        Some(BookmarkRevert::New(idx)) => {
+            this.remove(idx);
         }
+        None => {}
     }
     if any_changed {
         if ui.button("Restore").clicked() {
@@ -142,3 +270,42 @@ fn diff_slotmap<I: SlotmapDiffItem>(
         ui.label("No changes");
     }
 }
+
+/// Find the counterpart in `this` of each bookmark in `clean`, by offset and label.
+///
+/// Returns the index of the counterpart for each clean bookmark, and for each bookmark in `this`
+/// whether it has a clean counterpart.
+fn pair_bookmarks(this: &[Bookmark], clean: &[Bookmark]) -> (Vec<Option<usize>>, Vec<bool>) {
+    let mut matched = vec![false; this.len()];
+    let mut pairs = Vec::with_capacity(clean.len());
+    for clean_bm in clean {
+        let idx = (0..this.len()).find(|&idx| {
+            let bm = &this[idx];
+            !matched[idx] && bm.offset == clean_bm.offset && bm.label == clean_bm.label
+        });
+        if let Some(idx) = idx {
+            matched[idx] = true;
+        }
+        pairs.push(idx);
+    }
+    (pairs, matched)
+}
+
+#[test]
+fn test_pair_bookmarks() {
+    let bm = |offset, label: &str| Bookmark {
+        offset,
+        label: label.into(),
+        desc: String::new(),
+        value_type: crate::meta::ValueType::None,
+        big_endian: false,
+    };
+    let clean = [bm(1, "a"), bm(2, "b"), bm(3, "c"), bm(4, "d")];
+    // "b" was deleted, "d" modified, and "e" added
+    let mut d = bm(4, "d");
+    d.desc = "changed".into();
+    let this = [bm(1, "a"), bm(3, "c"), d, bm(5, "e")];
+    let (pairs, matched) = pair_bookmarks(&this, &clean);
+    assert_eq!(pairs, [Some(0), None, Some(1), Some(2)]);
+    assert_eq!(matched, [true, true, true, false]);
+}
@@ -19,6 +19,10 @@ pub struct OpenProcessWindow {
     addr_sort: Sort,
     size_sort: Sort,
     maps_sort_col: MapsSortColumn,
+    /// Start address (hex) of a custom range to open
+    custom_start_string: String,
+    custom_size: usize,
+    custom_write: bool,
 }
 
 #[derive(Default, Clone, Copy)]
@@ -87,6 +91,34 @@ impl OpenProcessWindow {
                     }
                 });
             }
+            ui.horizontal(|ui| {
+                ui.label("Custom range");
+                ui.add(
+                    egui::TextEdit::singleline(&mut win.custom_start_string)
+                        .hint_text("Start (hex)")
+                        .desired_width(140.0),
+                );
+                ui.add(egui::DragValue::new(&mut win.custom_size).prefix("size: "));
+                ui.checkbox(&mut win.custom_write, "Writable");
+                if ui.button("Open").clicked() {
+                    let start = win.custom_start_string.trim_start_matches("0x");
+                    match usize::from_str_radix(start, 16) {
+                        Ok(start) => {
+                            msg_if_fail(
+                                app.load_proc_memory(
+                                    pid,
+                                    start,
+                                    win.custom_size,
+                                    win.custom_write,
+                                    font,
+                                ),
+                                "Failed to load process memory",
+                            );
+                        }
+                        Err(e) => msg_fail(&e, "Invalid start address"),
+                    }
+                }
+            });
             TableBuilder::new(ui)
                 .column(Size::initial(140.0))
                 .column(Size::initial(80.0))
@@ -1,8 +1,13 @@
 //! Various common operations that are triggered by gui interactions
 
 use {
-    super::regions_window::RegionsWindow,
-    crate::{meta::region::Region, meta_state::MetaState},
+    super::{dialogs::UnsavedChangesDialog, regions_window::RegionsWindow, Gui},
+    crate::{
+        app::{App, FileOp},
+        meta::region::Region,
+        meta_state::MetaState,
+    },
+    egui_sfml::sfml::graphics::Font,
 };
 
 pub fn add_region_from_selection(
@@ -14,3 +19,12 @@ pub fn add_region_from_selection(
     gui_regions_window.open.set(true);
     gui_regions_window.selected_key = Some(key);
 }
+
+/// Run `op`, but ask what to do with unsaved changes first, if there are any
+pub fn request_file_op(gui: &mut Gui, app: &mut App, op: FileOp, font: &Font) {
+    if app.has_unsaved_changes() {
+        gui.add_dialog(UnsavedChangesDialog { op: Some(op) });
+    } else {
+        app.run_file_op(op, font);
+    }
+}
@@ -18,7 +18,7 @@ pub struct PerspectivesWindow {
 impl PerspectivesWindow {
     pub(crate) fn ui(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut crate::app::App) {
         TableBuilder::new(ui)
-            .columns(Size::remainder(), 4)
+            .columns(Size::remainder(), 7)
             .striped(true)
             .header(24.0, |mut row| {
                 row.col(|ui| {
@@ -33,6 +33,15 @@ impl PerspectivesWindow {
                 row.col(|ui| {
                     ui.label("Flip row order");
                 });
+                row.col(|ui| {
+                    ui.label("Guides");
+                });
+                row.col(|ui| {
+                    ui.label("Sectors");
+                });
+                row.col(|ui| {
+                    ui.label("Dimensions");
+                });
             })
             .body(|body| {
                 let keys: Vec<_> = app.meta_state.meta.low.perspectives.keys().collect();
@@ -87,6 +96,85 @@ impl PerspectivesWindow {
                             "",
                         );
                     });
+                    row.col(|ui| {
+                        let cursor = app.edit_state.cursor;
+                        let regions = &app.meta_state.meta.low.regions;
+                        let per = &mut app.meta_state.meta.low.perspectives[keys[idx]];
+                        ui.menu_button(format!("{} guides", per.guide_cols.len()), |ui| {
+                            let mut changed = false;
+                            per.guide_cols.retain_mut(|col| {
+                                let mut retain = true;
+                                ui.horizontal(|ui| {
+                                    changed |= ui
+                                        .add(egui::DragValue::new(col).prefix("column "))
+                                        .changed();
+                                    if ui.button("🗑").clicked() {
+                                        retain = false;
+                                    }
+                                });
+                                retain
+                            });
+                            if ui
+                                .button("✚ Add at cursor column")
+                                .on_hover_text("The guide is drawn in front of the column")
+                                .clicked()
+                            {
+                                let (_row, col) = per.row_col_of_byte_offset(cursor, regions);
+                                per.guide_cols.push(col);
+                                changed = true;
+                            }
+                            if changed {
+                                per.guide_cols.sort_unstable();
+                                per.guide_cols.dedup();
+                            }
+                        });
+                    });
+                    row.col(|ui| {
+                        let regions = &app.meta_state.meta.low.regions;
+                        let per = &mut app.meta_state.meta.low.perspectives[keys[idx]];
+                        let label = |size: usize| match size {
+                            0 => String::from("off"),
+                            n => n.to_string(),
+                        };
+                        egui::ComboBox::from_id_source(keys[idx])
+                            .selected_text(label(per.sector_size))
+                            .show_ui(ui, |ui| {
+                                for size in [0, 512, 4096] {
+                                    if ui
+                                        .selectable_value(&mut per.sector_size, size, label(size))
+                                        .clicked()
+                                        && size != 0
+                                    {
+                                        // One sector per row
+                                        per.cols = size;
+                                        per.clamp_cols(regions);
+                                    }
+                                }
+                            })
+                            .response
+                            .on_hover_text("Separate and number the sectors of disk images");
+                    });
+                    row.col(|ui| {
+                        let regions = &app.meta_state.meta.low.regions;
+                        let per = &app.meta_state.meta.low.perspectives[keys[idx]];
+                        if per.cols == 0 {
+                            ui.label("-");
+                            return;
+                        }
+                        let len = regions[per.region].region.len();
+                        let text = format!("{} rows, {} bytes", per.n_rows(regions), len);
+                        let (_, rem) = per.region_row_span(regions[per.region].region);
+                        if rem == 0 {
+                            ui.label(text);
+                        } else {
+                            ui.label(egui::RichText::new(text).color(egui::Color32::YELLOW))
+                                .on_hover_text(format!(
+                                    "Ragged last row: {} of {} columns. \
+                                     The region length is not a multiple of the column count.",
+                                    rem, per.cols
+                                ));
+                        }
+                    });
                 });
                 match action {
                     Action::None => {}
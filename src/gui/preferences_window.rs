@@ -1,16 +1,28 @@
 use {
     super::{window_open::WindowOpen, Gui},
-    crate::{app::App, config},
+    crate::{
+        app::App,
+        config,
+        keybinds::{self, KeyAction, KeyCombo},
+        shell::msg_if_fail,
+    },
     egui_sfml::egui,
 };
 
 #[derive(Default)]
 pub struct PreferencesWindow {
     pub open: WindowOpen,
+    new_read_only_ext: String,
+    /// The action we're waiting for a key combination for
+    rebinding: Option<KeyAction>,
 }
 
 impl PreferencesWindow {
-    pub fn ui(ui: &mut egui::Ui, _gui: &mut Gui, app: &mut App) {
+    /// While rebinding, key presses shouldn't trigger actions
+    pub fn is_rebinding(&self) -> bool {
+        self.rebinding.is_some()
+    }
+    pub fn ui(ui: &mut egui::Ui, gui: &mut Gui, app: &mut App) {
         let style = &mut app.cfg.style;
         ui.heading("Font sizes");
         let mut any_changed = false;
@@ -51,5 +63,161 @@ impl PreferencesWindow {
         if any_changed {
             crate::gui::set_font_sizes_ctx(ui.ctx(), style);
         }
+        ui.separator();
+        ui.heading("Read-only extensions")
+            .on_hover_text("Files with these extensions are always opened as read-only");
+        app.cfg.read_only_exts.retain(|ext| {
+            ui.horizontal(|ui| {
+                ui.label(ext);
+                !ui.button("🗑").clicked()
+            })
+            .inner
+        });
+        ui.horizontal(|ui| {
+            let win = &mut gui.preferences_window;
+            let re = ui.add(
+                egui::TextEdit::singleline(&mut win.new_read_only_ext).hint_text("e.g. iso"),
+            );
+            if (ui.button("Add").clicked()
+                || (re.lost_focus() && ui.input().key_pressed(egui::Key::Enter)))
+                && !win.new_read_only_ext.trim().is_empty()
+            {
+                app.cfg
+                    .read_only_exts
+                    .push(std::mem::take(&mut win.new_read_only_ext).trim().to_owned());
+            }
+        });
+        ui.separator();
+        ui.heading("Loading");
+        ui.horizontal(|ui| {
+            ui.label("Max file size (MiB)");
+            ui.add(egui::DragValue::new(&mut app.cfg.max_read_size_mib))
+                .on_hover_text(
+                    "Bigger files are refused instead of loaded into memory.\n0 = no limit",
+                );
+        });
+        ui.checkbox(
+            &mut app.cfg.base_address_from_filename,
+            "Base address from file name",
+        )
+        .on_hover_text(
+            "For files named like dump_0x1000.bin, set the display base address to the offset \
+             at the end of the name, unless the metadata already has one",
+        );
+        ui.separator();
+        ui.heading("Byte order");
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut app.cfg.default_big_endian, false, "Little endian");
+            ui.radio_value(&mut app.cfg.default_big_endian, true, "Big endian");
+        })
+        .response
+        .on_hover_text(
+            "Default for the inspect panel, length-prefixed selection and new bookmarks",
+        );
+        ui.separator();
+        ui.heading("Metadata");
+        ui.checkbox(&mut app.cfg.sidecar_meta, "Metafile sidecar")
+            .on_hover_text(
+                "Automatically save metadata next to the opened file \
+                 (<file>.hexerator_meta), and load it when reopening the file",
+            );
+        ui.separator();
+        ui.heading("Edit log");
+        ui.horizontal(|ui| {
+            let mut enabled = app.cfg.edit_log_path.is_some();
+            let mut changed = false;
+            if ui
+                .checkbox(&mut enabled, "Log edits to file")
+                .on_hover_text("Append every committed edit to a file, as an audit trail")
+                .changed()
+            {
+                if enabled {
+                    app.cfg.edit_log_path = rfd::FileDialog::new().save_file();
+                } else {
+                    app.cfg.edit_log_path = None;
+                }
+                changed = true;
+            }
+            if let Some(path) = &app.cfg.edit_log_path {
+                ui.label(path.display().to_string());
+                if ui.button("Change...").clicked()
+                    && let Some(path) = rfd::FileDialog::new().save_file()
+                {
+                    app.cfg.edit_log_path = Some(path);
+                    changed = true;
+                }
+            }
+            if changed {
+                msg_if_fail(app.reopen_edit_log(), "Failed to open edit log");
+            }
+        });
+        ui.separator();
+        ui.heading("Keybindings");
+        let win = &mut gui.preferences_window;
+        if let Some(action) = win.rebinding {
+            let pressed = ui.input().events.iter().find_map(|ev| match ev {
+                egui::Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                } => Some((format!("{:?}", key), *modifiers)),
+                _ => None,
+            });
+            if let Some((name, modifiers)) = pressed && keybinds::is_key_name(&name) {
+                app.cfg.keybinds.set(
+                    action,
+                    KeyCombo {
+                        key: name,
+                        ctrl: modifiers.ctrl,
+                        shift: modifiers.shift,
+                        alt: modifiers.alt,
+                    },
+                );
+                win.rebinding = None;
+            }
+        }
+        egui::Grid::new("keybinds_grid").striped(true).show(ui, |ui| {
+            for action in KeyAction::ALL {
+                ui.label(action.name());
+                if win.rebinding == Some(action) {
+                    ui.label("Press a key combination...");
+                    if ui.button("Cancel").clicked() {
+                        win.rebinding = None;
+                    }
+                } else {
+                    let label = app.cfg.keybinds.label(action);
+                    if ui
+                        .button(if label.is_empty() { "<unbound>" } else { &label })
+                        .on_hover_text("Click to rebind")
+                        .clicked()
+                    {
+                        win.rebinding = Some(action);
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Reset").clicked() {
+                            app.cfg.keybinds.reset(action);
+                        }
+                        if ui.button("Unbind").clicked() {
+                            app.cfg.keybinds.unbind(action);
+                        }
+                    });
+                }
+                let conflicts: Vec<_> = app
+                    .cfg
+                    .keybinds
+                    .conflicts(action)
+                    .map(KeyAction::name)
+                    .collect();
+                if conflicts.is_empty() {
+                    ui.label("");
+                } else {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        format!("Conflicts with {}", conflicts.join(", ")),
+                    );
+                }
+                ui.end_row();
+            }
+        });
     }
 }
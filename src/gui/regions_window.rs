@@ -1,6 +1,9 @@
 use {
     super::window_open::WindowOpen,
-    crate::{app::App, meta::RegionKey},
+    crate::{
+        app::{App, RegionComparison},
+        meta::RegionKey,
+    },
     egui_extras::{Size, TableBuilder},
     egui_sfml::egui::{self, Ui},
 };
@@ -11,8 +14,13 @@ pub struct RegionsWindow {
     pub selected_key: Option<RegionKey>,
     select_active: bool,
     rename_active: bool,
+    /// Region to compare the selected region against
+    compare_key: Option<RegionKey>,
+    name_filter_string: String,
 }
 
+const DEFAULT_TINT: [u8; 4] = [255, 255, 0, 40];
+
 #[macro_export]
 macro_rules! region_context_menu {
     ($ui:expr, $app:expr, $reg:expr, $action:expr) => {{
@@ -39,6 +47,12 @@ macro_rules! region_context_menu {
 
 impl RegionsWindow {
     pub fn ui(ui: &mut Ui, gui: &mut crate::gui::Gui, app: &mut App) {
+        // Switching tabs can leave a region of another file selected
+        if let Some(key) = gui.regions_window.selected_key
+            && !app.meta_state.meta.low.regions.contains_key(key)
+        {
+            gui.regions_window.selected_key = None;
+        }
         let button = egui::Button::new("Add selection as region");
         match app.hex_ui.selection() {
             Some(sel) => {
@@ -54,6 +68,10 @@ impl RegionsWindow {
                 ui.add_enabled(false, button);
             }
         }
+        ui.add(
+            egui::TextEdit::singleline(&mut gui.regions_window.name_filter_string)
+                .hint_text("Filter by name"),
+        );
         ui.separator();
         TableBuilder::new(ui)
             .striped(true)
@@ -80,6 +98,11 @@ impl RegionsWindow {
                 let mut keys: Vec<RegionKey> = app.meta_state.meta.low.regions.keys().collect();
                 let mut action = Action::None;
                 keys.sort_by_key(|k| app.meta_state.meta.low.regions[*k].region.begin);
+                let filter = gui.regions_window.name_filter_string.to_lowercase();
+                keys.retain(|&k| {
+                    filter.is_empty()
+                        || app.meta_state.meta.low.regions[k].name.to_lowercase().contains(&filter)
+                });
                 for k in keys {
                     body.row(20.0, |mut row| {
                         let reg = &app.meta_state.meta.low.regions[k];
@@ -99,12 +122,12 @@ impl RegionsWindow {
                             }
                         });
                         row.col(|ui| {
-                            if ui.link(reg.region.begin.to_string()).clicked() {
+                            if ui.link(app.display_offset(reg.region.begin).to_string()).clicked() {
                                 action = Action::Goto(reg.region.begin);
                             }
                         });
                         row.col(|ui| {
-                            if ui.link(reg.region.end.to_string()).clicked() {
+                            if ui.link(app.display_offset(reg.region.end).to_string()).clicked() {
                                 action = Action::Goto(reg.region.end);
                             }
                         });
@@ -161,13 +184,91 @@ impl RegionsWindow {
             } else {
                 ui.add_enabled(false, egui::Button::new("Set to selection"));
             }
+            ui.horizontal(|ui| {
+                let mut tinted = reg.tint.is_some();
+                if ui
+                    .checkbox(&mut tinted, "Tint")
+                    .on_hover_text("Tint the background of the bytes in this region")
+                    .changed()
+                {
+                    reg.tint = tinted.then_some(DEFAULT_TINT);
+                }
+                if let Some(tint) = &mut reg.tint {
+                    ui.color_edit_button_srgba_unmultiplied(tint);
+                }
+            });
             ui.label("Description");
             ui.text_edit_multiline(&mut reg.desc);
+            ui.horizontal(|ui| {
+                let regions = &app.meta_state.meta.low.regions;
+                let compare_key = &mut gui.regions_window.compare_key;
+                let other_name = compare_key
+                    .and_then(|k| regions.get(k))
+                    .map_or("-", |reg| reg.name.as_str());
+                egui::ComboBox::from_label("Compare with")
+                    .selected_text(other_name)
+                    .show_ui(ui, |ui| {
+                        for (k, other) in regions.iter().filter(|(k, _)| *k != key) {
+                            ui.selectable_value(compare_key, Some(k), &other.name);
+                        }
+                    });
+                if let Some(other) = compare_key.and_then(|k| regions.get(k))
+                    && ui.button("Compare").clicked()
+                {
+                    app.region_comparison = Some(RegionComparison::new(
+                        regions[key].region,
+                        other.region,
+                        &app.data,
+                    ));
+                }
+            });
             if ui.button("Delete").clicked() {
                 app.meta_state.meta.low.regions.remove(key);
                 gui.regions_window.selected_key = None;
             }
         }
+        if let Some(cmp) = &app.region_comparison {
+            ui.separator();
+            ui.label(format!(
+                "Comparing {}..={} with {}..={}",
+                cmp.a.begin, cmp.a.end, cmp.b.begin, cmp.b.end
+            ));
+            if cmp.a.len() != cmp.b.len() {
+                ui.label(format!(
+                    "Lengths differ ({} vs {}), only the first {} bytes are compared",
+                    cmp.a.len(),
+                    cmp.b.len(),
+                    cmp.a.len().min(cmp.b.len())
+                ));
+            }
+            let mut goto = None;
+            match cmp.first_diff {
+                None => {
+                    ui.label("Identical");
+                }
+                Some(first) => {
+                    ui.label(format!("{} differing bytes", cmp.diff_count));
+                    ui.horizontal(|ui| {
+                        ui.label("First difference at");
+                        if ui.link((cmp.a.begin + first).to_string()).clicked() {
+                            goto = Some(cmp.a.begin + first);
+                        }
+                        ui.label("/");
+                        if ui.link((cmp.b.begin + first).to_string()).clicked() {
+                            goto = Some(cmp.b.begin + first);
+                        }
+                    });
+                }
+            }
+            if ui.button("Clear comparison").clicked() {
+                app.region_comparison = None;
+            }
+            if let Some(off) = goto {
+                app.center_view_on_offset(off);
+                app.edit_state.set_cursor(off);
+                app.hex_ui.flash_cursor();
+            }
+        }
     }
 }
 
@@ -0,0 +1,171 @@
+use {
+    super::{
+        chunked_scan::{self, push_found, ChunkedScan, Found, Scanner},
+        window_open::WindowOpen,
+        Gui,
+    },
+    crate::app::App,
+    egui_extras::{Size, TableBuilder},
+    egui_sfml::egui,
+    std::ops::Range,
+};
+
+/// How many bytes are scanned per frame, so big files don't freeze the ui
+const SCAN_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
+/// Lists runs of a repeated byte value, like padding or erased flash
+pub struct RunsWindow {
+    pub open: WindowOpen,
+    /// Minimum number of repetitions a run needs to be listed
+    min_len: usize,
+    /// Only list runs of this value, if set
+    value: Option<u8>,
+    /// The runs found, along with their byte value
+    results: Vec<Found<u8>>,
+    /// The scan in progress, if any
+    scan: Option<ChunkedScan<RunScanner>>,
+}
+
+impl Default for RunsWindow {
+    fn default() -> Self {
+        Self {
+            open: WindowOpen::default(),
+            min_len: 16,
+            value: None,
+            results: Vec::new(),
+            scan: None,
+        }
+    }
+}
+
+struct RunScanner {
+    min_len: usize,
+    value: Option<u8>,
+    /// Start and value of the current run
+    run: Option<(usize, u8)>,
+}
+
+impl Scanner for RunScanner {
+    type Kind = u8;
+    fn scan(&mut self, data: &[u8], range: Range<usize>, out: &mut Vec<Found<u8>>) {
+        for (i, &byte) in data.iter().enumerate().take(range.end).skip(range.start) {
+            if self.run.is_some_and(|&(_, run_value)| run_value == byte) {
+                continue;
+            }
+            if let Some((start, run_value)) = self.run.take() {
+                push_found(out, start, i, self.min_len, run_value);
+            }
+            if self.value.map_or(true, |value| value == byte) {
+                self.run = Some((i, byte));
+            }
+        }
+    }
+    fn finish(&mut self, end: usize, out: &mut Vec<Found<u8>>) {
+        if let Some((start, run_value)) = self.run.take() {
+            push_found(out, start, end, self.min_len, run_value);
+        }
+    }
+}
+
+impl RunsWindow {
+    /// Forget the results and cancel the scan, as they refer to another file
+    pub fn clear(&mut self) {
+        self.results.clear();
+        self.scan = None;
+    }
+    pub fn ui(ui: &mut egui::Ui, gui: &mut Gui, app: &mut App) {
+        let win = &mut gui.runs_window;
+        ui.horizontal(|ui| {
+            ui.label("Min. length");
+            ui.add(egui::DragValue::new(&mut win.min_len).clamp_range(2..=usize::MAX));
+            let mut filter = win.value.is_some();
+            ui.checkbox(&mut filter, "Only value")
+                .on_hover_text("Only list runs of this byte value");
+            if filter {
+                let value = win.value.get_or_insert(0);
+                ui.add(egui::DragValue::new(value));
+                ui.label(format!("(0x{:02X})", value));
+            } else {
+                win.value = None;
+            }
+            if ui.button("Scan").clicked() {
+                win.results.clear();
+                let scanner = RunScanner {
+                    min_len: win.min_len,
+                    value: win.value,
+                    run: None,
+                };
+                win.scan = Some(ChunkedScan::new(scanner, app));
+            }
+        });
+        chunked_scan::progress_ui(ui, &mut win.scan, app, SCAN_CHUNK_SIZE, &mut win.results);
+        ui.label(format!("{} runs", win.results.len()));
+        ui.separator();
+        let mut select = None;
+        TableBuilder::new(ui)
+            .striped(true)
+            .column(Size::initial(120.0))
+            .column(Size::initial(100.0))
+            .column(Size::remainder())
+            .header(20.0, |mut row| {
+                row.col(|ui| {
+                    ui.label("Offset");
+                });
+                row.col(|ui| {
+                    ui.label("Length");
+                });
+                row.col(|ui| {
+                    ui.label("Value");
+                });
+            })
+            .body(|body| {
+                body.rows(20.0, win.results.len(), |i, mut row| {
+                    let run = &win.results[i];
+                    row.col(|ui| {
+                        if ui
+                            .link(app.display_offset(run.region.begin).to_string())
+                            .on_hover_text("Select run")
+                            .clicked()
+                        {
+                            select = Some(run.region);
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.label(run.region.len().to_string());
+                    });
+                    row.col(|ui| {
+                        ui.label(format!("{:02X}", run.kind));
+                    });
+                });
+            });
+        if let Some(region) = select {
+            app.hex_ui.select_a = Some(region.begin);
+            app.hex_ui.select_b = Some(region.end);
+            app.center_view_on_offset(region.begin);
+            app.edit_state.set_cursor(region.begin);
+            app.hex_ui.flash_cursor();
+        }
+        win.open.post_ui();
+    }
+}
+
+#[test]
+fn test_run_scanner() {
+    let data = [1, 0, 0, 0, 2, 0xff, 0xff, 0xff, 0xff, 0, 0, 0];
+    let scanner = |value| RunScanner {
+        min_len: 3,
+        value,
+        run: None,
+    };
+    let runs = |found: Vec<Found<u8>>| {
+        found
+            .iter()
+            .map(|run| (run.region.begin, run.region.len(), run.kind))
+            .collect::<Vec<_>>()
+    };
+    // Small chunks, so runs straddle chunk boundaries
+    let found = chunked_scan::scan_all(scanner(None), &data, 2);
+    assert_eq!(runs(found), [(1, 3, 0), (5, 4, 0xff), (9, 3, 0)]);
+    let found = chunked_scan::scan_all(scanner(Some(0xff)), &data, 100);
+    assert_eq!(runs(found), [(5, 4, 0xff)]);
+}
@@ -0,0 +1,218 @@
+use {
+    super::{
+        chunked_scan::{self, push_found, ChunkedScan, Found, Scanner},
+        window_open::WindowOpen,
+        Gui,
+    },
+    crate::app::App,
+    egui_extras::{Size, TableBuilder},
+    egui_sfml::egui,
+    std::ops::Range,
+};
+
+/// How many bytes are scanned per frame, so big files don't freeze the ui
+const SCAN_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Strings longer than this are truncated in the list
+const MAX_DISPLAY_LEN: usize = 256;
+
+/// Lists runs of printable text in the data, like `strings(1)`
+pub struct StringsWindow {
+    pub open: WindowOpen,
+    /// Minimum number of characters a run needs to be listed
+    min_len: usize,
+    /// Also look for (2 byte aligned) UTF-16 LE strings
+    utf16: bool,
+    results: Vec<Found<Encoding>>,
+    /// The scan in progress, if any
+    scan: Option<ChunkedScan<StringScanner>>,
+}
+
+impl Default for StringsWindow {
+    fn default() -> Self {
+        Self {
+            open: WindowOpen::default(),
+            min_len: 4,
+            utf16: false,
+            results: Vec::new(),
+            scan: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Encoding {
+    Ascii,
+    Utf16,
+}
+
+/// The text of a found string, truncated to [`MAX_DISPLAY_LEN`] characters
+fn string_text(found: &Found<Encoding>, data: &[u8]) -> String {
+    let Some(bytes) = data.get(found.region.begin..=found.region.end) else {
+        return String::new();
+    };
+    // Only printable ascii is found, so the high bytes of UTF-16 units are always 0
+    let step = match found.kind {
+        Encoding::Ascii => 1,
+        Encoding::Utf16 => 2,
+    };
+    bytes
+        .iter()
+        .step_by(step)
+        .take(MAX_DISPLAY_LEN)
+        .map(|&b| char::from(b))
+        .collect()
+}
+
+fn is_printable(byte: u8) -> bool {
+    byte.is_ascii_graphic() || byte == b' ' || byte == b'\t'
+}
+
+struct StringScanner {
+    /// Minimum number of characters
+    min_len: usize,
+    utf16: bool,
+    ascii_start: Option<usize>,
+    utf16_start: Option<usize>,
+}
+
+impl Scanner for StringScanner {
+    type Kind = Encoding;
+    fn scan(&mut self, data: &[u8], range: Range<usize>, out: &mut Vec<Found<Encoding>>) {
+        for i in range {
+            let printable = is_printable(data[i]);
+            match (self.ascii_start, printable) {
+                (None, true) => self.ascii_start = Some(i),
+                (Some(start), false) => {
+                    push_found(out, start, i, self.min_len, Encoding::Ascii);
+                    self.ascii_start = None;
+                }
+                _ => {}
+            }
+            if self.utf16 && i % 2 == 0 {
+                let printable = printable && data.get(i + 1) == Some(&0);
+                match (self.utf16_start, printable) {
+                    (None, true) => self.utf16_start = Some(i),
+                    (Some(start), false) => {
+                        push_found(out, start, i, self.min_len * 2, Encoding::Utf16);
+                        self.utf16_start = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    fn finish(&mut self, end: usize, out: &mut Vec<Found<Encoding>>) {
+        if let Some(start) = self.ascii_start.take() {
+            push_found(out, start, end, self.min_len, Encoding::Ascii);
+        }
+        if let Some(start) = self.utf16_start.take() {
+            push_found(out, start, end & !1, self.min_len * 2, Encoding::Utf16);
+        }
+    }
+}
+
+impl StringsWindow {
+    /// Forget the results and cancel the scan, as they refer to another file
+    pub fn clear(&mut self) {
+        self.results.clear();
+        self.scan = None;
+    }
+    pub fn ui(ui: &mut egui::Ui, gui: &mut Gui, app: &mut App) {
+        let win = &mut gui.strings_window;
+        ui.horizontal(|ui| {
+            ui.label("Min. length");
+            ui.add(egui::DragValue::new(&mut win.min_len).clamp_range(1..=1024));
+            ui.checkbox(&mut win.utf16, "UTF-16 LE")
+                .on_hover_text("Also find UTF-16 LE strings starting at even offsets");
+            if ui.button("Scan").clicked() {
+                win.results.clear();
+                let scanner = StringScanner {
+                    min_len: win.min_len,
+                    utf16: win.utf16,
+                    ascii_start: None,
+                    utf16_start: None,
+                };
+                win.scan = Some(ChunkedScan::new(scanner, app));
+            }
+        });
+        if chunked_scan::progress_ui(ui, &mut win.scan, app, SCAN_CHUNK_SIZE, &mut win.results) {
+            // Runs are added when they end, which isn't necessarily in order of offset
+            win.results.sort_by_key(|found| found.region.begin);
+        }
+        ui.label(format!("{} strings", win.results.len()));
+        ui.separator();
+        let mut goto = None;
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .column(Size::initial(100.0))
+            .column(Size::initial(60.0))
+            .column(Size::remainder())
+            .header(20.0, |mut row| {
+                row.col(|ui| {
+                    ui.label("Offset");
+                });
+                row.col(|ui| {
+                    ui.label("Kind");
+                });
+                row.col(|ui| {
+                    ui.label("Text");
+                });
+            })
+            .body(|body| {
+                body.rows(20.0, win.results.len(), |i, mut row| {
+                    let found = &win.results[i];
+                    row.col(|ui| {
+                        let offset = found.region.begin;
+                        if ui.link(app.display_offset(offset).to_string()).clicked() {
+                            goto = Some(offset);
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.label(match found.kind {
+                            Encoding::Ascii => "ascii",
+                            Encoding::Utf16 => "utf-16",
+                        });
+                    });
+                    row.col(|ui| {
+                        ui.label(egui::RichText::new(string_text(found, &app.data)).code());
+                    });
+                });
+            });
+        if let Some(off) = goto {
+            app.center_view_on_offset(off);
+            app.edit_state.set_cursor(off);
+            app.hex_ui.flash_cursor();
+        }
+        win.open.post_ui();
+    }
+}
+
+#[test]
+fn test_scanner() {
+    use crate::meta::region::Region;
+    let scanner = |utf16| StringScanner {
+        min_len: 4,
+        utf16,
+        ascii_start: None,
+        utf16_start: None,
+    };
+    let data = b"ab\0hello\x01wor\0ld!!";
+    // Small chunks, so runs straddle chunk boundaries
+    let out = chunked_scan::scan_all(scanner(false), data, 3);
+    let found: Vec<_> = out
+        .iter()
+        .map(|f| (f.region.begin, string_text(f, data)))
+        .collect();
+    assert_eq!(found, [(3, "hello".into()), (13, "ld!!".into())]);
+    let data = b"\x01\0h\0i\0y\0o\0\xff";
+    let out = chunked_scan::scan_all(scanner(true), data, 100);
+    assert_eq!(
+        out,
+        [Found {
+            region: Region { begin: 2, end: 9 },
+            kind: Encoding::Utf16
+        }]
+    );
+}
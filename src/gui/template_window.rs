@@ -0,0 +1,85 @@
+use {
+    super::{window_open::WindowOpen, Gui},
+    crate::{app::App, shell::msg_if_fail, template::Template},
+    egui_extras::{Size, TableBuilder},
+    egui_sfml::egui,
+};
+
+/// Checks the data against a template of expected byte values, and lists the deviations
+#[derive(Default)]
+pub struct TemplateWindow {
+    pub open: WindowOpen,
+}
+
+impl TemplateWindow {
+    pub fn ui(ui: &mut egui::Ui, gui: &mut Gui, app: &mut App) {
+        ui.horizontal(|ui| {
+            if ui.button("Load template...").clicked() {
+                if let Some(path) = rfd::FileDialog::new().pick_file() {
+                    let result = Template::load(path).map(|t| app.template = Some(t));
+                    msg_if_fail(result, "Failed to load template");
+                }
+            }
+            if app.template.is_some() && ui.button("Unload").clicked() {
+                app.template = None;
+            }
+        });
+        ui.label("Lines like `0x10: 4D 5A ?? 00` (hex bytes, ?? matches anything, # comments)");
+        let Some(template) = &app.template else {
+            ui.label("No template loaded");
+            gui.template_window.open.post_ui();
+            return;
+        };
+        ui.label(template.path.display().to_string());
+        let violations: Vec<_> = template.violations(&app.data).collect();
+        ui.label(format!(
+            "{} of {} checked bytes deviate",
+            violations.len(),
+            template.checked_count()
+        ));
+        ui.separator();
+        let mut goto = None;
+        TableBuilder::new(ui)
+            .striped(true)
+            .column(Size::initial(120.0))
+            .column(Size::initial(80.0))
+            .column(Size::remainder())
+            .header(20.0, |mut row| {
+                row.col(|ui| {
+                    ui.label("Offset");
+                });
+                row.col(|ui| {
+                    ui.label("Expected");
+                });
+                row.col(|ui| {
+                    ui.label("Actual");
+                });
+            })
+            .body(|body| {
+                body.rows(20.0, violations.len(), |i, mut row| {
+                    let violation = &violations[i];
+                    row.col(|ui| {
+                        let off = app.display_offset(violation.offset);
+                        if ui.link(format!("{} ({:X})", off, off)).clicked() {
+                            goto = Some(violation.offset);
+                        }
+                    });
+                    row.col(|ui| {
+                        ui.label(format!("{:02X}", violation.expected));
+                    });
+                    row.col(|ui| {
+                        ui.label(match violation.actual {
+                            Some(byte) => format!("{:02X}", byte),
+                            None => "out of bounds".into(),
+                        });
+                    });
+                });
+            });
+        if let Some(off) = goto {
+            app.center_view_on_offset(off);
+            app.edit_state.set_cursor(off);
+            app.hex_ui.flash_cursor();
+        }
+        gui.template_window.open.post_ui();
+    }
+}
@@ -1,12 +1,22 @@
 use {
     super::{
-        dialogs::{AutoSaveReloadDialog, JumpDialog, LuaFillDialog, PatternFillDialog},
+        dialogs::{
+            AutoSaveReloadDialog, DisplayBaseDialog, EditAsTextDialog, ExportCHeaderDialog,
+            ExportDataMapDialog, InsertBytesDialog, JumpDialog, LuaFillDialog, PatternFillDialog,
+            RandomFillDialog, SelectLengthPrefixedDialog, SelectToOffsetDialog,
+        },
+        ops::request_file_op,
         util::{button_with_shortcut, ButtonWithShortcut},
     },
     crate::{
-        app::{col_change_impl_view_perspective, App},
-        args::Args,
-        damage_region::DamageRegion,
+        app::{
+            col_change_impl_view_perspective, edit_op::EditOp, presentation::ColorPreset, App,
+            FileOp,
+        },
+        args::{Args, SourceArgs},
+        hex_dump::hex_dump,
+        keybinds::KeyAction,
+        preferences::{offset_list, OffsetBase, OffsetSeparator},
         shell::{msg_if_fail, msg_info},
         source::SourceProvider,
     },
@@ -14,15 +24,14 @@ use {
         egui::{self, Layout},
         sfml::{graphics::Font, window::clipboard},
     },
-    rand::{thread_rng, RngCore},
-    std::fmt::Write,
+    std::{fmt::Write, path::Path},
 };
 
 pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, font: &Font) {
     ui.horizontal(|ui| {
         ui.menu_button("File", |ui| {
-            if button_with_shortcut(ui, "Open...", "Ctrl+O").clicked() {
-                crate::shell::open_file(app, font);
+            if button_with_shortcut(ui, "Open...", &app.cfg.keybinds.label(KeyAction::Open)).clicked() {
+                request_file_op(gui, app, FileOp::Open, font);
                 ui.close_menu();
             }
             if ui.button("Advanced open...").clicked() {
@@ -34,29 +43,51 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 ui.close_menu();
             }
             let mut load = None;
-            if button_with_shortcut(ui, "Open previous", "Ctrl+P").on_hover_text("Can be used to switch between 2 files quickly for comparison").clicked() {
+            if button_with_shortcut(ui, "Open previous", &app.cfg.keybinds.label(KeyAction::OpenPrevious)).on_hover_text("Can be used to switch between 2 files quickly for comparison").clicked() {
                 crate::shell::open_previous(app, &mut load);
                 ui.close_menu();
             }
             ui.checkbox(&mut app.preferences.keep_meta, "Keep metadata").on_hover_text("Keep metadata when loading a new file");
             ui.menu_button("Recent", |ui| {
+                let recent_label = |entry: &SourceArgs| {
+                    entry
+                        .file
+                        .as_ref()
+                        .map(|path| path.display().to_string())
+                        .unwrap_or_else(|| String::from("Unnamed file"))
+                };
+                let mut unpin = None;
+                for entry in &app.cfg.pinned_recent {
+                    ui.horizontal(|ui| {
+                        if ui.button(recent_label(entry)).clicked() {
+                            load = Some(entry.clone());
+                            ui.close_menu();
+                        }
+                        ui.separator();
+                        if ui.button("📌").on_hover_text("Unpin").clicked() {
+                            unpin = Some(entry.clone());
+                        }
+                    });
+                    ui.separator();
+                }
+                if let Some(entry) = unpin {
+                    app.cfg.toggle_pin(&entry);
+                }
+                let pinned = &mut app.cfg.pinned_recent;
                 app.cfg.recent.retain(|entry| {
+                    if pinned.contains(entry) {
+                        return true;
+                    }
                     let mut retain = true;
                     ui.horizontal(|ui| {
-                        if ui
-                            .button(
-                                entry
-                                    .file
-                                    .as_ref()
-                                    .map(|path| path.display().to_string())
-                                    .unwrap_or_else(|| String::from("Unnamed file")),
-                            )
-                            .clicked()
-                        {
+                        if ui.button(recent_label(entry)).clicked() {
                             load = Some(entry.clone());
                             ui.close_menu();
                         }
                         ui.separator();
+                        if ui.button("📍").on_hover_text("Pin").clicked() {
+                            pinned.push(entry.clone());
+                        }
                         if ui.button("🗑").clicked() {
                             retain = false;
                         }
@@ -69,27 +100,32 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 if ui.add(egui::DragValue::new(&mut cap).prefix("list capacity: ")).changed() {
                     app.cfg.recent.set_capacity(cap);
                 }
-
+                if ui.button("Clear unpinned").clicked() {
+                    app.cfg.clear_unpinned_recent();
+                    ui.close_menu();
+                }
             });
             if let Some(args) = load {
-                msg_if_fail(
-                    app.load_file_args(Args{ src: args, recent: false, meta: None },font),
-                    "Failed to load file",
-                );
+                let args = Args{ src: args, recent: false, meta: None };
+                request_file_op(gui, app, FileOp::Load(args), font);
             }
             ui.separator();
             if ui
                 .add_enabled(
                     app.source.is_some_and(|src| src.attr.permissions.write) && app.edit_state.dirty_region.is_some(),
-                    ButtonWithShortcut("Save", "Ctrl+S"),
+                    ButtonWithShortcut("Save", &app.cfg.keybinds.label(KeyAction::Save)),
                 )
                 .clicked()
             {
                 msg_if_fail(app.save(), "Failed to save");
                 ui.close_menu();
             }
-            if button_with_shortcut(ui, "Reload", "Ctrl+R").clicked() {
-                msg_if_fail(app.reload(), "Failed to reload");
+            if ui.add_enabled(app.source.is_some_and(|src| src.attr.permissions.write), egui::Button::new("Flush to disk")).on_hover_text("Make sure everything saved so far reaches the disk.\nUnsaved edits are not written.").clicked() {
+                msg_if_fail(app.flush_to_disk(), "Failed to flush to disk");
+                ui.close_menu();
+            }
+            if button_with_shortcut(ui, "Reload", &app.cfg.keybinds.label(KeyAction::Reload)).clicked() {
+                request_file_op(gui, app, FileOp::Reload, font);
                 ui.close_menu();
             }
             if ui.button("Auto save/reload...").clicked() {
@@ -97,6 +133,21 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 gui.add_dialog(AutoSaveReloadDialog);
             }
             ui.separator();
+            if ui.add_enabled(app.source.is_some_and(|src| src.attr.permissions.write), egui::Button::new("Apply IPS patch...")).clicked() {
+                ui.close_menu();
+                if let Some(path) = rfd::FileDialog::new().add_filter("IPS patch", &["ips"]).pick_file() {
+                    msg_if_fail(app.apply_ips_patch(&path), "Failed to apply patch");
+                }
+            }
+            if ui.button("Create IPS patch...").on_hover_text("Create a patch from an original file to the current data").clicked() {
+                ui.close_menu();
+                if let Some(original) = rfd::FileDialog::new().set_title("Original file").pick_file()
+                    && let Some(out) = rfd::FileDialog::new().set_title("Save patch").add_filter("IPS patch", &["ips"]).save_file()
+                {
+                    msg_if_fail(app.create_ips_patch(&original, &out), "Failed to create patch");
+                }
+            }
+            ui.separator();
             if ui.button("Create backup").clicked() {
                 msg_if_fail(app.create_backup(), "Failed to create backup");
                 ui.close_menu();
@@ -106,18 +157,48 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 ui.close_menu();
             }
             ui.separator();
+            // Standard input has no path
+            let file_path = app.source_file().filter(|path| path.as_os_str() != "-").map(|path| path.to_owned());
+            if ui.add_enabled(file_path.is_some(), egui::Button::new("Copy file path")).clicked() {
+                if let Some(path) = &file_path {
+                    clipboard::set_string(&path.display().to_string());
+                }
+                ui.close_menu();
+            }
+            if ui.add_enabled(file_path.is_some(), egui::Button::new("Open containing folder")).clicked() {
+                if let Some(path) = &file_path {
+                    // Relative paths in the current directory have an empty parent
+                    let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+                    msg_if_fail(open::that(dir), "Failed to open folder");
+                }
+                ui.close_menu();
+            }
+            ui.separator();
             if ui.button("Preferences").clicked() {
                 gui.preferences_window.open.toggle();
                 ui.close_menu();
             }
             ui.separator();
-            if button_with_shortcut(ui, "Close", "Ctrl+W").clicked() {
-                app.close_file();
+            if button_with_shortcut(ui, "Close", &app.cfg.keybinds.label(KeyAction::Close)).clicked() {
+                request_file_op(gui, app, FileOp::Close, font);
+                ui.close_menu();
+            }
+            if ui.add_enabled(app.last_closed.is_some(), ButtonWithShortcut("Reopen last closed", &app.cfg.keybinds.label(KeyAction::ReopenClosed))).clicked() {
+                request_file_op(gui, app, FileOp::ReopenClosed, font);
+                ui.close_menu();
+            }
+            ui.separator();
+            if button_with_shortcut(ui, "New tab", &app.cfg.keybinds.label(KeyAction::NewTab)).clicked() {
+                app.new_tab();
+                ui.close_menu();
+            }
+            if ui.add_enabled(app.tabs.len() > 1, egui::Button::new("Close tab")).clicked() {
+                request_file_op(gui, app, FileOp::CloseTab, font);
                 ui.close_menu();
             }
         });
         ui.menu_button("Edit", |ui| {
-            if button_with_shortcut(ui, "Find...", "Ctrl+F").clicked() {
+            if button_with_shortcut(ui, "Find...", &app.cfg.keybinds.label(KeyAction::Find)).clicked() {
                 gui.find_dialog.open.toggle();
                 ui.close_menu();
             }
@@ -130,7 +211,11 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 app.hex_ui.select_b = Some(app.edit_state.cursor);
                 ui.close_menu();
             }
-            if button_with_shortcut(ui, "Select all in view", "Ctrl+A").clicked() {
+            if ui.button("Select from cursor to offset...").clicked() {
+                gui.add_dialog(SelectToOffsetDialog::default());
+                ui.close_menu();
+            }
+            if button_with_shortcut(ui, "Select all in view", &app.cfg.keybinds.label(KeyAction::SelectAll)).clicked() {
                 app.focused_view_select_all();
                 ui.close_menu();
             }
@@ -139,6 +224,20 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 app.hex_ui.select_b = None;
                 ui.close_menu();
             }
+            if ui.add_enabled(app.hex_ui.selection().is_some(), egui::Button::new("Invert selection")).on_hover_text("Select the larger of the parts before and after the selection").clicked() {
+                app.hex_ui.invert_selection(app.data.len());
+                ui.close_menu();
+            }
+            ui.add_enabled_ui(app.hex_ui.selection().is_some(), |ui| {
+                ui.menu_button("Expand selection to alignment", |ui| {
+                    for (label, align) in [("2", 2), ("4", 4), ("8", 8), ("16", 16), ("Page (4 KiB)", 4096)] {
+                        if ui.button(label).clicked() {
+                            app.hex_ui.align_selection(align, app.data.len());
+                            ui.close_menu();
+                        }
+                    }
+                });
+            });
             ui.separator();
             if ui.button("External command...").clicked() {
                 gui.external_command_window.open.toggle();
@@ -153,14 +252,45 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 gui.add_dialog(LuaFillDialog::default());
                 ui.close_menu();
             }
-            if ui.button("Random fill").clicked() {
+            if ui.add_enabled(app.hex_ui.selection().is_some(), egui::Button::new("Random fill...")).clicked() {
+                gui.add_dialog(RandomFillDialog::default());
+                ui.close_menu();
+            }
+            if ui.add_enabled(app.hex_ui.selection().is_some(), egui::Button::new("Edit as text...")).clicked() {
                 if let Some(sel) = app.hex_ui.selection() {
-                    let range = sel.begin..=sel.end;
-                    thread_rng().fill_bytes(&mut app.data[range.clone()]);
-                    app.edit_state.widen_dirty_region(DamageRegion::RangeInclusive(range));
+                    gui.add_dialog(EditAsTextDialog::new(sel, &app.data));
                 }
                 ui.close_menu();
             }
+            let repeat_hint = app.last_op.as_ref().map_or_else(|| "Nothing to repeat".into(), EditOp::describe);
+            if ui.add_enabled(app.last_op.is_some(), ButtonWithShortcut("Repeat last operation", &app.cfg.keybinds.label(KeyAction::RepeatLastOp))).on_hover_text(repeat_hint).clicked() {
+                app.repeat_last_op();
+                ui.close_menu();
+            }
+            ui.separator();
+            let resize_hint = "Only supported for regular files opened without seek/take";
+            if ui
+                .add_enabled(app.can_resize(), egui::Button::new("Insert bytes..."))
+                .on_disabled_hover_text(resize_hint)
+                .clicked()
+            {
+                gui.add_dialog(InsertBytesDialog::default());
+                ui.close_menu();
+            }
+            if ui
+                .add_enabled(
+                    app.can_resize() && app.hex_ui.selection().is_some(),
+                    egui::Button::new("Delete selection"),
+                )
+                .on_disabled_hover_text(resize_hint)
+                .clicked()
+            {
+                if let Some(sel) = app.hex_ui.selection() {
+                    msg_if_fail(app.delete_range(sel), "Failed to delete selection");
+                }
+                ui.close_menu();
+            }
+            ui.separator();
             if ui.button("Copy selection as hex").clicked() {
                 if let Some(sel) = app.hex_ui.selection() {
                     let mut s = String::new();
@@ -171,6 +301,71 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 }
                 ui.close_menu();
             }
+            if ui.button("Copy as hex dump").on_hover_text("Offsets, hex and ascii like hexdump -C, of the selection or the visible rows").clicked() {
+                if let Some(reg) = app.selection_or_visible() {
+                    let prefs = &app.preferences;
+                    let dump = hex_dump(&app.data[reg.begin..=reg.end], app.display_offset(reg.begin), prefs.copy_offset_base, prefs.hex_dump_group);
+                    clipboard::set_string(dump.trim_end());
+                }
+                ui.close_menu();
+            }
+            if ui.button("Copy cursor row as hex").on_hover_text("The row of the focused view's perspective").clicked() {
+                if let Some(per) = App::focused_perspective(&app.hex_ui, &app.meta_state.meta) {
+                    let rmap = &app.meta_state.meta.low.regions;
+                    let (row, _) = per.row_col_of_byte_offset(app.edit_state.cursor, rmap);
+                    clipboard::set_string(&offsets_hex(&app.data, &per.row_offsets(row, rmap)));
+                }
+                ui.close_menu();
+            }
+            if ui.button("Copy cursor column as hex").on_hover_text("The column across every row of the focused view's perspective").clicked() {
+                if let Some(per) = App::focused_perspective(&app.hex_ui, &app.meta_state.meta) {
+                    let rmap = &app.meta_state.meta.low.regions;
+                    let (_, col) = per.row_col_of_byte_offset(app.edit_state.cursor, rmap);
+                    clipboard::set_string(&offsets_hex(&app.data, &per.col_offsets(col, rmap)));
+                }
+                ui.close_menu();
+            }
+            if ui.button("Copy cursor offset").clicked() {
+                clipboard::set_string(&app.preferences.copy_offset_base.format(app.display_offset(app.edit_state.cursor)));
+                ui.close_menu();
+            }
+            if ui
+                .add_enabled(
+                    !gui.find_dialog.results_vec.is_empty(),
+                    egui::Button::new("Copy find result offsets"),
+                )
+                .clicked()
+            {
+                let prefs = &app.preferences;
+                let offsets: Vec<u64> = gui.find_dialog.results_vec.iter().map(|&off| app.display_offset(off)).collect();
+                clipboard::set_string(&offset_list(
+                    &offsets,
+                    prefs.copy_offset_base,
+                    prefs.copy_offset_sep,
+                ));
+                ui.close_menu();
+            }
+            ui.horizontal(|ui| {
+                ui.label("Offset base");
+                ui.radio_value(&mut app.preferences.copy_offset_base, OffsetBase::Hex, "hex");
+                ui.radio_value(&mut app.preferences.copy_offset_base, OffsetBase::Dec, "dec");
+            });
+            ui.horizontal(|ui| {
+                ui.label("List separator");
+                ui.radio_value(&mut app.preferences.copy_offset_sep, OffsetSeparator::Newline, "newline");
+                ui.radio_value(&mut app.preferences.copy_offset_sep, OffsetSeparator::Comma, "comma");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Hex dump grouping");
+                for group in [1, 2, 4, 8] {
+                    ui.radio_value(&mut app.preferences.hex_dump_group, group, group.to_string());
+                }
+            });
+            ui.separator();
+            if ui.button("Decode selection as text...").clicked() {
+                gui.decode_selection_window.open.toggle();
+                ui.close_menu();
+            }
             if ui.button("Save selection to file").clicked() {
                 if let Some(file_path) = rfd::FileDialog::new().save_file() && let Some(sel) = app.hex_ui.selection() {
                     let result = std::fs::write(file_path, &app.data[sel.begin..=sel.end]);
@@ -178,6 +373,17 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 }
                 ui.close_menu();
             }
+            if ui
+                .add_enabled(
+                    app.source.is_some_and(|src| src.attr.permissions.write) && app.hex_ui.selection().is_some(),
+                    egui::Button::new("Save selection to source"),
+                )
+                .on_hover_text("Write only the selected bytes back to the source, at their own offsets")
+                .clicked()
+            {
+                msg_if_fail(app.save_selection(), "Failed to save selection");
+                ui.close_menu();
+            }
             ui.separator();
             ui.checkbox(&mut app.preferences.move_edit_cursor, "Move edit cursor")
                 .on_hover_text("With the cursor keys in edit mode, move edit cursor by default.\n\
@@ -188,6 +394,27 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                                 value to completion or press enter");
             ui.checkbox(&mut app.preferences.sticky_edit, "Sticky edit")
                 .on_hover_text("Don't automatically move cursor after editing is finished");
+            ui.add_enabled_ui(!app.preferences.sticky_edit, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Edit step");
+                    let step = egui::DragValue::new(&mut app.preferences.edit_step)
+                        .clamp_range(1..=65536);
+                    ui.add_enabled(!app.preferences.edit_step_next_row, step)
+                        .on_hover_text("How many bytes the cursor moves after editing is finished");
+                    ui.checkbox(&mut app.preferences.edit_step_next_row, "Next row")
+                        .on_hover_text("Move to the same column of the next row instead");
+                });
+            });
+            if ui
+                .checkbox(&mut app.preferences.mark_dirty_bytes, "Mark modified bytes")
+                .on_hover_text("Draw bytes that differ from the last loaded/saved data \
+                                in a distinct color.\n\
+                                Keeps a copy of the data in memory. \
+                                Edits made before enabling this aren't marked.")
+                .changed()
+            {
+                app.take_pristine_snapshot();
+            }
         });
         ui.menu_button("Cursor", |ui| {
             let re = ui
@@ -198,7 +425,7 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 app.set_cursor_init();
                 ui.close_menu();
             }
-            if button_with_shortcut(ui, "Jump...", "Ctrl+J").clicked() {
+            if button_with_shortcut(ui, "Jump...", &app.cfg.keybinds.label(KeyAction::Jump)).clicked() {
                 ui.close_menu();
                 gui.add_dialog(JumpDialog::default());
             }
@@ -211,6 +438,32 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 app.hex_ui.flash_cursor();
                 ui.close_menu();
             }
+            if ui.button("Select length-prefixed...").on_hover_text("Read a length at the cursor, and select that many bytes after it").clicked() {
+                ui.close_menu();
+                gui.add_dialog(SelectLengthPrefixedDialog::new(app.cfg.default_big_endian));
+            }
+            ui.separator();
+            if button_with_shortcut(ui, "Region start", &app.cfg.keybinds.label(KeyAction::RegionStart)).on_hover_text("Jump to the start of the innermost region containing the cursor").clicked() {
+                app.jump_to_region_boundary(false);
+                ui.close_menu();
+            }
+            if button_with_shortcut(ui, "Region end", &app.cfg.keybinds.label(KeyAction::RegionEnd)).on_hover_text("Jump to the end of the innermost region containing the cursor").clicked() {
+                app.jump_to_region_boundary(true);
+                ui.close_menu();
+            }
+            ui.separator();
+            if button_with_shortcut(ui, "Next different byte", &app.cfg.keybinds.label(KeyAction::NextDifferentByte)).on_hover_text("Skip forward over bytes equal to the skip value").clicked() {
+                app.skip_to_different_byte(true);
+                ui.close_menu();
+            }
+            if button_with_shortcut(ui, "Previous different byte", &app.cfg.keybinds.label(KeyAction::PrevDifferentByte)).on_hover_text("Skip backward over bytes equal to the skip value").clicked() {
+                app.skip_to_different_byte(false);
+                ui.close_menu();
+            }
+            ui.horizontal(|ui| {
+                ui.label("Skip value");
+                ui.add(egui::DragValue::new(&mut app.preferences.skip_value));
+            });
         });
         ui.menu_button("View", |ui| {
             ui.menu_button("Layout", |ui| {
@@ -233,18 +486,63 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 app.focus_next_view_in_layout();
                 ui.close_menu();
             }
+            if button_with_shortcut(ui, "Toggle edit target", &app.cfg.keybinds.label(KeyAction::ToggleEditTarget))
+                .on_hover_text("Focus the next view of a different kind (e.g. hex <-> text)")
+                .clicked()
+            {
+                app.toggle_edit_target();
+                ui.close_menu();
+            }
             if button_with_shortcut(ui, "Views...", "F6").clicked() {
                 gui.views_window.open.toggle();
                 ui.close_menu();
             }
             ui.checkbox(&mut app.preferences.col_change_lock_col, "Lock col on col change");
             ui.checkbox(&mut app.preferences.col_change_lock_row, "Lock row on col change");
+            ui.checkbox(&mut app.preferences.smooth_scroll, "Smooth scrolling")
+                .on_hover_text("Animate mouse wheel scrolling instead of jumping");
+            ui.checkbox(&mut app.preferences.show_cursor_value, "Cursor value readout")
+                .on_hover_text("Show the byte at the cursor in several bases in the bottom panel");
+            ui.checkbox(&mut app.preferences.highlight_same_byte, "Highlight same byte")
+                .on_hover_text("Highlight the bytes equal to the byte at the cursor");
+            ui.checkbox(&mut app.preferences.keep_cursor_centered, "Keep cursor centered")
+                .on_hover_text("Scroll to keep the cursor centered whenever it moves");
+            ui.menu_button("Panels", |ui| {
+                let binds = &app.cfg.keybinds;
+                ui.checkbox(&mut app.preferences.show_top_panel, "Top panel").on_hover_text(binds.label(KeyAction::ToggleTopPanel));
+                ui.checkbox(&mut app.preferences.show_bottom_panel, "Bottom panel").on_hover_text(binds.label(KeyAction::ToggleBottomPanel));
+                ui.checkbox(&mut app.preferences.show_inspect_panel, "Inspect panel").on_hover_text(binds.label(KeyAction::ToggleInspectPanel));
+                if button_with_shortcut(ui, "Hide all panels", &binds.label(KeyAction::TogglePanels)).on_hover_text("Press the shortcut again to bring them back.\nEsc brings back the top panel.").clicked() {
+                    app.preferences.toggle_panels();
+                    ui.close_menu();
+                }
+            });
+            ui.menu_button("Color preset", |ui| {
+                for preset in ColorPreset::ALL {
+                    if ui.selectable_label(app.cfg.color_preset == preset, preset.name()).clicked() {
+                        app.apply_color_preset(preset);
+                        ui.close_menu();
+                    }
+                }
+            });
         });
         ui.menu_button("Perspective", |ui| {
             if button_with_shortcut(ui, "Perspectives...", "F7").clicked() {
                 gui.perspectives_window.open.toggle();
                 ui.close_menu();
             }
+            if ui.add_enabled(app.hex_ui.selection().is_some(), egui::Button::new("Set cols from selection length")).on_hover_text("Make the rows as wide as the selection, e.g. to line up records of that size").clicked() {
+                app.set_cols_from_selection();
+                ui.close_menu();
+            }
+            if ui.add_enabled(app.hex_ui.selection().is_some(), ButtonWithShortcut("Center on selection", &app.cfg.keybinds.label(KeyAction::CenterSelection))).clicked() {
+                app.center_view_on_selection();
+                ui.close_menu();
+            }
+            if ui.add_enabled(app.hex_ui.selection().is_some(), ButtonWithShortcut("Zoom to fit selection", &app.cfg.keybinds.label(KeyAction::ZoomToSelection))).on_hover_text("Set the column count, and shrink the font if needed, to fit the selection into the view").clicked() {
+                app.zoom_to_fit_selection(font);
+                ui.close_menu();
+            }
             let Some(view_key) = app.hex_ui.focused_view else { return };
             let view = &mut app.meta_state.meta.views[view_key].view;
             if ui.button("Set offset to cursor").clicked() {
@@ -289,6 +587,18 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 gui.meta_diff_window.open.toggle();
                 ui.close_menu();
             }
+            if ui.button("Display base address...").on_hover_text("Address added to displayed offsets, e.g. the load address of a memory dump").clicked() {
+                gui.add_dialog(DisplayBaseDialog::new(app));
+                ui.close_menu();
+            }
+            if ui.button("Export C header...").on_hover_text("Export region and bookmark offsets as #defines").clicked() {
+                gui.add_dialog(ExportCHeaderDialog::default());
+                ui.close_menu();
+            }
+            if ui.button("Export data map...").on_hover_text("Export regions, bookmarks and comments as CSV").clicked() {
+                gui.add_dialog(ExportDataMapDialog::default());
+                ui.close_menu();
+            }
             ui.separator();
             if ui.add_enabled(!app.meta_state.current_meta_path.as_os_str().is_empty(), egui::Button::new("Reload")).on_hover_text(format!("Reload from {}", app.meta_state.current_meta_path.display())).clicked() {
                 msg_if_fail(app.consume_meta_from_file(app.meta_state.current_meta_path.clone()), "Failed to load metafile");
@@ -322,6 +632,18 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 msg_info(format);
                 ui.close_menu();
             }
+            if ui.button("Strings...").on_hover_text("List runs of printable text").clicked() {
+                gui.strings_window.open.toggle();
+                ui.close_menu();
+            }
+            if ui.button("Repeated byte runs...").on_hover_text("List runs of a repeated byte, like padding or erased flash").clicked() {
+                gui.runs_window.open.toggle();
+                ui.close_menu();
+            }
+            if ui.button("Template check...").on_hover_text("Check the data against a template of expected byte values").clicked() {
+                gui.template_window.open.toggle();
+                ui.close_menu();
+            }
             ui.separator();
             if ui.button("Diff with file...").clicked() {
                 ui.close_menu();
@@ -346,17 +668,43 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                 _ => { ui.add_enabled(false, egui::Button::new("Diff with backup")); }
             }
             ui.separator();
+            if ui.button("Set reference file...").on_hover_text("Highlight and navigate bytes that differ from a baseline file").clicked() {
+                ui.close_menu();
+                if let Some(path) = rfd::FileDialog::default().pick_file() {
+                    msg_if_fail(app.load_reference_file(path), "Failed to load reference file");
+                }
+            }
+            if let Some(reference) = &app.reference {
+                ui.label(format!("Reference: {}", reference.path.display()));
+                if button_with_shortcut(ui, "Next change", &app.cfg.keybinds.label(KeyAction::ReferenceNext)).clicked() {
+                    app.jump_to_reference_diff(true);
+                    ui.close_menu();
+                }
+                if button_with_shortcut(ui, "Previous change", &app.cfg.keybinds.label(KeyAction::ReferencePrev)).clicked() {
+                    app.jump_to_reference_diff(false);
+                    ui.close_menu();
+                }
+                if ui.button("Clear reference file").clicked() {
+                    app.reference = None;
+                    ui.close_menu();
+                }
+            }
+            ui.separator();
             if ui.add_enabled(gui.open_process_window.selected_pid.is_some(), egui::Button::new("Find memory pointers...")).clicked() {
                 gui.find_memory_pointers_window.open.toggle();
                 ui.close_menu()
             }
+            if ui.button("Find pointers to offset...").clicked() {
+                gui.find_pointers_to_window.open.toggle();
+                ui.close_menu();
+            }
         });
         ui.menu_button("Help", |ui| {
             if ui.button("Hexerator book").clicked() {
                 msg_if_fail(open::that("https://crumblingstatue.github.io/hexerator-book/"), "Failed to open help");
                 ui.close_menu();
             }
-            if button_with_shortcut(ui, "Debug panel...", "F12").clicked() {
+            if button_with_shortcut(ui, "Debug panel...", &app.cfg.keybinds.label(KeyAction::DebugPanel)).clicked() {
                 ui.close_menu();
                 gamedebug_core::toggle();
             }
@@ -379,6 +727,9 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
                             ui.label(format!("Windows process: {}", handle));
                         }
                     }
+                    if !src.attr.permissions.write {
+                        ui.label("[read-only]");
+                    }
                     if src.attr.stream {
                         if src.state.stream_end {
                             ui.label("[finished stream]");
@@ -395,3 +746,13 @@ pub fn top_menu(ui: &mut egui::Ui, gui: &mut crate::gui::Gui, app: &mut App, fon
         });
     });
 }
+
+/// The bytes at `offsets` as space separated hex
+fn offsets_hex(data: &[u8], offsets: &[usize]) -> String {
+    let mut s = String::new();
+    for byte in offsets.iter().filter_map(|&off| data.get(off)) {
+        let _ = write!(&mut s, "{:02x} ", byte);
+    }
+    s.truncate(s.trim_end().len());
+    s
+}
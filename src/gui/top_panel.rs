@@ -1,19 +1,23 @@
 use {
-    super::{dialogs::LuaColorDialog, top_menu::top_menu, Gui},
+    super::{dialogs::LuaColorDialog, ops::request_file_op, top_menu::top_menu, Gui},
     crate::{
-        app::App,
+        app::{presentation::Presentation, App, FileOp},
         color::{self, ColorMethod, Palette},
+        keybinds::KeyAction,
         shell::{msg_fail, msg_if_fail, msg_warn},
     },
     anyhow::Context,
     egui_sfml::{
         egui::{self, ComboBox, Layout, Ui},
-        sfml::graphics::{Font, Image},
+        sfml::graphics::{Color, Font, Image},
     },
 };
 
 pub fn ui(ui: &mut Ui, gui: &mut Gui, app: &mut App, font: &Font) {
     top_menu(ui, gui, app, font);
+    if app.tabs.len() > 1 {
+        tab_bar(ui, gui, app, font);
+    }
     ui.horizontal(|ui| {
         if app.hex_ui.select_a.is_some() || app.hex_ui.select_b.is_some() {
             ui.label("Selection");
@@ -37,6 +41,7 @@ pub fn ui(ui: &mut Ui, gui: &mut Gui, app: &mut App, font: &Font) {
             let presentation = &mut app.meta_state.meta.views[view_key].view.presentation;
             ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                 ui.checkbox(&mut presentation.invert_color, "invert");
+                ui.checkbox(&mut presentation.lowercase_hex, "lowercase hex");
                 ComboBox::new("color_combo", "Color")
                     .selected_text(presentation.color_method.name())
                     .show_ui(ui, |ui| {
@@ -65,6 +70,16 @@ pub fn ui(ui: &mut Ui, gui: &mut Gui, app: &mut App, font: &Font) {
                             ColorMethod::Grayscale,
                             ColorMethod::Grayscale.name(),
                         );
+                        ui.selectable_value(
+                            &mut presentation.color_method,
+                            ColorMethod::HighNibble,
+                            ColorMethod::HighNibble.name(),
+                        );
+                        ui.selectable_value(
+                            &mut presentation.color_method,
+                            ColorMethod::LowNibble,
+                            ColorMethod::LowNibble.name(),
+                        );
                         if ui
                             .selectable_label(
                                 matches!(&presentation.color_method, ColorMethod::Custom(..)),
@@ -87,6 +102,26 @@ pub fn ui(ui: &mut Ui, gui: &mut Gui, app: &mut App, font: &Font) {
                     });
                 ui.color_edit_button_rgb(&mut app.preferences.bg_color);
                 ui.label("Bg color");
+                ui.menu_button("Colors", |ui| {
+                    for (label, color) in [
+                        ("Selection", &mut presentation.sel_color),
+                        ("Find results", &mut presentation.find_result_color),
+                        ("Cursor", &mut presentation.cursor_color),
+                        ("Cursor (focused view)", &mut presentation.cursor_active_color),
+                    ] {
+                        ui.horizontal(|ui| {
+                            sfml_color_edit_button(ui, color);
+                            ui.label(label);
+                        });
+                    }
+                    if ui.button("Reset").clicked() {
+                        let default = Presentation::default();
+                        presentation.sel_color = default.sel_color;
+                        presentation.find_result_color = default.find_result_color;
+                        presentation.cursor_color = default.cursor_color;
+                        presentation.cursor_active_color = default.cursor_active_color;
+                    }
+                });
                 if let ColorMethod::Custom(arr) = &mut presentation.color_method {
                     let Some(&byte) = app.data.get(app.edit_state.cursor) else { return };
                     let col = &mut arr.0[byte as usize];
@@ -160,6 +195,40 @@ pub fn ui(ui: &mut Ui, gui: &mut Gui, app: &mut App, font: &Font) {
     });
 }
 
+/// Switch between the open tabs, or close the active one
+fn tab_bar(ui: &mut Ui, gui: &mut Gui, app: &mut App, font: &Font) {
+    ui.horizontal(|ui| {
+        for idx in 0..app.tabs.len() {
+            if ui
+                .selectable_label(idx == app.active_tab, app.tab_title(idx))
+                .clicked()
+            {
+                app.switch_tab(idx);
+            }
+        }
+        ui.separator();
+        if ui.button("🗙").on_hover_text("Close tab").clicked() {
+            request_file_op(gui, app, FileOp::CloseTab, font);
+        }
+        let hover = match app.cfg.keybinds.get(KeyAction::NewTab) {
+            Some(combo) => format!("New tab ({})", combo),
+            None => "New tab".into(),
+        };
+        if ui.button("➕").on_hover_text(hover).clicked() {
+            app.new_tab();
+        }
+    });
+}
+
+/// Color picker button for an sfml color
+fn sfml_color_edit_button(ui: &mut Ui, color: &mut Color) -> egui::Response {
+    let mut rgba = [color.r, color.g, color.b, color.a];
+    let re = ui.color_edit_button_srgba_unmultiplied(&mut rgba);
+    let [r, g, b, a] = rgba;
+    *color = Color::rgba(r, g, b, a);
+    re
+}
+
 fn color_from_hexcode(mut src: &str) -> anyhow::Result<[u8; 3]> {
     let mut out = [0; 3];
     src = src.trim_start_matches('#');
@@ -2,9 +2,9 @@
 
 use egui_sfml::egui::{pos2, text::LayoutJob, Response, TextStyle, Ui, Widget};
 
-pub struct ButtonWithShortcut(pub &'static str, pub &'static str);
+pub struct ButtonWithShortcut<'a>(pub &'a str, pub &'a str);
 
-impl Widget for ButtonWithShortcut {
+impl Widget for ButtonWithShortcut<'_> {
     fn ui(self, ui: &mut Ui) -> Response {
         button_with_shortcut(ui, self.0, self.1)
     }
@@ -4,7 +4,7 @@ use {
         app::App,
         meta::{NamedView, ViewKey},
         region_context_menu,
-        view::{HexData, TextData, TextKind, View, ViewKind},
+        view::{BitData, HexData, TextData, TextKind, View, ViewKind},
     },
     egui_extras::{Size, TableBuilder},
     egui_sfml::{
@@ -27,12 +27,14 @@ impl ViewKind {
     const DEC_NAME: &str = "Decimal";
     const TEXT_NAME: &str = "Text";
     const BLOCK_NAME: &str = "Block";
+    const BITS_NAME: &str = "Bits";
     fn name(&self) -> &'static str {
         match *self {
             ViewKind::Hex(_) => Self::HEX_NAME,
             ViewKind::Dec(_) => Self::DEC_NAME,
             ViewKind::Text(_) => Self::TEXT_NAME,
             ViewKind::Block => Self::BLOCK_NAME,
+            ViewKind::Bits(_) => Self::BITS_NAME,
         }
     }
 }
@@ -192,10 +194,12 @@ impl ViewsWindow {
                 });
             ui.group(|ui| {
                 let mut adjust_block_size = false;
+                let mut line_spacing_changed = false;
                 match &mut view.view.kind {
                     ViewKind::Hex(HexData { font_size, .. })
                     | ViewKind::Dec(HexData { font_size, .. })
-                    | ViewKind::Text(TextData { font_size, .. }) => {
+                    | ViewKind::Text(TextData { font_size, .. })
+                    | ViewKind::Bits(BitData { font_size, .. }) => {
                         ui.horizontal(|ui| {
                             ui.label("Font size");
                             if ui
@@ -238,12 +242,66 @@ impl ViewsWindow {
                             if changed {
                                 view.view.bytes_per_block = text.text_kind.bytes_needed();
                             }
+                            ui.horizontal(|ui| {
+                                ui.label("Line spacing");
+                                line_spacing_changed |= ui
+                                    .add(
+                                        egui::DragValue::new(&mut text.line_spacing)
+                                            .clamp_range(1..=MAX_FONT_SIZE * 4),
+                                    )
+                                    .changed();
+                                #[expect(
+                                    clippy::cast_possible_truncation,
+                                    clippy::cast_sign_loss,
+                                    reason = "It's extremely unlikely line spacing is not between 0 and i16::MAX"
+                                )]
+                                if ui
+                                    .button("Reset")
+                                    .on_hover_text("Use the font's line spacing")
+                                    .clicked()
+                                {
+                                    text.line_spacing =
+                                        font.line_spacing(u32::from(text.font_size)) as u16;
+                                    line_spacing_changed = true;
+                                }
+                            });
+                        }
+                        if let ViewKind::Hex(hex) | ViewKind::Dec(hex) = &mut view.view.kind {
+                            let label = |group: u8| match group {
+                                0 | 1 => String::from("off"),
+                                n => format!("{} bytes", n),
+                            };
+                            egui::ComboBox::new(egui::Id::new("byte_swap_combo"), "Byte swap")
+                                .selected_text(label(hex.byte_swap_group))
+                                .show_ui(ui, |ui| {
+                                    for group in [0, 2, 4, 8] {
+                                        ui.selectable_value(
+                                            &mut hex.byte_swap_group,
+                                            group,
+                                            label(group),
+                                        );
+                                    }
+                                })
+                                .response
+                                .on_hover_text("Display the bytes of each group in reverse order");
+                        }
+                        if let ViewKind::Hex(hex) = &mut view.view.kind
+                            && ui
+                                .checkbox(&mut hex.inline_ascii, "Inline ascii")
+                                .on_hover_text("Show the character of each byte after its digits")
+                                .changed()
+                        {
+                            adjust_block_size = true;
+                        }
+                        if let ViewKind::Bits(bits) = &mut view.view.kind {
+                            ui.checkbox(&mut bits.lsb_first, "LSB first")
+                                .on_hover_text("Show the least significant bit first");
                         }
                     }
                     ViewKind::Block => {}
                 }
                 if adjust_block_size {
-                    view.view.adjust_block_size();
+                    // Changing the font size resets the line spacing to the font's default
                     #[expect(
                         clippy::cast_possible_truncation,
                         clippy::cast_sign_loss,
@@ -253,6 +311,9 @@ impl ViewsWindow {
                         data.line_spacing = font.line_spacing(u32::from(data.font_size)) as u16;
                     }
                 }
+                if adjust_block_size || line_spacing_changed {
+                    view.view.adjust_block_size();
+                }
                 ui.horizontal(|ui| {
                     labelled_drag(ui, "col w", &mut view.view.col_w, 1..=128);
                     labelled_drag(ui, "row h", &mut view.view.row_h, 1..=128);
@@ -263,6 +324,21 @@ impl ViewsWindow {
                     &mut view.view.bytes_per_block,
                     1..=64,
                 );
+                labelled_drag(ui, "scroll speed", &mut view.view.scroll_speed, 0..=1024)
+                    .on_hover_text("Pixels scrolled per mouse wheel tick. 0 means 3 rows.");
+                ui.checkbox(&mut view.view.sync_scroll, "Sync scroll").on_hover_text(
+                    "Scroll together with the other views of the same perspective.\n\
+                     When off, this view stays put while the others scroll.",
+                );
+                let low = &app.meta_state.meta.low;
+                let region = &low.regions[low.perspectives[view.view.perspective].region].region;
+                labelled_drag(
+                    ui,
+                    "data offset",
+                    &mut view.view.data_offset,
+                    0..=region.len().saturating_sub(1),
+                )
+                .on_hover_text("Start this view this many bytes into the perspective's region");
             });
             if ui.button("Delete").clicked() {
                 removed_idx = Some(gui.views_window.selected);
@@ -315,6 +391,13 @@ fn view_combo(
                 *kind = ViewKind::Block;
                 changed = true;
             }
+            if ui
+                .selectable_label(kind.name() == ViewKind::BITS_NAME, ViewKind::BITS_NAME)
+                .clicked()
+            {
+                *kind = ViewKind::Bits(BitData::default());
+                changed = true;
+            }
         });
     changed
 }
@@ -7,10 +7,12 @@ fn test_byte_16_digits() {
     assert_eq!(byte_16_digits(255), [15, 15]);
 }
 
-pub fn byte_to_hex_digits(byte: u8) -> [u8; 2] {
+pub fn byte_to_hex_digits(byte: u8, lowercase: bool) -> [u8; 2] {
     let [l, r] = byte_16_digits(byte);
     const TABLE: &[u8; 16] = b"0123456789ABCDEF";
-    [TABLE[l as usize], TABLE[r as usize]]
+    const TABLE_LOWER: &[u8; 16] = b"0123456789abcdef";
+    let table = if lowercase { TABLE_LOWER } else { TABLE };
+    [table[l as usize], table[r as usize]]
 }
 
 #[test]
@@ -26,8 +28,9 @@ fn test_byte_to_hex_digits() {
         (64, b"40"),
     ];
     for (byte, hex) in pairs {
-        assert_eq!(byte_to_hex_digits(byte), *hex);
+        assert_eq!(byte_to_hex_digits(byte, false), *hex);
     }
+    assert_eq!(&byte_to_hex_digits(167, true), b"a7");
 }
 
 fn digit_to_byte(digit: u8) -> Option<u8> {
@@ -0,0 +1,62 @@
+//! Formatting data as a classic hex dump, like `hexdump -C` or `xxd`
+
+use {crate::preferences::OffsetBase, std::fmt::Write};
+
+/// Number of bytes shown per line
+const LINE_LEN: usize = 16;
+
+/// Format `data` as lines of an offset, the bytes in hex (in groups of `group` bytes),
+/// and the bytes as ascii.
+///
+/// `offset` is the offset shown for the first byte.
+pub fn hex_dump(data: &[u8], offset: u64, base: OffsetBase, group: usize) -> String {
+    let group = group.clamp(1, LINE_LEN);
+    let mut out = String::new();
+    for (i, line) in data.chunks(LINE_LEN).enumerate() {
+        let line_offset = offset.wrapping_add((i * LINE_LEN) as u64);
+        let _ = match base {
+            OffsetBase::Hex => write!(out, "{:08x} ", line_offset),
+            OffsetBase::Dec => write!(out, "{:08} ", line_offset),
+        };
+        for j in 0..LINE_LEN {
+            if j % group == 0 {
+                out.push(' ');
+                // Extra gap between the two halves of the line
+                if j == LINE_LEN / 2 {
+                    out.push(' ');
+                }
+            }
+            match line.get(j) {
+                Some(byte) => {
+                    let _ = write!(out, "{:02x}", byte);
+                }
+                None => out.push_str("  "),
+            }
+        }
+        out.push_str("  |");
+        out.extend(line.iter().map(|&b| {
+            if b.is_ascii_graphic() || b == b' ' {
+                char::from(b)
+            } else {
+                '.'
+            }
+        }));
+        out.push_str("|\n");
+    }
+    out
+}
+
+#[test]
+fn test_hex_dump() {
+    let data = b"Hello, world!\n\0\xffHexerator";
+    // Same as `hexdump -C`
+    assert_eq!(
+        hex_dump(data, 0, OffsetBase::Hex, 1),
+        "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 ff  |Hello, world!...|\n\
+         00000010  48 65 78 65 72 61 74 6f  72                       |Hexerator|\n"
+    );
+    assert_eq!(
+        hex_dump(&data[..4], 16, OffsetBase::Dec, 2),
+        "00000016  4865 6c6c                                 |Hell|\n"
+    );
+}
@@ -1,9 +1,10 @@
 use {
     crate::{
         app::interact_mode::InteractMode,
+        layout::Divider,
         meta::{region::Region, LayoutKey, ViewKey},
         timer::Timer,
-        view::ViewportRect,
+        view::{ViewportRect, ViewportVec},
     },
     slotmap::Key,
     std::time::Duration,
@@ -27,6 +28,12 @@ pub struct HexUi {
     pub scissor_views: bool,
     /// When alt is being held, it shows things like names of views as overlays
     pub show_alt_overlay: bool,
+    /// Size of a record, used to show how many records the selection spans
+    pub record_size: usize,
+    /// The layout divider being dragged, along with the last mouse position
+    pub divider_drag: Option<(Divider, ViewportVec)>,
+    /// The cursor position on the previous frame, to notice when the cursor moves
+    pub prev_frame_cursor: usize,
 }
 
 impl HexUi {
@@ -40,6 +47,27 @@ impl HexUi {
             None
         }
     }
+    /// Expand the selection outward to multiples of `align`, staying within `data_len`
+    pub fn align_selection(&mut self, align: usize, data_len: usize) {
+        let Some(sel) = self.selection() else { return };
+        let aligned = sel.aligned_outward(align);
+        self.select_a = Some(aligned.begin);
+        self.select_b = Some(aligned.end.min(data_len.saturating_sub(1)));
+    }
+    /// Select the larger of the parts before and after the selection
+    pub fn invert_selection(&mut self, data_len: usize) {
+        let Some(sel) = self.selection() else { return };
+        match sel.larger_complement(data_len) {
+            Some(inverted) => {
+                self.select_a = Some(inverted.begin);
+                self.select_b = Some(inverted.end);
+            }
+            None => {
+                self.select_a = None;
+                self.select_b = None;
+            }
+        }
+    }
     /// Clear existing meta references
     pub fn clear_meta_refs(&mut self) {
         self.current_layout = LayoutKey::null();
@@ -78,6 +106,9 @@ impl Default for HexUi {
             flash_cursor_timer: Timer::default(),
             hex_iface_rect: ViewportRect::default(),
             show_alt_overlay: false,
+            record_size: 1,
+            divider_drag: None,
+            prev_frame_cursor: 0,
             current_layout: LayoutKey::null(),
         }
     }
@@ -0,0 +1,140 @@
+//! Reading and writing of IPS patches
+
+use anyhow::{bail, Context};
+
+const HEADER: &[u8] = b"PATCH";
+const FOOTER: &[u8] = b"EOF";
+/// Offsets are 24 bit
+const MAX_OFFSET: usize = 0xFF_FFFF;
+/// This offset would be read back as the "EOF" footer, so it can't start a record
+const EOF_OFFSET: usize = 0x45_4F46;
+const MAX_RECORD_LEN: usize = 0xFFFF;
+
+/// A patch record, with RLE records already expanded
+#[derive(Debug, PartialEq, Eq)]
+pub struct Record {
+    pub offset: usize,
+    pub data: Vec<u8>,
+}
+
+impl Record {
+    pub fn end(&self) -> usize {
+        self.offset + self.data.len()
+    }
+}
+
+/// Parse the records of an IPS patch
+pub fn parse(mut bytes: &[u8]) -> anyhow::Result<Vec<Record>> {
+    let bytes = &mut bytes;
+    if take(bytes, HEADER.len()).context("Missing header")? != HEADER {
+        bail!("Not an IPS patch (invalid header)");
+    }
+    let mut records = Vec::new();
+    loop {
+        let offset = take(bytes, 3)?;
+        if offset == FOOTER {
+            break;
+        }
+        let offset = be_uint(offset);
+        let len = be_uint(take(bytes, 2)?);
+        let data = if len == 0 {
+            let run_len = be_uint(take(bytes, 2)?);
+            vec![take(bytes, 1)?[0]; run_len]
+        } else {
+            take(bytes, len)?.to_vec()
+        };
+        records.push(Record { offset, data });
+    }
+    Ok(records)
+}
+
+/// Create an IPS patch that turns `original` into `modified`.
+///
+/// If `modified` is shorter than `original`, the excess of `original` is left as is.
+pub fn create(original: &[u8], modified: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut out = HEADER.to_vec();
+    let mut offset = 0;
+    while offset < modified.len() {
+        if original.get(offset) == Some(&modified[offset]) {
+            offset += 1;
+            continue;
+        }
+        // Start one byte earlier if this offset would be mistaken for the footer
+        let begin = if offset == EOF_OFFSET { offset - 1 } else { offset };
+        let mut end = offset + 1;
+        while end < modified.len()
+            && end - begin < MAX_RECORD_LEN
+            && original.get(end) != Some(&modified[end])
+        {
+            end += 1;
+        }
+        if begin > MAX_OFFSET {
+            bail!("Difference at {:X} is beyond the 24 bit offset limit of IPS", begin);
+        }
+        out.extend_from_slice(&be_bytes(begin, 3));
+        out.extend_from_slice(&be_bytes(end - begin, 2));
+        out.extend_from_slice(&modified[begin..end]);
+        offset = end;
+    }
+    out.extend_from_slice(FOOTER);
+    Ok(out)
+}
+
+/// Split off the first `n` bytes
+fn take<'a>(bytes: &mut &'a [u8], n: usize) -> anyhow::Result<&'a [u8]> {
+    if bytes.len() < n {
+        bail!("Unexpected end of patch");
+    }
+    let (taken, rest) = bytes.split_at(n);
+    *bytes = rest;
+    Ok(taken)
+}
+
+fn be_uint(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .fold(0, |acc, &b| (acc << 8) | usize::from(b))
+}
+
+#[expect(
+    clippy::cast_possible_truncation,
+    reason = "Truncation to the lowest byte is intended"
+)]
+fn be_bytes(value: usize, n: usize) -> Vec<u8> {
+    (0..n).rev().map(|i| (value >> (i * 8)) as u8).collect()
+}
+
+#[test]
+fn test_parse() {
+    let patch = b"PATCH\x00\x00\x02\x00\x02\xAA\xBB\x00\x00\x10\x00\x00\x00\x03\xCCEOF";
+    assert_eq!(
+        parse(patch).unwrap(),
+        [
+            Record {
+                offset: 2,
+                data: vec![0xAA, 0xBB]
+            },
+            Record {
+                offset: 0x10,
+                data: vec![0xCC; 3]
+            },
+        ]
+    );
+    assert!(parse(b"PATCH\x00\x00\x02\x00\x05\xAA").is_err());
+    assert!(parse(b"NOPE").is_err());
+}
+
+#[test]
+fn test_create_roundtrip() {
+    let original = [0, 1, 2, 3, 4, 5, 6, 7];
+    let modified = [0, 9, 9, 3, 4, 5, 8, 7, 1, 2];
+    let records = parse(&create(&original, &modified).unwrap()).unwrap();
+    let mut patched = original.to_vec();
+    for rec in records {
+        if patched.len() < rec.end() {
+            patched.resize(rec.end(), 0);
+        }
+        patched[rec.offset..rec.end()].copy_from_slice(&rec.data);
+    }
+    assert_eq!(patched, modified);
+}
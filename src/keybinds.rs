@@ -0,0 +1,357 @@
+//! User configurable keyboard shortcuts
+
+use {
+    crate::KeyMod,
+    egui_sfml::sfml::window::Key,
+    serde::{Deserialize, Serialize},
+    std::{collections::BTreeMap, fmt},
+};
+
+/// An action that can be bound to a key combination
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum KeyAction {
+    Open,
+    OpenPrevious,
+    Save,
+    Reload,
+    Close,
+    Find,
+    Jump,
+    SelectAll,
+    CenterSelection,
+    ZoomToSelection,
+    ToggleTopPanel,
+    ToggleBottomPanel,
+    ToggleInspectPanel,
+    TogglePanels,
+    PrevField,
+    NextField,
+    ReopenClosed,
+    NewTab,
+    PrevTab,
+    NextTab,
+    NextDifferentByte,
+    PrevDifferentByte,
+    RepeatLastOp,
+    ReferenceNext,
+    ReferencePrev,
+    RegionStart,
+    RegionEnd,
+    ToggleEditTarget,
+    DiffNext,
+    DiffPrev,
+    DebugPanel,
+}
+
+impl KeyAction {
+    pub const ALL: [Self; 31] = [
+        Self::Open,
+        Self::OpenPrevious,
+        Self::Save,
+        Self::Reload,
+        Self::Close,
+        Self::Find,
+        Self::Jump,
+        Self::SelectAll,
+        Self::CenterSelection,
+        Self::ZoomToSelection,
+        Self::ToggleTopPanel,
+        Self::ToggleBottomPanel,
+        Self::ToggleInspectPanel,
+        Self::TogglePanels,
+        Self::PrevField,
+        Self::NextField,
+        Self::ReopenClosed,
+        Self::NewTab,
+        Self::PrevTab,
+        Self::NextTab,
+        Self::NextDifferentByte,
+        Self::PrevDifferentByte,
+        Self::RepeatLastOp,
+        Self::ReferenceNext,
+        Self::ReferencePrev,
+        Self::RegionStart,
+        Self::RegionEnd,
+        Self::ToggleEditTarget,
+        Self::DiffNext,
+        Self::DiffPrev,
+        Self::DebugPanel,
+    ];
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Open => "Open",
+            Self::OpenPrevious => "Open previous",
+            Self::Save => "Save",
+            Self::Reload => "Reload",
+            Self::Close => "Close",
+            Self::Find => "Find",
+            Self::Jump => "Jump",
+            Self::SelectAll => "Select all",
+            Self::CenterSelection => "Center on selection",
+            Self::ZoomToSelection => "Zoom to fit selection",
+            Self::ToggleTopPanel => "Toggle top panel",
+            Self::ToggleBottomPanel => "Toggle bottom panel",
+            Self::ToggleInspectPanel => "Toggle inspect panel",
+            Self::TogglePanels => "Toggle all panels",
+            Self::PrevField => "Previous field",
+            Self::NextField => "Next field",
+            Self::ReopenClosed => "Reopen last closed",
+            Self::NewTab => "New tab",
+            Self::PrevTab => "Previous tab",
+            Self::NextTab => "Next tab",
+            Self::NextDifferentByte => "Next different byte",
+            Self::PrevDifferentByte => "Previous different byte",
+            Self::RepeatLastOp => "Repeat last operation",
+            Self::ReferenceNext => "Next change from reference",
+            Self::ReferencePrev => "Previous change from reference",
+            Self::RegionStart => "Region start",
+            Self::RegionEnd => "Region end",
+            Self::ToggleEditTarget => "Toggle edit target",
+            Self::DiffNext => "Next file diff",
+            Self::DiffPrev => "Previous file diff",
+            Self::DebugPanel => "Debug panel",
+        }
+    }
+    fn default_combo(self) -> KeyCombo {
+        match self {
+            Self::Open => KeyCombo::ctrl("O"),
+            Self::OpenPrevious => KeyCombo::ctrl("P"),
+            Self::Save => KeyCombo::ctrl("S"),
+            Self::Reload => KeyCombo::ctrl("R"),
+            Self::Close => KeyCombo::ctrl("W"),
+            Self::Find => KeyCombo::ctrl("F"),
+            Self::Jump => KeyCombo::ctrl("J"),
+            Self::SelectAll => KeyCombo::ctrl("A"),
+            Self::CenterSelection => KeyCombo::ctrl("G"),
+            Self::ZoomToSelection => KeyCombo {
+                shift: true,
+                ..KeyCombo::ctrl("G")
+            },
+            Self::ToggleTopPanel => KeyCombo::ctrl("F10"),
+            Self::ToggleBottomPanel => KeyCombo::shift("F10"),
+            Self::ToggleInspectPanel => KeyCombo::plain("F10"),
+            Self::TogglePanels => KeyCombo::plain("F11"),
+            Self::PrevField => KeyCombo::alt("ArrowUp"),
+            Self::NextField => KeyCombo::alt("ArrowDown"),
+            Self::ReopenClosed => KeyCombo {
+                shift: true,
+                ..KeyCombo::ctrl("T")
+            },
+            Self::NewTab => KeyCombo::ctrl("T"),
+            Self::PrevTab => KeyCombo::ctrl("PageUp"),
+            Self::NextTab => KeyCombo::ctrl("PageDown"),
+            Self::NextDifferentByte => KeyCombo::plain("F4"),
+            Self::PrevDifferentByte => KeyCombo::shift("F4"),
+            Self::RepeatLastOp => KeyCombo::plain("Period"),
+            Self::ReferenceNext => KeyCombo::ctrl("F3"),
+            Self::ReferencePrev => KeyCombo {
+                shift: true,
+                ..KeyCombo::ctrl("F3")
+            },
+            Self::RegionStart => KeyCombo::ctrl("LBracket"),
+            Self::RegionEnd => KeyCombo::ctrl("RBracket"),
+            Self::ToggleEditTarget => KeyCombo::ctrl("Tab"),
+            Self::DiffNext => KeyCombo::plain("F3"),
+            Self::DiffPrev => KeyCombo::shift("F3"),
+            Self::DebugPanel => KeyCombo::plain("F12"),
+        }
+    }
+}
+
+/// A key along with the modifiers that need to be held
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct KeyCombo {
+    /// Name of the key, as in [`KEY_NAMES`]
+    pub key: String,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyCombo {
+    fn plain(key: &str) -> Self {
+        Self {
+            key: key.into(),
+            ctrl: false,
+            shift: false,
+            alt: false,
+        }
+    }
+    fn ctrl(key: &str) -> Self {
+        Self {
+            ctrl: true,
+            ..Self::plain(key)
+        }
+    }
+    fn shift(key: &str) -> Self {
+        Self {
+            shift: true,
+            ..Self::plain(key)
+        }
+    }
+    fn alt(key: &str) -> Self {
+        Self {
+            alt: true,
+            ..Self::plain(key)
+        }
+    }
+    fn matches(&self, key: Key, key_mod: &KeyMod) -> bool {
+        key_name(key) == Some(self.key.as_str())
+            && self.ctrl == key_mod.ctrl
+            && self.shift == key_mod.shift
+            && self.alt == key_mod.alt
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("Ctrl+")?;
+        }
+        if self.shift {
+            f.write_str("Shift+")?;
+        }
+        if self.alt {
+            f.write_str("Alt+")?;
+        }
+        f.write_str(&self.key)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(from = "SavedKeybinds")]
+pub struct Keybinds {
+    /// `None` if the action was unbound
+    map: BTreeMap<KeyAction, Option<KeyCombo>>,
+}
+
+/// Keybinds as saved in the config.
+///
+/// Actions that were added after the config was saved are missing from it.
+#[derive(Deserialize)]
+struct SavedKeybinds {
+    map: BTreeMap<KeyAction, Option<KeyCombo>>,
+}
+
+impl From<SavedKeybinds> for Keybinds {
+    fn from(saved: SavedKeybinds) -> Self {
+        let mut this = Self::default();
+        this.map.extend(saved.map);
+        this
+    }
+}
+
+impl Default for Keybinds {
+    fn default() -> Self {
+        Self {
+            map: KeyAction::ALL
+                .into_iter()
+                .map(|action| (action, Some(action.default_combo())))
+                .collect(),
+        }
+    }
+}
+
+impl Keybinds {
+    pub fn get(&self, action: KeyAction) -> Option<&KeyCombo> {
+        self.map.get(&action)?.as_ref()
+    }
+    pub fn set(&mut self, action: KeyAction, combo: KeyCombo) {
+        self.map.insert(action, Some(combo));
+    }
+    pub fn reset(&mut self, action: KeyAction) {
+        self.map.insert(action, Some(action.default_combo()));
+    }
+    /// Unbound actions have no shortcut
+    pub fn unbind(&mut self, action: KeyAction) {
+        self.map.insert(action, None);
+    }
+    /// The shortcut text of an action, for displaying in menus
+    pub fn label(&self, action: KeyAction) -> String {
+        self.get(action)
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+    pub(crate) fn action_for(&self, key: Key, key_mod: &KeyMod) -> Option<KeyAction> {
+        self.map
+            .iter()
+            .find(|(_, combo)| combo.as_ref().is_some_and(|c| c.matches(key, key_mod)))
+            .map(|(&action, _)| action)
+    }
+    /// Other actions that are bound to the same combination as `action`
+    pub fn conflicts(&self, action: KeyAction) -> impl Iterator<Item = KeyAction> + '_ {
+        let combo = self.get(action);
+        self.map
+            .iter()
+            .filter(move |&(&other, other_combo)| {
+                other != action && combo.is_some() && other_combo.as_ref() == combo
+            })
+            .map(|(&other, _)| other)
+    }
+}
+
+macro_rules! key_names {
+    ($($key:ident)* ; $($sfml_key:ident => $name:literal)*) => {
+        /// Keys that can be bound, along with their names.
+        ///
+        /// The names match the `Debug` representation of the corresponding egui keys.
+        /// Keys egui has no equivalent of (like `Period`) can't be picked when rebinding,
+        /// but they work in the defaults.
+        pub const KEY_NAMES: &[(Key, &str)] =
+            &[$((Key::$key, stringify!($key)),)* $((Key::$sfml_key, $name),)*];
+    };
+}
+
+key_names! {
+    A B C D E F G H I J K L M N O P Q R S T U V W X Y Z
+    Num0 Num1 Num2 Num3 Num4 Num5 Num6 Num7 Num8 Num9
+    F1 F2 F3 F4 F5 F6 F7 F8 F9 F10 F11 F12
+    Space Insert Delete Home End PageUp PageDown Tab Escape Enter Period LBracket RBracket;
+    Up => "ArrowUp" Down => "ArrowDown" Left => "ArrowLeft" Right => "ArrowRight"
+}
+
+fn key_name(key: Key) -> Option<&'static str> {
+    KEY_NAMES
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, name)| *name)
+}
+
+/// Whether `name` is the name of a bindable key
+pub fn is_key_name(name: &str) -> bool {
+    KEY_NAMES.iter().any(|(_, key_name)| *key_name == name)
+}
+
+#[test]
+fn test_conflicts() {
+    let mut binds = Keybinds::default();
+    assert_eq!(binds.conflicts(KeyAction::Save).count(), 0);
+    binds.set(KeyAction::Find, KeyCombo::ctrl("S"));
+    assert_eq!(
+        binds.conflicts(KeyAction::Save).collect::<Vec<_>>(),
+        [KeyAction::Find]
+    );
+    binds.reset(KeyAction::Find);
+    assert_eq!(binds.conflicts(KeyAction::Save).count(), 0);
+}
+
+#[test]
+fn test_missing_actions_get_defaults() {
+    // Saved by an older version, before most of the actions existed
+    #[derive(Serialize)]
+    struct OldKeybinds {
+        map: BTreeMap<KeyAction, KeyCombo>,
+    }
+    let old = OldKeybinds {
+        map: [(KeyAction::Save, KeyCombo::ctrl("K"))]
+            .into_iter()
+            .collect(),
+    };
+    let mut binds: Keybinds = crate::test_util::reload_as(&old);
+    assert_eq!(binds.get(KeyAction::Save), Some(&KeyCombo::ctrl("K")));
+    assert_eq!(binds.get(KeyAction::Find), Some(&KeyCombo::ctrl("F")));
+    // Unbinding sticks through a save and load
+    binds.unbind(KeyAction::Jump);
+    let binds: Keybinds = crate::test_util::reload_as(&binds);
+    assert_eq!(binds.get(KeyAction::Jump), None);
+}
@@ -15,6 +15,34 @@ pub struct Layout {
     /// Margin around views
     #[serde(default = "default_margin")]
     pub margin: ViewportScalar,
+    /// Relative heights of the rows.
+    ///
+    /// Rows are sized automatically if this doesn't match the row count.
+    #[serde(default)]
+    pub row_weights: Vec<u16>,
+    /// Relative widths of the views in each row.
+    ///
+    /// A row is sized automatically if its weights don't match its view count.
+    #[serde(default)]
+    pub col_weights: Vec<Vec<u16>>,
+}
+
+/// A draggable boundary between two adjacent views
+#[derive(Clone, Copy, Debug)]
+pub enum Divider {
+    /// Between row `n` and row `n + 1`
+    Row(usize),
+    /// Between view `col` and view `col + 1` of `row`
+    Col { row: usize, col: usize },
+}
+
+/// Direction to move an item in the view grid
+#[derive(Clone, Copy)]
+pub enum Direction {
+    /// Left for views, up for rows
+    Prev,
+    /// Right for views, down for rows
+    Next,
 }
 
 pub const fn default_margin() -> ViewportScalar {
@@ -50,6 +78,106 @@ impl Layout {
         self.iter().any(|k| k == key)
     }
 
+    /// Move a view one position left or right within its row
+    pub(crate) fn move_view_in_row(&mut self, key: ViewKey, dir: Direction) {
+        let Some((row, col)) = self.idx_of_key(key) else { return };
+        let row = &mut self.view_grid[row];
+        match dir {
+            Direction::Prev if col > 0 => row.swap(col, col - 1),
+            Direction::Next if col + 1 < row.len() => row.swap(col, col + 1),
+            _ => {}
+        }
+    }
+
+    /// Move a row one position up or down
+    pub(crate) fn move_row(&mut self, row_idx: usize, dir: Direction) {
+        match dir {
+            Direction::Prev if row_idx > 0 => self.view_grid.swap(row_idx, row_idx - 1),
+            Direction::Next if row_idx + 1 < self.view_grid.len() => {
+                self.view_grid.swap(row_idx, row_idx + 1)
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the divider in the margin at the given position, if any
+    pub(crate) fn divider_at_pos(
+        &self,
+        views: &ViewMap,
+        x: ViewportScalar,
+        y: ViewportScalar,
+    ) -> Option<Divider> {
+        for (row_idx, row) in self.view_grid.iter().enumerate() {
+            for (col, pair) in row.windows(2).enumerate() {
+                let a = &views[pair[0]].view.viewport_rect;
+                let b = &views[pair[1]].view.viewport_rect;
+                if x > a.x + a.w && x < b.x && y >= a.y && y <= a.y + a.h.max(b.h) {
+                    return Some(Divider::Col { row: row_idx, col });
+                }
+            }
+            let Some(next_row) = self.view_grid.get(row_idx + 1) else { break };
+            let bottom = row
+                .iter()
+                .map(|&k| {
+                    let rect = &views[k].view.viewport_rect;
+                    rect.y + rect.h
+                })
+                .max();
+            let next_top = next_row.iter().map(|&k| views[k].view.viewport_rect.y).min();
+            if let (Some(bottom), Some(next_top)) = (bottom, next_top)
+                && y > bottom
+                && y < next_top
+            {
+                return Some(Divider::Row(row_idx));
+            }
+        }
+        None
+    }
+
+    /// Move a divider by `delta` pixels, turning the affected views' sizes into weights
+    pub(crate) fn drag_divider(
+        &mut self,
+        views: &ViewMap,
+        divider: Divider,
+        delta: ViewportScalar,
+    ) {
+        match divider {
+            Divider::Row(row_idx) => {
+                if self.row_weights.len() != self.view_grid.len() {
+                    self.row_weights = self
+                        .view_grid
+                        .iter()
+                        .map(|row| {
+                            let h = row.iter().map(|&k| views[k].view.viewport_rect.h).max();
+                            size_weight(h.unwrap_or(0))
+                        })
+                        .collect();
+                }
+                shift_weight(&mut self.row_weights, row_idx, delta);
+            }
+            Divider::Col { row, col } => {
+                let Some(keys) = self.view_grid.get(row) else { return };
+                if self.col_weights.len() < self.view_grid.len() {
+                    self.col_weights.resize(self.view_grid.len(), Vec::new());
+                }
+                let weights = &mut self.col_weights[row];
+                if weights.len() != keys.len() {
+                    *weights = keys
+                        .iter()
+                        .map(|&k| size_weight(views[k].view.viewport_rect.w))
+                        .collect();
+                }
+                shift_weight(weights, col, delta);
+            }
+        }
+    }
+
+    /// Go back to automatic sizing for all rows and views
+    pub(crate) fn reset_weights(&mut self) {
+        self.row_weights.clear();
+        self.col_weights.clear();
+    }
+
     pub(crate) fn remove_view(&mut self, rem_key: ViewKey) {
         self.view_grid.retain_mut(|row| {
             row.retain(|view_key| *view_key != rem_key);
@@ -58,6 +186,43 @@ impl Layout {
     }
 }
 
+fn size_weight(size: ViewportScalar) -> u16 {
+    u16::try_from(size.max(1)).unwrap_or(1)
+}
+
+/// Move `delta` worth of weight from `weights[idx + 1]` to `weights[idx]`,
+/// keeping both at least 1
+fn shift_weight(weights: &mut [u16], idx: usize, delta: ViewportScalar) {
+    let (Some(&a), Some(&b)) = (weights.get(idx), weights.get(idx + 1)) else { return };
+    let total = i32::from(a) + i32::from(b);
+    let new_a = (i32::from(a) + i32::from(delta)).clamp(1, total - 1);
+    weights[idx] = u16::try_from(new_a).unwrap_or(a);
+    weights[idx + 1] = u16::try_from(total - new_a).unwrap_or(b);
+}
+
+/// Split `avail` pixels proportionally to `weights`.
+///
+/// The last item gets the rounding remainder, so the sizes add up to `avail`.
+fn distribute(avail: ViewportScalar, weights: &[u16]) -> Vec<ViewportScalar> {
+    let avail = avail.max(0);
+    let total: i64 = weights.iter().map(|&w| i64::from(w)).sum();
+    let mut sizes: Vec<ViewportScalar> = weights
+        .iter()
+        .map(|&w| {
+            if total == 0 {
+                0
+            } else {
+                ViewportScalar::try_from(i64::from(avail) * i64::from(w) / total)
+                    .unwrap_or(avail)
+            }
+        })
+        .collect();
+    if let Some((last, rest)) = sizes.split_last_mut() {
+        *last = avail - rest.iter().sum::<ViewportScalar>();
+    }
+    sizes
+}
+
 pub fn do_auto_layout(
     layout: &Layout,
     view_map: &mut ViewMap,
@@ -120,6 +285,26 @@ pub fn do_auto_layout(
         }
         h_remaining -= max_can_add;
     }
+    // Explicit weights override the automatic sizes
+    for (row_idx, row) in layout.view_grid.iter().enumerate() {
+        let Some(weights) = layout.col_weights.get(row_idx) else { break };
+        if weights.len() != row.len() {
+            continue;
+        }
+        let row_n_cols = i16::try_from(row.len()).expect("Too many columns in layout");
+        let avail = hex_iface_rect.w - (layout.margin * (row_n_cols + 1));
+        for (&view_key, w) in row.iter().zip(distribute(avail, weights)) {
+            view_map[view_key].view.viewport_rect.w = w;
+        }
+    }
+    if layout.row_weights.len() == layout.view_grid.len() {
+        let heights = distribute(h_to_fill_viewport, &layout.row_weights);
+        for (row, h) in layout.view_grid.iter().zip(heights) {
+            for &view_key in row {
+                view_map[view_key].view.viewport_rect.h = h;
+            }
+        }
+    }
     // Lay out
     let mut x_cursor = hex_iface_rect.x + layout.margin;
     let mut y_cursor = hex_iface_rect.y + layout.margin;
@@ -136,3 +321,16 @@ pub fn do_auto_layout(
         y_cursor += max_h + layout.margin;
     }
 }
+
+#[test]
+fn test_distribute() {
+    assert_eq!(distribute(100, &[1, 1]), [50, 50]);
+    assert_eq!(distribute(100, &[1, 2]), [33, 67]);
+    assert_eq!(distribute(10, &[0, 0]), [0, 10]);
+    assert!(distribute(10, &[]).is_empty());
+    let mut weights = [10, 20];
+    shift_weight(&mut weights, 0, 5);
+    assert_eq!(weights, [15, 15]);
+    shift_weight(&mut weights, 0, -100);
+    assert_eq!(weights, [1, 29]);
+}
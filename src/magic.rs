@@ -0,0 +1,56 @@
+//! Detection of common file formats from the magic bytes at the start of the data
+
+/// A known file format, recognized by the bytes it starts with
+pub struct Signature {
+    pub name: &'static str,
+    magic: &'static [u8],
+    /// Length of the fixed size header of the format, at least as long as `magic`
+    pub header_len: usize,
+}
+
+/// More specific signatures come first, so they win over shorter ones with the same prefix
+const SIGNATURES: &[Signature] = &[
+    sig("PNG", b"\x89PNG\r\n\x1a\n", 8),
+    sig("ELF (32-bit)", b"\x7fELF\x01", 52),
+    sig("ELF (64-bit)", b"\x7fELF\x02", 64),
+    sig("ZIP", b"PK\x03\x04", 30),
+    sig("GZIP", b"\x1f\x8b", 10),
+    sig("PDF", b"%PDF-", 8),
+    sig("GIF", b"GIF87a", 13),
+    sig("GIF", b"GIF89a", 13),
+    // Start of image, and the marker of the first segment
+    sig("JPEG", b"\xff\xd8\xff", 4),
+    sig("7z", b"7z\xbc\xaf\x27\x1c", 32),
+    sig("RAR", b"Rar!\x1a\x07", 7),
+    sig("SQLite 3", b"SQLite format 3\0", 100),
+    sig("RIFF", b"RIFF", 12),
+    sig("Ogg", b"OggS", 27),
+    sig("FLAC", b"fLaC", 4),
+    sig("Java class", b"\xca\xfe\xba\xbe", 10),
+    sig("PE/DOS (MZ)", b"MZ", 64),
+    sig("BMP", b"BM", 14),
+];
+
+const fn sig(name: &'static str, magic: &'static [u8], header_len: usize) -> Signature {
+    Signature {
+        name,
+        magic,
+        header_len,
+    }
+}
+
+/// The format `data` appears to be in, judging by its first bytes
+pub fn detect(data: &[u8]) -> Option<&'static Signature> {
+    SIGNATURES.iter().find(|sig| data.starts_with(sig.magic))
+}
+
+#[test]
+fn test_detect() {
+    let name = |data: &[u8]| detect(data).map(|sig| sig.name);
+    assert_eq!(name(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), Some("PNG"));
+    assert_eq!(name(b"\x7fELF\x02\x01\x01"), Some("ELF (64-bit)"));
+    assert_eq!(name(b"MZ\x90\0"), Some("PE/DOS (MZ)"));
+    assert_eq!(name(b"\x89PN"), None);
+    assert_eq!(name(b"hello"), None);
+    assert!(SIGNATURES.iter().all(|sig| sig.header_len >= sig.magic.len()));
+}
@@ -22,30 +22,45 @@
 
 mod app;
 mod args;
+mod c_header;
 mod color;
+mod compression;
 mod config;
 mod damage_region;
+mod data_map;
 mod dec_conv;
+mod edit_log;
 pub mod edit_buffer;
 mod gui;
 mod hex_conv;
+mod hex_dump;
 mod hex_ui;
 mod input;
+mod ips;
+mod keybinds;
 mod layout;
+mod magic;
 mod meta;
 mod meta_state;
+mod offset_expr;
 mod parse_radix;
 mod preferences;
 mod shell;
 mod slice_ext;
 mod source;
+mod template;
+#[cfg(test)]
+mod test_util;
 mod timer;
 mod view;
 #[cfg(windows)]
 mod windows;
 
 use {
-    crate::{app::App, view::ViewportVec},
+    crate::{
+        app::{edit_op::EditOp, App, FileOp},
+        view::ViewportVec,
+    },
     anyhow::Context,
     app::interact_mode::InteractMode,
     args::Args,
@@ -62,7 +77,8 @@ use {
         SfEgui,
     },
     gamedebug_core::per_msg,
-    gui::{dialogs::JumpDialog, ContextMenu, ContextMenuData, Gui},
+    gui::{dialogs::JumpDialog, ops::request_file_op, ContextMenu, ContextMenuData, Gui},
+    keybinds::KeyAction,
     meta::{NamedView, PerspectiveMap, RegionMap},
     serde::{Deserialize, Serialize},
     shell::{msg_if_fail, msg_warn},
@@ -97,6 +113,7 @@ fn try_main() -> anyhow::Result<()> {
     sf_egui.context().set_style(style);
     let mut vertex_buffer = Vec::new();
     let mut gui = Gui::default();
+    let mut title = String::from("Hexerator");
 
     while window.is_open() {
         if !do_frame(
@@ -109,6 +126,11 @@ fn try_main() -> anyhow::Result<()> {
         ) {
             return Ok(());
         }
+        let new_title = app.window_title();
+        if new_title != title {
+            window.set_title(&new_title);
+            title = new_title;
+        }
         // Save a metafile backup every so often
         if app.meta_state.last_meta_backup.get().elapsed() >= Duration::from_secs(60) {
             if let Err(e) = app.save_temp_metafile_backup() {
@@ -116,7 +138,7 @@ fn try_main() -> anyhow::Result<()> {
             }
         }
     }
-    app.close_file();
+    app.close_all_tabs();
     app.cfg.save()?;
     Ok(())
 }
@@ -141,6 +163,9 @@ fn do_frame(
     if !gui::do_egui(sf_egui, gui, app, mp, font) {
         return false;
     }
+    if let Some(op) = app.pending_file_op.take() {
+        app.run_file_op(op, font);
+    }
     let [r, g, b] = app.preferences.bg_color;
     #[expect(
         clippy::cast_possible_truncation,
@@ -204,6 +229,15 @@ fn update(app: &mut App, egui_wants_kb: bool) {
             app.meta_state.meta.views[key].view.scroll_y(spd);
         }
     }
+    if let Some(key) = app.hex_ui.focused_view {
+        app.meta_state.meta.views[key].view.update_smooth_scroll();
+    }
+    if app.preferences.keep_cursor_centered
+        && app.edit_state.cursor != app.hex_ui.prev_frame_cursor
+    {
+        app.center_view_on_offset(app.edit_state.cursor);
+    }
+    app.hex_ui.prev_frame_cursor = app.edit_state.cursor;
     // Sync all other views to active view
     if let Some(key) = app.hex_ui.focused_view {
         let src = &app.meta_state.meta.views[key].view;
@@ -211,12 +245,16 @@ fn update(app: &mut App, egui_wants_kb: bool) {
         let (src_row, src_col) = (src.scroll_offset.row(), src.scroll_offset.col());
         let (src_yoff, src_xoff) = (src.scroll_offset.pix_yoff(), src.scroll_offset.pix_xoff());
         let (src_row_h, src_col_w) = (src.row_h, src.col_w);
+        let src_sync = src.sync_scroll;
         for NamedView { view, name: _ } in app.meta_state.meta.views.values_mut() {
             // Only sync views that have the same perspective
             if view.perspective != src_perspective {
                 continue;
             }
-            view.sync_to(src_row, src_yoff, src_col, src_xoff, src_row_h, src_col_w);
+            // Locked views neither follow nor drive other views
+            if src_sync && view.sync_scroll {
+                view.sync_to(src_row, src_yoff, src_col, src_xoff, src_row_h, src_col_w);
+            }
             // Also clamp view ranges
             if view.scroll_offset.row == 0 && view.scroll_offset.pix_yoff < 0 {
                 view.scroll_offset.pix_yoff = 0;
@@ -263,6 +301,9 @@ fn draw(
     for view_key in app.meta_state.meta.layouts[app.hex_ui.current_layout].iter() {
         crate::view::View::draw(view_key, app, gui, window, vertex_buffer, font);
     }
+    if app.hex_ui.show_alt_overlay {
+        crate::view::View::draw_alt_legend(app, window, vertex_buffer, font);
+    }
 }
 
 fn handle_events(
@@ -305,8 +346,25 @@ fn handle_events(
                 }
                 if button == mouse::Button::Left {
                     gui.context_menu = None;
-                    if let Some((off, _view_idx)) = app.byte_offset_at_pos(mp.x, mp.y) {
+                    let layout = &app.meta_state.meta.layouts[app.hex_ui.current_layout];
+                    if let Some(divider) =
+                        layout.divider_at_pos(&app.meta_state.meta.views, mp.x, mp.y)
+                    {
+                        app.hex_ui.divider_drag = Some((divider, mp));
+                        continue;
+                    }
+                    if let Some((off, view_key)) = app.byte_offset_at_pos(mp.x, mp.y) {
                         app.edit_state.set_cursor(off);
+                        // Clicking a bit in a bit view toggles it
+                        if app.hex_ui.interact_mode == InteractMode::Edit
+                            && let Some(bit) =
+                                app.meta_state.meta.views[view_key].view.bit_at_pos(mp.x, mp.y)
+                        {
+                            msg_if_fail(
+                                app.apply_op(EditOp::ToggleBit(bit)),
+                                "Failed to toggle bit",
+                            );
+                        }
                     }
                     if let Some(view_idx) = app.view_idx_at_pos(mp.x, mp.y) {
                         app.hex_ui.focused_view = Some(view_idx);
@@ -325,9 +383,31 @@ fn handle_events(
                     }
                 }
             }
+            Event::MouseWheelScrolled { delta, .. }
+                if !wants_pointer && app.input.key_down(Key::LControl) =>
+            {
+                app.zoom_focused_view(if delta > 0.0 { 1 } else { -1 }, font);
+            }
+            Event::MouseWheelScrolled { delta, .. } if !wants_pointer => {
+                if let Some(key) = app.hex_ui.focused_view {
+                    let smooth = app.preferences.smooth_scroll;
+                    app.meta_state.meta.views[key].view.wheel_scroll(delta, smooth);
+                }
+            }
+            Event::MouseButtonReleased {
+                button: mouse::Button::Left,
+                ..
+            } => app.hex_ui.divider_drag = None,
+            Event::MouseMoved { x, y } => {
+                if app.hex_ui.divider_drag.is_some() {
+                    app.drag_divider(try_conv_mp_zero((x, y)));
+                }
+            }
             Event::LostFocus => {
                 // When alt-tabbing, keys held down can get "stuck", because the key release events won't reach us
                 app.input.clear();
+                // Don't leave pending edits unsaved while the user is elsewhere
+                app.auto_save();
             }
             Event::Resized {
                 mut width,
@@ -378,6 +458,7 @@ fn handle_text_entered(app: &mut App, unicode: char) {
                 &mut app.edit_state,
                 &app.preferences,
                 &mut app.data,
+                &app.meta_state.meta.low.perspectives,
             );
             keep_cursor_in_view(
                 view,
@@ -390,6 +471,63 @@ fn handle_text_entered(app: &mut App, unicode: char) {
     }
 }
 
+fn handle_key_action(action: KeyAction, gui: &mut Gui, app: &mut App, font: &Font) {
+    match action {
+        KeyAction::Open => request_file_op(gui, app, FileOp::Open, font),
+        KeyAction::OpenPrevious => {
+            let mut load = None;
+            crate::shell::open_previous(app, &mut load);
+            if let Some(args) = load {
+                let args = Args{ src: args, recent: false, meta: None };
+                request_file_op(gui, app, FileOp::Load(args), font);
+            }
+        }
+        KeyAction::Save => match &mut app.source {
+            Some(source) => {
+                if !source.attr.permissions.write {
+                    msg_warn("This source cannot be written to.");
+                } else {
+                    msg_if_fail(app.save(), "Failed to save");
+                }
+            }
+            None => msg_warn("No source opened"),
+        },
+        KeyAction::Reload => request_file_op(gui, app, FileOp::Reload, font),
+        KeyAction::Close => request_file_op(gui, app, FileOp::Close, font),
+        KeyAction::Find => gui.find_dialog.open.toggle(),
+        KeyAction::Jump => gui.add_dialog(JumpDialog::default()),
+        KeyAction::SelectAll => app.focused_view_select_all(),
+        KeyAction::CenterSelection => app.center_view_on_selection(),
+        KeyAction::ZoomToSelection => app.zoom_to_fit_selection(font),
+        KeyAction::ToggleTopPanel => app.preferences.show_top_panel ^= true,
+        KeyAction::ToggleBottomPanel => app.preferences.show_bottom_panel ^= true,
+        KeyAction::ToggleInspectPanel => app.preferences.show_inspect_panel ^= true,
+        KeyAction::PrevField => app.jump_to_field(false),
+        KeyAction::NextField => app.jump_to_field(true),
+        KeyAction::NextDifferentByte => app.skip_to_different_byte(true),
+        KeyAction::PrevDifferentByte => app.skip_to_different_byte(false),
+        KeyAction::ReferenceNext => app.jump_to_reference_diff(true),
+        KeyAction::ReferencePrev => app.jump_to_reference_diff(false),
+        KeyAction::RegionStart => app.jump_to_region_boundary(false),
+        KeyAction::RegionEnd => app.jump_to_region_boundary(true),
+        KeyAction::ToggleEditTarget => app.toggle_edit_target(),
+        KeyAction::DiffNext => gui.file_diff_result_window.next_diff(app),
+        KeyAction::DiffPrev => gui.file_diff_result_window.prev_diff(app),
+        KeyAction::RepeatLastOp => {
+            if app.hex_ui.interact_mode == InteractMode::View {
+                app.repeat_last_op();
+            }
+        }
+        // Handled separately, as these should work even without any data
+        KeyAction::TogglePanels
+        | KeyAction::ReopenClosed
+        | KeyAction::NewTab
+        | KeyAction::PrevTab
+        | KeyAction::NextTab
+        | KeyAction::DebugPanel => {}
+    }
+}
+
 struct KeyMod {
     ctrl: bool,
     shift: bool,
@@ -404,13 +542,53 @@ fn handle_key_pressed(
     font: &Font,
     egui_wants_kb: bool,
 ) {
-    if code == Key::F12 && !key_mod.shift && !key_mod.ctrl && !key_mod.alt {
+    if gui.preferences_window.is_rebinding() {
+        return;
+    }
+    let action = app.cfg.keybinds.action_for(code, &key_mod);
+    if action == Some(KeyAction::DebugPanel) {
         gamedebug_core::toggle();
     }
+    // Without the top panel there's no menu to bring it back with, so there is a way back
+    // that doesn't depend on the keybinds
+    if code == Key::Escape && !app.preferences.show_top_panel {
+        app.preferences.show_top_panel = true;
+        return;
+    }
+    // Handled before the empty data check, since there is usually nothing open at this point
+    if !egui_wants_kb {
+        match action {
+            Some(KeyAction::TogglePanels) => {
+                app.preferences.toggle_panels();
+                return;
+            }
+            Some(KeyAction::ReopenClosed) => {
+                request_file_op(gui, app, FileOp::ReopenClosed, font);
+                return;
+            }
+            Some(KeyAction::NewTab) => {
+                app.new_tab();
+                return;
+            }
+            Some(KeyAction::PrevTab | KeyAction::NextTab) if app.tabs.len() > 1 => {
+                app.cycle_tab(action == Some(KeyAction::NextTab));
+                return;
+            }
+            _ => {}
+        }
+    }
     if app.data.is_empty() || egui_wants_kb {
         return;
     }
+    if let Some(action) = action {
+        handle_key_action(action, gui, app, font);
+        return;
+    }
+    let cursor_before = app.edit_state.cursor;
     match code {
+        Key::Home | Key::End if key_mod.shift && app.hex_ui.interact_mode == InteractMode::Edit => {
+            app.cursor_to_row_edge(code == Key::End);
+        }
         Key::Up => match app.hex_ui.interact_mode {
             InteractMode::View => {
                 if key_mod.ctrl && let Some(view_key) = app.hex_ui.focused_view {
@@ -586,49 +764,41 @@ fn handle_key_pressed(
         }
         Key::Enter => {
             if let Some(view_key) = app.hex_ui.focused_view {
-                app.meta_state.meta.views[view_key].view.finish_editing(&mut app.edit_state, &mut app.data, &app.preferences);
-            }
-        }
-        Key::A if key_mod.ctrl => {
-            app.focused_view_select_all();
-        }
-        Key::F if key_mod.ctrl => {
-            gui.find_dialog.open.toggle();
-        }
-        Key::S if key_mod.ctrl => match &mut app.source {
-            Some(source) => {
-                if !source.attr.permissions.write {
-                    msg_warn("This source cannot be written to.");
-                } else {
-                    msg_if_fail(app.save(), "Failed to save");
-                }
-            }
-            None => msg_warn("No source opened"),
-        },
-        Key::R if key_mod.ctrl => {
-            msg_if_fail(app.reload(), "Failed to reload");
-        }
-        Key::O if key_mod.ctrl => {
-            shell::open_file(app, font);
-        }
-        Key::P if key_mod.ctrl => {
-            let mut load = None;
-            crate::shell::open_previous(app, &mut load);
-            if let Some(args) = load {
-                msg_if_fail(
-                    app.load_file_args(Args{ src: args, recent: false, meta: None },font),
-                    "Failed to load file",
+                app.meta_state.meta.views[view_key].view.finish_editing(
+                    &mut app.edit_state,
+                    &mut app.data,
+                    &app.preferences,
+                    &app.meta_state.meta.low.perspectives,
                 );
             }
         }
-        Key::W if key_mod.ctrl => app.close_file(),
-        Key::J if key_mod.ctrl => gui.add_dialog(JumpDialog::default()),
         Key::Num1 if key_mod.shift => app.hex_ui.select_a = Some(app.edit_state.cursor),
         Key::Num2 if key_mod.shift => app.hex_ui.select_b = Some(app.edit_state.cursor),
         Key::Tab if key_mod.shift => app.focus_prev_view_in_layout(),
         Key::Tab => app.focus_next_view_in_layout(),
         _ => {}
     }
+    if app.hex_ui.interact_mode == InteractMode::Edit
+        && !key_mod.ctrl
+        && !key_mod.alt
+        && matches!(code, Key::Left | Key::Right | Key::Up | Key::Down | Key::Home | Key::End)
+    {
+        update_key_selection(app, cursor_before, key_mod.shift);
+    }
+}
+
+/// Moving the cursor with shift held selects from where the cursor was (or extends the existing
+/// selection). Moving it without shift drops the selection.
+fn update_key_selection(app: &mut App, cursor_before: usize, shift: bool) {
+    if shift {
+        if app.hex_ui.select_a.is_none() {
+            app.hex_ui.select_a = Some(cursor_before);
+        }
+        app.hex_ui.select_b = Some(app.edit_state.cursor);
+    } else {
+        app.hex_ui.select_a = None;
+        app.hex_ui.select_b = None;
+    }
 }
 
 fn keep_cursor_in_view(
@@ -638,7 +808,7 @@ fn keep_cursor_in_view(
     cursor: usize,
 ) {
     let view_offs = view.offsets(perspectives, regions);
-    let (cur_row, cur_col) = perspectives[view.perspective].row_col_of_byte_offset(cursor, regions);
+    let (cur_row, cur_col) = view.row_col_of_byte_offset(cursor, perspectives, regions);
     view.scroll_offset.pix_xoff = 0;
     view.scroll_offset.pix_yoff = 0;
     if view_offs.row > cur_row {
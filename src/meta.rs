@@ -7,6 +7,7 @@ use {
     egui_sfml::egui::epaint::ahash::HashMap,
     serde::{Deserialize, Serialize},
     slotmap::{new_key_type, SlotMap},
+    std::collections::BTreeMap,
 };
 
 new_key_type! {
@@ -23,7 +24,7 @@ pub type LayoutMap = SlotMap<LayoutKey, Layout>;
 pub type Bookmarks = Vec<Bookmark>;
 
 /// A bookmark for an offset in a file
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
 pub struct Bookmark {
     /// Offset the bookmark applies to
     pub offset: usize,
@@ -34,14 +35,19 @@ pub struct Bookmark {
     /// A bookmark can optionally have a type, which can be used to display its value, etc.
     #[serde(default)]
     pub value_type: ValueType,
+    /// Byte order of multi-byte values
+    #[serde(default)]
+    pub big_endian: bool,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Default, Debug)]
 pub enum ValueType {
     #[default]
     None,
     U8,
-    U16Le,
+    /// Byte order is determined by [`Bookmark::big_endian`]
+    #[serde(alias = "U16Le")]
+    U16,
     StringMap(HashMap<u8, String>),
 }
 
@@ -72,6 +78,9 @@ pub struct Meta {
     pub layouts: LayoutMap,
     pub bookmarks: Bookmarks,
     pub misc: Misc,
+    /// Free-form comments attached to offsets
+    #[serde(default)]
+    pub comments: BTreeMap<usize, String>,
 }
 
 pub(crate) fn find_most_specific_region_for_offset(
@@ -96,6 +105,16 @@ pub(crate) fn find_most_specific_region_for_offset(
     most_specific
 }
 
+/// Returns the tint of the most specific tinted region containing `off`, if any
+pub(crate) fn region_tint_for_offset(regions: &RegionMap, off: usize) -> Option<[u8; 4]> {
+    regions
+        .values()
+        .filter(|reg| reg.region.contains(off))
+        .filter_map(|reg| Some((reg.tint?, reg.region.len())))
+        .min_by_key(|&(_, len)| len)
+        .map(|(tint, _)| tint)
+}
+
 /// Misc information that's worth saving
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Misc {
@@ -104,12 +123,16 @@ pub struct Misc {
     /// Worth saving because it can be used for binary file change testing, which can
     /// take a long time over many sessions.
     pub fill_lua_script: String,
+    /// Added to file offsets when displaying them, e.g. the load address of a memory dump
+    #[serde(default)]
+    pub display_base_address: u64,
 }
 
 impl Default for Misc {
     fn default() -> Self {
         Self {
             fill_lua_script: DEFAULT_CODE.into(),
+            display_base_address: 0,
         }
     }
 }
@@ -141,6 +164,80 @@ impl Meta {
             .insert(NamedRegion::new_from_selection(sel))
     }
 
+    /// Adjust offset based metadata after `delta` bytes were inserted (positive)
+    /// or deleted (negative) at offset `at`.
+    ///
+    /// Bookmarks inside a deleted range are removed, regions are clamped to what remains of them.
+    pub fn shift_offsets(&mut self, at: usize, delta: isize) {
+        let n = delta.unsigned_abs();
+        if !delta.is_negative() {
+            let shift = |off: &mut usize| {
+                if *off >= at {
+                    *off += n;
+                }
+            };
+            for bm in &mut self.bookmarks {
+                shift(&mut bm.offset);
+            }
+            for reg in self.low.regions.values_mut() {
+                shift(&mut reg.region.begin);
+                shift(&mut reg.region.end);
+            }
+            self.comments = std::mem::take(&mut self.comments)
+                .into_iter()
+                .map(|(mut off, comment)| {
+                    shift(&mut off);
+                    (off, comment)
+                })
+                .collect();
+        } else {
+            // Exclusive end of the deleted range
+            let del_end = at + n;
+            self.bookmarks.retain_mut(|bm| {
+                if bm.offset >= del_end {
+                    bm.offset -= n;
+                    true
+                } else {
+                    bm.offset < at
+                }
+            });
+            for reg in self.low.regions.values_mut() {
+                let r = &mut reg.region;
+                if r.begin >= del_end {
+                    r.begin -= n;
+                } else if r.begin >= at {
+                    r.begin = at;
+                }
+                if r.end >= del_end {
+                    r.end -= n;
+                } else if r.end >= at {
+                    r.end = at.saturating_sub(1).max(r.begin);
+                }
+            }
+            // Comments on deleted bytes go away with them
+            self.comments = std::mem::take(&mut self.comments)
+                .into_iter()
+                .filter_map(|(off, comment)| {
+                    if off >= del_end {
+                        Some((off - n, comment))
+                    } else {
+                        (off < at).then_some((off, comment))
+                    }
+                })
+                .collect();
+        }
+    }
+
+    /// Extend the regions that end at `last`, the last offset of the data before `n` bytes
+    /// were appended to it, so they still reach the end of the data
+    pub fn extend_regions_at_end(&mut self, last: usize, n: usize) {
+        for reg in self.low.regions.values_mut() {
+            if reg.region.end == last {
+                reg.region.end += n;
+            }
+        }
+    }
+
     pub(crate) fn remove_view(&mut self, rem_key: ViewKey) {
         self.views.remove(rem_key);
 
@@ -156,6 +253,9 @@ pub struct NamedRegion {
     pub region: Region,
     #[serde(default)]
     pub desc: String,
+    /// Optional RGBA color to tint the background of the bytes in this region with
+    #[serde(default)]
+    pub tint: Option<[u8; 4]>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -169,6 +269,85 @@ impl NamedRegion {
             name: format!("New ({}..={})", sel.begin, sel.end),
             region: sel,
             desc: String::new(),
+            tint: None,
         }
     }
 }
+
+#[cfg(test)]
+fn test_meta(bookmark_offsets: &[usize], region: Region) -> (Meta, RegionKey) {
+    let mut meta = Meta::default();
+    for &offset in bookmark_offsets {
+        meta.bookmarks.push(Bookmark {
+            offset,
+            label: String::new(),
+            desc: String::new(),
+            value_type: ValueType::None,
+            big_endian: false,
+        });
+    }
+    let key = meta.add_region_from_selection(region);
+    (meta, key)
+}
+
+#[cfg(test)]
+fn bookmark_offsets(meta: &Meta) -> Vec<usize> {
+    meta.bookmarks.iter().map(|bm| bm.offset).collect()
+}
+
+#[test]
+fn test_shift_offsets_insert_before() {
+    let (mut meta, key) = test_meta(&[2, 10], Region { begin: 10, end: 20 });
+    meta.shift_offsets(3, 5);
+    assert_eq!(bookmark_offsets(&meta), [2, 15]);
+    assert_eq!(meta.low.regions[key].region, Region { begin: 15, end: 25 });
+}
+
+#[test]
+fn test_extend_regions_at_end() {
+    let (mut meta, key) = test_meta(&[], Region { begin: 10, end: 20 });
+    let inner = meta.add_region_from_selection(Region { begin: 0, end: 5 });
+    // Appending after the last offset doesn't move anything
+    meta.shift_offsets(21, 4);
+    meta.extend_regions_at_end(20, 4);
+    assert_eq!(meta.low.regions[key].region, Region { begin: 10, end: 24 });
+    assert_eq!(meta.low.regions[inner].region, Region { begin: 0, end: 5 });
+}
+
+#[test]
+fn test_shift_offsets_insert_inside_region() {
+    let (mut meta, key) = test_meta(&[12, 18], Region { begin: 10, end: 20 });
+    meta.shift_offsets(15, 4);
+    assert_eq!(bookmark_offsets(&meta), [12, 22]);
+    assert_eq!(meta.low.regions[key].region, Region { begin: 10, end: 24 });
+}
+
+#[test]
+fn test_shift_offsets_delete_spanning_bookmark() {
+    let (mut meta, key) = test_meta(&[5, 11, 15, 30], Region { begin: 10, end: 20 });
+    // Delete 11..=15
+    meta.shift_offsets(11, -5);
+    assert_eq!(bookmark_offsets(&meta), [5, 25]);
+    assert_eq!(meta.low.regions[key].region, Region { begin: 10, end: 15 });
+}
+
+#[test]
+fn test_shift_offsets_comments() {
+    let (mut meta, _) = test_meta(&[], Region { begin: 0, end: 0 });
+    for off in [2, 11, 30] {
+        meta.comments.insert(off, off.to_string());
+    }
+    meta.shift_offsets(5, 3);
+    assert_eq!(meta.comments.keys().copied().collect::<Vec<_>>(), [2, 14, 33]);
+    // Delete 10..=14
+    meta.shift_offsets(10, -5);
+    assert_eq!(meta.comments.keys().copied().collect::<Vec<_>>(), [2, 28]);
+    assert_eq!(meta.comments[&28], "30");
+}
+
+#[test]
+fn test_shift_offsets_delete_whole_region() {
+    let (mut meta, key) = test_meta(&[], Region { begin: 12, end: 14 });
+    meta.shift_offsets(10, -10);
+    assert_eq!(meta.low.regions[key].region, Region { begin: 10, end: 10 });
+}
@@ -18,6 +18,13 @@ pub struct Perspective {
     /// A row order flipped perspective helps view and manipulate this kind of data better.
     pub flip_row_order: bool,
     pub name: String,
+    /// Columns that a vertical guide line is drawn in front of
+    #[serde(default)]
+    pub guide_cols: Vec<usize>,
+    /// Sector size for disk images (0 = off). Rows starting a sector get a separator and
+    /// a sector number.
+    #[serde(default)]
+    pub sector_size: usize,
 }
 
 impl Perspective {
@@ -37,12 +44,19 @@ impl Perspective {
         let offset = offset.saturating_sub(reg.region.begin);
         (offset / self.cols, offset % self.cols)
     }
-    /// Whether the columns are within `cols` and the calculated offset is within the region
-    pub(crate) fn row_col_within_bound(&self, row: usize, col: usize, rmap: &RegionMap) -> bool {
+    /// Whether the columns are within `cols` and the calculated offset (shifted by `shift`)
+    /// is within the region
+    pub(crate) fn row_col_within_bound(
+        &self,
+        row: usize,
+        col: usize,
+        shift: usize,
+        rmap: &RegionMap,
+    ) -> bool {
         col < self.cols
             && rmap[self.region]
                 .region
-                .contains(self.byte_offset_of_row_col(row, col, rmap))
+                .contains(self.byte_offset_of_row_col(row, col, rmap) + shift)
     }
     pub(crate) fn clamp_cols(&mut self, rmap: &RegionMap) {
         self.cols = self.cols.clamp(1, rmap[self.region].region.len())
@@ -60,12 +74,103 @@ impl Perspective {
         rows
     }
 
+    /// Offsets of the bytes in `row` that are within the region
+    pub(crate) fn row_offsets(&self, row: usize, rmap: &RegionMap) -> Vec<usize> {
+        let region = &rmap[self.region].region;
+        (0..self.cols)
+            .map(|col| self.byte_offset_of_row_col(row, col, rmap))
+            .filter(|&off| region.contains(off))
+            .collect()
+    }
+    /// Offsets of the bytes in `col` across every row that are within the region
+    pub(crate) fn col_offsets(&self, col: usize, rmap: &RegionMap) -> Vec<usize> {
+        let region = &rmap[self.region].region;
+        (0..self.n_rows(rmap))
+            .map(|row| self.byte_offset_of_row_col(row, col, rmap))
+            .filter(|&off| region.contains(off))
+            .collect()
+    }
+
+    /// The sector that starts within `row`, if sectors are enabled
+    pub(crate) fn sector_starting_in_row(&self, row: usize, rmap: &RegionMap) -> Option<usize> {
+        if self.sector_size == 0 {
+            return None;
+        }
+        let off = self.byte_offset_of_row_col(row, 0, rmap);
+        let rem = off % self.sector_size;
+        let boundary = if rem == 0 {
+            off
+        } else {
+            off + (self.sector_size - rem)
+        };
+        (boundary < off + self.cols).then_some(boundary / self.sector_size)
+    }
+
     pub(crate) fn from_region(key: RegionKey, name: String) -> Self {
         Self {
             region: key,
             cols: 48,
             flip_row_order: false,
             name,
+            guide_cols: Vec::new(),
+            sector_size: 0,
         }
     }
 }
+
+#[test]
+fn test_row_col_offsets() {
+    let fx = crate::test_util::Fixture::new(Region { begin: 2, end: 11 }, 4);
+    let (per, rmap) = (fx.perspective(), &fx.regions);
+    assert_eq!(per.row_offsets(1, rmap), [6, 7, 8, 9]);
+    // Last row is incomplete
+    assert_eq!(per.row_offsets(2, rmap), [10, 11]);
+    assert_eq!(per.col_offsets(1, rmap), [3, 7, 11]);
+    assert_eq!(per.col_offsets(3, rmap), [5, 9]);
+}
+
+#[test]
+fn test_sector_starting_in_row() {
+    let region = Region {
+        begin: 0,
+        end: 4095,
+    };
+    let mut fx = crate::test_util::Fixture::new(region, 512);
+    let (per, rmap) = (&mut fx.perspectives[fx.per], &fx.regions);
+    per.sector_size = 512;
+    assert_eq!(per.sector_starting_in_row(0, rmap), Some(0));
+    assert_eq!(per.sector_starting_in_row(3, rmap), Some(3));
+    per.cols = 128;
+    assert_eq!(per.sector_starting_in_row(4, rmap), Some(1));
+    assert_eq!(per.sector_starting_in_row(5, rmap), None);
+    per.sector_size = 0;
+    assert_eq!(per.sector_starting_in_row(0, rmap), None);
+}
+
+#[test]
+fn test_display_flags_roundtrip() {
+    use crate::test_util::reload_as;
+    // Saved by an older version, before guide columns and sector sizes existed
+    #[derive(Serialize)]
+    struct OldPerspective {
+        region: RegionKey,
+        cols: usize,
+        flip_row_order: bool,
+        name: String,
+    }
+    let old = |flip_row_order, name: &str| OldPerspective {
+        region: RegionKey::default(),
+        cols: 16,
+        flip_row_order,
+        name: name.into(),
+    };
+    let old = vec![old(true, "flipped"), old(false, "plain")];
+    let mut pers: Vec<Perspective> = reload_as(&old);
+    let flags = |p: &Perspective| (p.flip_row_order, p.guide_cols.clone(), p.sector_size);
+    assert_eq!(flags(&pers[0]), (true, vec![], 0));
+    assert_eq!(flags(&pers[1]), (false, vec![], 0));
+    // Each perspective keeps its own flags through a save and load of the meta
+    pers[0].guide_cols = vec![4, 8];
+    pers[0].sector_size = 512;
+    assert_eq!(reload_as::<Vec<Perspective>>(&pers), pers);
+}
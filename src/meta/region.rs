@@ -19,4 +19,56 @@ impl Region {
     pub(crate) fn contains_region(&self, reg: &Region) -> bool {
         self.begin <= reg.begin && self.end >= reg.end
     }
+
+    /// Expand outward so that `begin` and `end + 1` are multiples of `align`
+    pub(crate) fn aligned_outward(&self, align: usize) -> Region {
+        if align < 2 {
+            return *self;
+        }
+        let begin = self.begin - self.begin % align;
+        let end = self.end.saturating_add(align - self.end % align - 1);
+        Region { begin, end }
+    }
+
+    /// The larger of the two parts of `0..len` that are outside of this region
+    pub(crate) fn larger_complement(&self, len: usize) -> Option<Region> {
+        let before = self.begin;
+        let after = len.saturating_sub(self.end.saturating_add(1));
+        if before == 0 && after == 0 {
+            None
+        } else if before >= after {
+            Some(Region {
+                begin: 0,
+                end: self.begin - 1,
+            })
+        } else {
+            Some(Region {
+                begin: self.end + 1,
+                end: len - 1,
+            })
+        }
+    }
+}
+
+#[test]
+fn test_aligned_outward() {
+    let reg = Region { begin: 5, end: 9 };
+    assert_eq!(reg.aligned_outward(1), reg);
+    assert_eq!(reg.aligned_outward(4), Region { begin: 4, end: 11 });
+    assert_eq!(reg.aligned_outward(16), Region { begin: 0, end: 15 });
+    let aligned = Region { begin: 8, end: 15 };
+    assert_eq!(aligned.aligned_outward(8), aligned);
+}
+
+#[test]
+fn test_larger_complement() {
+    let reg = Region { begin: 10, end: 19 };
+    assert_eq!(reg.larger_complement(25), Some(Region { begin: 0, end: 9 }));
+    assert_eq!(
+        reg.larger_complement(40),
+        Some(Region { begin: 20, end: 39 })
+    );
+    assert_eq!(reg.larger_complement(20), Some(Region { begin: 0, end: 9 }));
+    let all = Region { begin: 0, end: 19 };
+    assert_eq!(all.larger_complement(20), None);
 }
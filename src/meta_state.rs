@@ -11,6 +11,10 @@ pub struct MetaState {
     pub meta: Meta,
     /// Whether metafile needs saving
     pub meta_dirty: bool,
+    /// The sidecar metafile of the current source failed to load.
+    ///
+    /// It's not saved to automatically then, so the metadata in it isn't lost.
+    pub sidecar_load_failed: bool,
 }
 
 impl Default for MetaState {
@@ -21,6 +25,7 @@ impl Default for MetaState {
             last_meta_backup: Cell::new(Instant::now()),
             current_meta_path: PathBuf::new(),
             meta_dirty: false,
+            sidecar_load_failed: false,
         }
     }
 }
@@ -0,0 +1,163 @@
+//! Evaluation of simple arithmetic expressions for offset inputs, like `base + 0x20`
+
+use {crate::parse_radix::parse_guess_radix, anyhow::bail};
+
+/// Values of the identifiers that can be used in an expression
+pub struct Vars {
+    /// `base`: The cursor offset
+    pub base: usize,
+    /// `sel`: Start of the selection, if any
+    pub sel: Option<usize>,
+    /// `end`: The last offset of the data
+    pub end: usize,
+}
+
+/// Evaluate an expression consisting of `+ - * /`, parentheses,
+/// (hex or decimal) literals, and the identifiers of [`Vars`].
+pub fn eval(input: &str, vars: &Vars) -> anyhow::Result<usize> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser {
+        tokens: &tokens,
+        pos: 0,
+        vars,
+    };
+    let value = parser.expr()?;
+    if let Some(tok) = parser.tokens.get(parser.pos) {
+        bail!("Unexpected {:?}", tok);
+    }
+    Ok(value)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Word(&'a str),
+    Op(char),
+    Open,
+    Close,
+}
+
+fn tokenize(input: &str) -> anyhow::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '+' | '-' | '*' | '/' => tokens.push(Token::Op(c)),
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            c if c.is_whitespace() => {}
+            c if c.is_ascii_alphanumeric() => {
+                let mut end = i + 1;
+                while let Some(&(j, c)) = chars.peek() && c.is_ascii_alphanumeric() {
+                    end = j + 1;
+                    chars.next();
+                }
+                tokens.push(Token::Word(&input[i..end]));
+            }
+            _ => bail!("Unexpected character '{}'", c),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser<'a, 'v> {
+    tokens: &'a [Token<'a>],
+    pos: usize,
+    vars: &'v Vars,
+}
+
+impl<'a> Parser<'a, '_> {
+    fn next(&mut self) -> Option<Token<'a>> {
+        let tok = self.tokens.get(self.pos).copied();
+        self.pos += 1;
+        tok
+    }
+    fn peek_op(&self, ops: &[char]) -> Option<char> {
+        match self.tokens.get(self.pos) {
+            Some(&Token::Op(op)) if ops.contains(&op) => Some(op),
+            _ => None,
+        }
+    }
+    fn expr(&mut self) -> anyhow::Result<usize> {
+        let mut lhs = self.term()?;
+        while let Some(op) = self.peek_op(&['+', '-']) {
+            self.pos += 1;
+            let rhs = self.term()?;
+            lhs = match op {
+                '+' => lhs.checked_add(rhs),
+                _ => lhs.checked_sub(rhs),
+            }
+            .ok_or_else(|| anyhow::anyhow!("Result out of range"))?;
+        }
+        Ok(lhs)
+    }
+    fn term(&mut self) -> anyhow::Result<usize> {
+        let mut lhs = self.factor()?;
+        while let Some(op) = self.peek_op(&['*', '/']) {
+            self.pos += 1;
+            let rhs = self.factor()?;
+            lhs = match op {
+                '*' => lhs.checked_mul(rhs),
+                _ => lhs.checked_div(rhs),
+            }
+            .ok_or_else(|| anyhow::anyhow!("Result out of range (or division by zero)"))?;
+        }
+        Ok(lhs)
+    }
+    fn factor(&mut self) -> anyhow::Result<usize> {
+        match self.next() {
+            Some(Token::Word("base")) => Ok(self.vars.base),
+            Some(Token::Word("sel")) => match self.vars.sel {
+                Some(sel) => Ok(sel),
+                None => bail!("`sel` used, but there is no selection"),
+            },
+            Some(Token::Word("end")) => Ok(self.vars.end),
+            Some(Token::Word(word)) => match parse_guess_radix(word) {
+                Ok(num) => Ok(num),
+                Err(e) => bail!("Invalid number '{}': {}", word, e),
+            },
+            Some(Token::Open) => {
+                let value = self.expr()?;
+                match self.next() {
+                    Some(Token::Close) => Ok(value),
+                    _ => bail!("Missing ')'"),
+                }
+            }
+            Some(tok) => bail!("Unexpected {:?}", tok),
+            None => bail!("Unexpected end of expression"),
+        }
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn test_eval_above_4gb() {
+    let vars = Vars {
+        base: 1 << 33,
+        sel: None,
+        end: (1 << 34) - 1,
+    };
+    assert_eq!(eval("0x100000000 + 1", &vars).ok(), Some(0x1_0000_0001));
+    assert_eq!(eval("base + 0x10", &vars).ok(), Some((1 << 33) + 0x10));
+}
+
+#[test]
+fn test_eval() {
+    let vars = Vars {
+        base: 100,
+        sel: None,
+        end: 999,
+    };
+    let ev = |s| eval(s, &vars).ok();
+    assert_eq!(ev("0x1000 + 16*4"), Some(0x1000 + 64));
+    assert_eq!(ev("base + 0x20"), Some(132));
+    assert_eq!(ev("(base - 10) / 2"), Some(45));
+    assert_eq!(ev("end"), Some(999));
+    assert_eq!(ev("ff"), Some(255));
+    assert_eq!(ev("42"), Some(42));
+    assert_eq!(ev("sel"), None);
+    assert_eq!(ev("1 - 2"), None);
+    assert_eq!(ev("1 / 0"), None);
+    assert_eq!(ev("1 +"), None);
+    assert_eq!(ev("(1"), None);
+    assert_eq!(ev("1 2"), None);
+}
@@ -1,4 +1,4 @@
-use num_traits::Num;
+use {num_traits::Num, std::path::Path};
 
 pub fn parse_guess_radix<T: Num>(input: &str) -> Result<T, <T as Num>::FromStrRadixErr> {
     if let Some(stripped) = input.strip_prefix("0x") {
@@ -10,21 +10,27 @@ pub fn parse_guess_radix<T: Num>(input: &str) -> Result<T, <T as Num>::FromStrRa
     }
 }
 
-/// Relativity of an offset
-pub enum Relativity {
-    Absolute,
-    RelAdd,
-    RelSub,
+/// Parse an offset hint at the end of a file name, like in `dump_0x1000.bin`.
+///
+/// Only `0x` prefixed hex counts, so words like `face` or dates like in `photo_2023.jpg`
+/// aren't mistaken for offsets.
+pub fn offset_from_filename(path: &Path) -> Option<u64> {
+    let stem = path.file_stem()?.to_str()?;
+    let hint = stem.rsplit(['_', '-', '@', '.', ' ']).next()?;
+    let hex = hint.strip_prefix("0x").or_else(|| hint.strip_prefix("0X"))?;
+    u64::from_str_radix(hex, 16).ok()
 }
 
-pub fn parse_offset_maybe_relative(
-    input: &str,
-) -> Result<(usize, Relativity), <usize as Num>::FromStrRadixErr> {
-    Ok(if let Some(stripped) = input.strip_prefix('-') {
-        (parse_guess_radix(stripped)?, Relativity::RelSub)
-    } else if let Some(stripped) = input.strip_prefix('+') {
-        (parse_guess_radix(stripped)?, Relativity::RelAdd)
-    } else {
-        (parse_guess_radix(input)?, Relativity::Absolute)
-    })
+#[test]
+fn test_offset_from_filename() {
+    let off = |name: &str| offset_from_filename(Path::new(name));
+    assert_eq!(off("dump_0x1000.bin"), Some(0x1000));
+    assert_eq!(off("/tmp/ram-0x4096.bin"), Some(0x4096));
+    assert_eq!(off("dump@0XfF"), Some(0xff));
+    assert_eq!(off("dump_face.bin"), None);
+    assert_eq!(off("dump.bin"), None);
+    assert_eq!(off("1234.bin"), None);
+    assert_eq!(off("ram-4096.bin"), None);
+    assert_eq!(off("photo_2023.jpg"), None);
+    assert_eq!(off("dump_.bin"), None);
 }
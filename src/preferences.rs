@@ -7,8 +7,16 @@ pub struct Preferences {
     pub quick_edit: bool,
     /// Don't move the cursor after editing is finished
     pub sticky_edit: bool,
+    /// How many bytes the cursor moves forward after editing is finished
+    pub edit_step: usize,
+    /// After editing, move the cursor to the same column of the next row, instead of
+    /// by `edit_step`
+    pub edit_step_next_row: bool,
     /// Automatically save when editing is finished
     pub auto_save: bool,
+    /// Minimum time between auto saves in milliseconds. Edits made in between are
+    /// written together by the next save.
+    pub auto_save_interval_ms: u32,
     /// Keep metadata when loading.
     pub keep_meta: bool,
     /// Try to stay on current column when changing column count
@@ -21,6 +29,80 @@ pub struct Preferences {
     pub auto_reload: bool,
     /// Auto-reload interval in milliseconds
     pub auto_reload_interval_ms: u32,
+    /// Base to use when copying offsets to the clipboard
+    pub copy_offset_base: OffsetBase,
+    /// Separator to use when copying a list of offsets to the clipboard
+    pub copy_offset_sep: OffsetSeparator,
+    /// Number of bytes per group in copied hex dumps
+    pub hex_dump_group: usize,
+    /// Draw bytes that differ from the last loaded/saved data in a distinct color
+    pub mark_dirty_bytes: bool,
+    /// Animate mouse wheel scrolling over a few frames instead of jumping
+    pub smooth_scroll: bool,
+    /// Byte value that "skip to different byte" skips over
+    pub skip_value: u8,
+    /// Show the byte at the cursor in several bases in the bottom panel
+    pub show_cursor_value: bool,
+    /// Highlight the visible bytes that have the same value as the byte at the cursor
+    pub highlight_same_byte: bool,
+    /// Scroll the focused view to keep the cursor centered whenever it moves
+    pub keep_cursor_centered: bool,
+    /// Show the menu bar panel at the top
+    pub show_top_panel: bool,
+    /// Show the status panel at the bottom
+    pub show_bottom_panel: bool,
+    /// Show the inspect panel on the right
+    pub show_inspect_panel: bool,
+}
+
+impl Preferences {
+    /// Hide all the panels, leaving the whole window to the hex views.
+    /// If they're all hidden already, show them again.
+    pub fn toggle_panels(&mut self) {
+        let show = !(self.show_top_panel || self.show_bottom_panel || self.show_inspect_panel);
+        self.show_top_panel = show;
+        self.show_bottom_panel = show;
+        self.show_inspect_panel = show;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffsetBase {
+    Hex,
+    Dec,
+}
+
+impl OffsetBase {
+    pub fn format(self, offset: u64) -> String {
+        match self {
+            Self::Hex => format!("0x{:X}", offset),
+            Self::Dec => offset.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffsetSeparator {
+    Newline,
+    Comma,
+}
+
+impl OffsetSeparator {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Newline => "\n",
+            Self::Comma => ", ",
+        }
+    }
+}
+
+/// Format `offsets` as a list, for copying to the clipboard
+pub fn offset_list(offsets: &[u64], base: OffsetBase, sep: OffsetSeparator) -> String {
+    offsets
+        .iter()
+        .map(|&off| base.format(off))
+        .collect::<Vec<_>>()
+        .join(sep.as_str())
 }
 
 impl Default for Preferences {
@@ -29,13 +111,41 @@ impl Default for Preferences {
             move_edit_cursor: false,
             quick_edit: false,
             sticky_edit: false,
+            edit_step: 1,
+            edit_step_next_row: false,
             auto_save: false,
+            auto_save_interval_ms: 500,
             keep_meta: false,
             col_change_lock_col: false,
             col_change_lock_row: true,
             bg_color: [0.0; 3],
             auto_reload: false,
             auto_reload_interval_ms: 250,
+            copy_offset_base: OffsetBase::Hex,
+            copy_offset_sep: OffsetSeparator::Newline,
+            hex_dump_group: 1,
+            mark_dirty_bytes: false,
+            smooth_scroll: false,
+            skip_value: 0,
+            show_cursor_value: true,
+            highlight_same_byte: false,
+            keep_cursor_centered: false,
+            show_top_panel: true,
+            show_bottom_panel: true,
+            show_inspect_panel: true,
         }
     }
 }
+
+#[test]
+fn test_offset_list() {
+    assert_eq!(
+        offset_list(&[16, 255], OffsetBase::Hex, OffsetSeparator::Comma),
+        "0x10, 0xFF"
+    );
+    assert_eq!(
+        offset_list(&[16, 255], OffsetBase::Dec, OffsetSeparator::Newline),
+        "16\n255"
+    );
+    assert_eq!(offset_list(&[], OffsetBase::Dec, OffsetSeparator::Comma), "");
+}
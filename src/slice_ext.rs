@@ -1,13 +1,52 @@
-pub trait SliceExt {
+pub trait SliceExt<T> {
     fn pattern_fill(&mut self, pattern: &Self);
+    /// Index of the nearest element after (or before) `from` that isn't `value`
+    fn find_other_than(&self, from: usize, value: T, forward: bool) -> Option<usize>;
+    /// Index of the nearest element after (or before) `from` that differs from `other`.
+    ///
+    /// Elements past the end of `other` always count as different.
+    fn find_difference(&self, other: &Self, from: usize, forward: bool) -> Option<usize>;
 }
 
-impl<T: Copy> SliceExt for [T] {
+impl<T: Copy + PartialEq> SliceExt<T> for [T] {
     fn pattern_fill(&mut self, pattern: &Self) {
         for (src, dst) in pattern.iter().cycle().zip(self.iter_mut()) {
             *dst = *src;
         }
     }
+    fn find_other_than(&self, from: usize, value: T, forward: bool) -> Option<usize> {
+        if forward {
+            let start = from.checked_add(1)?;
+            let rest = self.get(start..)?;
+            rest.iter().position(|&v| v != value).map(|pos| start + pos)
+        } else {
+            let before = self.get(..from.min(self.len()))?;
+            before.iter().rposition(|&v| v != value)
+        }
+    }
+    fn find_difference(&self, other: &Self, from: usize, forward: bool) -> Option<usize> {
+        let common = self.len().min(other.len());
+        if forward {
+            let start = from.checked_add(1)?;
+            if start >= self.len() {
+                return None;
+            }
+            if start >= common {
+                return Some(start);
+            }
+            let (a, b) = (&self[start..common], &other[start..common]);
+            match a.iter().zip(b).position(|(a, b)| a != b) {
+                Some(pos) => Some(start + pos),
+                None => (common < self.len()).then_some(common),
+            }
+        } else {
+            let end = from.min(self.len());
+            if end > common {
+                return Some(end - 1);
+            }
+            self[..end].iter().zip(&other[..end]).rposition(|(a, b)| a != b)
+        }
+    }
 }
 
 #[test]
@@ -18,3 +57,28 @@ fn test_pattern_fill() {
     buf.pattern_fill(b"Hello, World!");
     assert_eq!(&buf, b"Hello, Wor");
 }
+
+#[test]
+fn test_find_other_than() {
+    let buf = [1u8, 0, 0, 2, 0, 0];
+    assert_eq!(buf.find_other_than(0, 0, true), Some(3));
+    assert_eq!(buf.find_other_than(3, 0, true), None);
+    assert_eq!(buf.find_other_than(5, 0, false), Some(3));
+    assert_eq!(buf.find_other_than(3, 0, false), Some(0));
+    assert_eq!(buf.find_other_than(0, 0, false), None);
+    assert_eq!(buf.find_other_than(10, 0, true), None);
+}
+
+#[test]
+fn test_find_difference() {
+    let buf = [1u8, 2, 3, 4, 5, 6];
+    let reference = [1u8, 9, 3, 4];
+    assert_eq!(buf.find_difference(&reference, 0, true), Some(1));
+    assert_eq!(buf.find_difference(&reference, 1, true), Some(4));
+    assert_eq!(buf.find_difference(&reference, 4, true), Some(5));
+    assert_eq!(buf.find_difference(&reference, 5, true), None);
+    assert_eq!(buf.find_difference(&reference, 5, false), Some(4));
+    assert_eq!(buf.find_difference(&reference, 4, false), Some(1));
+    assert_eq!(buf.find_difference(&reference, 1, false), None);
+    assert_eq!(buf[..4].find_difference(&reference[..], 1, true), None);
+}
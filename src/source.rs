@@ -1,6 +1,6 @@
 use std::{
     fs::File,
-    io::{Read, Stdin},
+    io::{ErrorKind, Read, Stdin},
 };
 
 #[derive(Debug)]
@@ -66,6 +66,26 @@ impl Clone for SourceProvider {
     }
 }
 
+impl SourceProvider {
+    /// Make sure everything written to the source has actually reached the underlying storage
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            SourceProvider::File(f) => match f.sync_data() {
+                // Special files like `/proc/<pid>/mem` can't be synced, but are written directly
+                Err(e) if matches!(e.kind(), ErrorKind::InvalidInput | ErrorKind::Unsupported) => {
+                    Ok(())
+                }
+                result => result,
+            },
+            // Nothing is ever written to stdin
+            SourceProvider::Stdin(_) => Ok(()),
+            // Process memory is written directly, there is nothing to flush
+            #[cfg(windows)]
+            SourceProvider::WinProc { .. } => Ok(()),
+        }
+    }
+}
+
 impl Read for SourceProvider {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         match self {
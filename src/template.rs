@@ -0,0 +1,100 @@
+//! Templates of expected byte values, for checking data against a spec
+//!
+//! A template is a text file with lines like `0x10: 4D 5A ?? 00`, which means the bytes starting
+//! at offset 0x10 are expected to be 4D, 5A, anything, 00. `#` starts a comment.
+
+use {
+    crate::parse_radix::parse_guess_radix,
+    anyhow::{anyhow, bail},
+    std::{collections::BTreeMap, path::PathBuf},
+};
+
+pub struct Template {
+    pub path: PathBuf,
+    /// Expected values by offset. Wildcards are left out.
+    expected: BTreeMap<usize, u8>,
+}
+
+/// A byte that doesn't have the value the template expects
+pub struct Violation {
+    pub offset: usize,
+    pub expected: u8,
+    /// `None` if the data is too short to contain the offset
+    pub actual: Option<u8>,
+}
+
+impl Template {
+    pub fn load(path: PathBuf) -> anyhow::Result<Self> {
+        let text = std::fs::read_to_string(&path)?;
+        Ok(Self {
+            expected: parse(&text)?,
+            path,
+        })
+    }
+    /// Number of bytes the template checks
+    pub fn checked_count(&self) -> usize {
+        self.expected.len()
+    }
+    /// Whether the byte at `offset` deviates from the template
+    pub fn violated_at(&self, offset: usize, data: &[u8]) -> bool {
+        self.expected
+            .get(&offset)
+            .is_some_and(|&expected| data.get(offset) != Some(&expected))
+    }
+    /// All the bytes of `data` that deviate from the template, in order of offset
+    pub fn violations<'a>(&'a self, data: &'a [u8]) -> impl Iterator<Item = Violation> + 'a {
+        self.expected.iter().filter_map(|(&offset, &expected)| {
+            let actual = data.get(offset).copied();
+            (actual != Some(expected)).then_some(Violation {
+                offset,
+                expected,
+                actual,
+            })
+        })
+    }
+}
+
+fn parse(text: &str) -> anyhow::Result<BTreeMap<usize, u8>> {
+    let mut expected = BTreeMap::new();
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line.split_once('#').map_or(line, |(before, _)| before).trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((offset, bytes)) = line.split_once(':') else {
+            bail!("Line {}: expected `offset: bytes`", line_no);
+        };
+        let offset: usize = parse_guess_radix(offset.trim())
+            .map_err(|e| anyhow!("Line {}: invalid offset: {}", line_no, e))?;
+        for (j, token) in bytes.split_whitespace().enumerate() {
+            if token == "??" {
+                continue;
+            }
+            let byte = u8::from_str_radix(token, 16)
+                .map_err(|e| anyhow!("Line {}: invalid byte `{}`: {}", line_no, token, e))?;
+            expected.insert(offset + j, byte);
+        }
+    }
+    Ok(expected)
+}
+
+#[test]
+fn test_template() {
+    let template = Template {
+        path: PathBuf::new(),
+        expected: parse("# header\n0x1: 4D ?? 00 # magic\n\n6: ff").unwrap(),
+    };
+    assert_eq!(template.checked_count(), 3);
+    let data = [0, 0x4D, 0x12, 0x01, 0, 0];
+    assert!(!template.violated_at(1, &data));
+    assert!(!template.violated_at(2, &data));
+    assert!(template.violated_at(3, &data));
+    let violations: Vec<_> = template
+        .violations(&data)
+        .map(|v| (v.offset, v.expected, v.actual))
+        .collect();
+    assert_eq!(violations, [(3, 0, Some(1)), (6, 0xff, None)]);
+    assert!(parse("12 34").is_err());
+    assert!(parse("0: 4G").is_err());
+}
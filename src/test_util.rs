@@ -0,0 +1,55 @@
+//! Helpers shared by tests
+
+use {
+    crate::{
+        meta::{
+            perspective::Perspective, region::Region, NamedRegion, PerspectiveKey, PerspectiveMap,
+            RegionMap,
+        },
+        view::{View, ViewKind},
+    },
+    serde::{de::DeserializeOwned, Serialize},
+};
+
+/// A region, a perspective of it, and a block view of that perspective
+pub struct Fixture {
+    pub regions: RegionMap,
+    pub perspectives: PerspectiveMap,
+    pub per: PerspectiveKey,
+    pub view: View,
+}
+
+impl Fixture {
+    /// A view of `region`, `cols` columns wide
+    pub fn new(region: Region, cols: usize) -> Self {
+        let mut regions = RegionMap::default();
+        let key = regions.insert(NamedRegion {
+            name: "test".into(),
+            region,
+            desc: String::new(),
+            tint: None,
+        });
+        let mut perspectives = PerspectiveMap::default();
+        let per = perspectives.insert(Perspective {
+            cols,
+            ..Perspective::from_region(key, "test".into())
+        });
+        Self {
+            regions,
+            perspectives,
+            per,
+            view: View::new(ViewKind::Block, per),
+        }
+    }
+    pub fn perspective(&self) -> &Perspective {
+        &self.perspectives[self.per]
+    }
+}
+
+/// Save `value` the way metafiles and the config are saved, and load it back as a `T`.
+///
+/// Used to check that what an older version saved still loads.
+pub fn reload_as<T: DeserializeOwned>(value: &impl Serialize) -> T {
+    let bytes = rmp_serde::to_vec(value).expect("Failed to encode");
+    rmp_serde::from_slice(&bytes).expect("Failed to decode")
+}
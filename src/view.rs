@@ -16,6 +16,11 @@ use {
 
 mod draw;
 
+/// Rows scrolled per wheel tick, when the view has no scroll speed set
+const DEFAULT_SCROLL_ROWS: u16 = 3;
+/// Smooth scrolling covers this fraction (1/n) of the remaining distance every frame
+const SMOOTH_SCROLL_DIVISOR: i16 = 4;
+
 /// A rectangular view in the viewport looking through a perspective at the data with a flavor
 /// of rendering/interaction (hex/ascii/block/etc.)
 ///
@@ -36,14 +41,28 @@ pub struct View {
     /// The scrolling offset
     #[serde(skip)]
     pub scroll_offset: ScrollOffset,
-    /// The amount scrolled for a single scroll operation, in pixels
+    /// The amount scrolled for a single scroll operation, in pixels.
+    ///
+    /// 0 means a few rows.
     pub scroll_speed: i16,
+    /// Pixels left to scroll vertically when smooth scrolling
+    #[serde(skip)]
+    pub smooth_scroll_remaining: i16,
     /// How many bytes are required for a single block in the view
     pub bytes_per_block: u8,
     /// The perspective this view is associated with
     pub perspective: PerspectiveKey,
     /// Color schemes, etc.
     pub presentation: Presentation,
+    /// Number of bytes this view starts after the beginning of its perspective's region.
+    ///
+    /// Useful for lining up views of interleaved structures that are a header apart.
+    #[serde(default)]
+    pub data_offset: usize,
+    /// Whether this view follows (and drives) the scrolling of other views
+    /// through the same perspective
+    #[serde(default = "default_sync_scroll")]
+    pub sync_scroll: bool,
 }
 
 impl PartialEq for View {
@@ -52,13 +71,20 @@ impl PartialEq for View {
             && self.col_w == other.col_w
             && self.row_h == other.row_h
             && self.scroll_speed == other.scroll_speed
+            && self.sync_scroll == other.sync_scroll
             && self.bytes_per_block == other.bytes_per_block
+            && self.perspective == other.perspective
             && self.presentation == other.presentation
+            && self.data_offset == other.data_offset
     }
 }
 
 impl Eq for View {}
 
+const fn default_sync_scroll() -> bool {
+    true
+}
+
 impl View {
     pub fn new(kind: ViewKind, perspective: PerspectiveKey) -> Self {
         let mut this = Self {
@@ -70,9 +96,12 @@ impl View {
             row_h: 4,
             scroll_offset: ScrollOffset::default(),
             scroll_speed: 0,
+            smooth_scroll_remaining: 0,
             bytes_per_block: 1,
             perspective,
             presentation: Presentation::default(),
+            data_offset: 0,
+            sync_scroll: true,
         };
         this.adjust_state_to_kind();
         this
@@ -102,6 +131,45 @@ impl View {
         )
     }
 
+    /// Scroll vertically for a mouse wheel movement of `delta` ticks.
+    ///
+    /// If `smooth` is true, the scrolling is spread out over the next few
+    /// [`Self::update_smooth_scroll`] calls.
+    pub(crate) fn wheel_scroll(&mut self, delta: f32, smooth: bool) {
+        let speed = if self.scroll_speed == 0 {
+            i16::try_from(self.row_h.saturating_mul(DEFAULT_SCROLL_ROWS)).unwrap_or(i16::MAX)
+        } else {
+            self.scroll_speed
+        };
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "Wheel deltas are a few ticks at most"
+        )]
+        let ticks = delta.round() as i16;
+        // Wheel up (positive delta) scrolls towards the beginning
+        let amount = ticks.saturating_mul(speed).saturating_neg();
+        if smooth {
+            self.smooth_scroll_remaining = self.smooth_scroll_remaining.saturating_add(amount);
+        } else {
+            self.scroll_y(amount);
+        }
+    }
+    /// Advance smooth scrolling by one frame.
+    ///
+    /// Scrolls a fraction of the remaining amount, so the total always adds up exactly.
+    pub(crate) fn update_smooth_scroll(&mut self) {
+        let remaining = self.smooth_scroll_remaining;
+        if remaining == 0 {
+            return;
+        }
+        let step = match remaining / SMOOTH_SCROLL_DIVISOR {
+            0 => remaining.signum(),
+            step => step,
+        };
+        self.smooth_scroll_remaining -= step;
+        self.scroll_y(step);
+    }
+
     pub(crate) fn sync_to(
         &mut self,
         src_row: usize,
@@ -199,14 +267,15 @@ impl View {
         }
         let row = self.scroll_offset.row;
         let col = self.scroll_offset.col;
+        // Not `is_positive`, that would make the first pixel row/column of the view miss
         #[expect(
             clippy::cast_sign_loss,
-            reason = "rel_x and rel_y being positive also ensure rel_row and rel_col are"
+            reason = "rel_x and rel_y being non-negative also ensure rel_row and rel_col are"
         )]
-        if rel_x.is_positive() && rel_y.is_positive() {
+        if rel_x >= 0 && rel_y >= 0 {
             let abs_row = row + rel_row as usize;
             let abs_col = col + rel_col as usize;
-            if perspective.row_col_within_bound(abs_row, abs_col, regions) {
+            if perspective.row_col_within_bound(abs_row, abs_col, self.data_offset, regions) {
                 Some((abs_row, abs_col))
             } else {
                 None
@@ -222,10 +291,43 @@ impl View {
         perspectives: &PerspectiveMap,
         regions: &RegionMap,
     ) {
-        let (row, col) = perspectives[self.perspective].row_col_of_byte_offset(offset, regions);
+        let (row, col) = self.row_col_of_byte_offset(offset, perspectives, regions);
         self.center_on_row_col(row, col);
     }
 
+    /// The bit under viewport position `x`/`y`, for bit views
+    pub(crate) fn bit_at_pos(&self, x: i16, y: i16) -> Option<u8> {
+        let ViewKind::Bits(bits) = &self.kind else { return None };
+        let (rel_x, _) = self.viewport_rect.relative_offset_of_pos(x, y)?;
+        let col_w = i16::try_from(self.col_w).ok()?;
+        let in_block = (rel_x + self.scroll_offset.pix_xoff).rem_euclid(col_w);
+        let glyph_w = i16::try_from(bits.font_size.saturating_sub(4)).ok()?.max(1);
+        let pos = u8::try_from(in_block / glyph_w).ok()?.min(7);
+        Some(bits.bit_at(pos))
+    }
+
+    /// Byte offset displayed at `row` and `col`, taking [`Self::data_offset`] into account
+    pub(crate) fn byte_offset_of_row_col(
+        &self,
+        row: usize,
+        col: usize,
+        perspectives: &PerspectiveMap,
+        regions: &RegionMap,
+    ) -> usize {
+        perspectives[self.perspective].byte_offset_of_row_col(row, col, regions) + self.data_offset
+    }
+
+    /// Row and column `offset` is displayed at, taking [`Self::data_offset`] into account
+    pub(crate) fn row_col_of_byte_offset(
+        &self,
+        offset: usize,
+        perspectives: &PerspectiveMap,
+        regions: &RegionMap,
+    ) -> (usize, usize) {
+        perspectives[self.perspective]
+            .row_col_of_byte_offset(offset.saturating_sub(self.data_offset), regions)
+    }
+
     fn center_on_row_col(&mut self, row: usize, col: usize) {
         self.scroll_offset.row = row;
         self.scroll_offset.col = col;
@@ -240,7 +342,7 @@ impl View {
         Offsets {
             row,
             col,
-            byte: perspectives[self.perspective].byte_offset_of_row_col(row, col, regions),
+            byte: self.byte_offset_of_row_col(row, col, perspectives, regions),
         }
     }
     /// Scroll to byte offset, with control of each axis individually
@@ -252,7 +354,7 @@ impl View {
         do_col: bool,
         do_row: bool,
     ) {
-        let (row, col) = perspectives[self.perspective].row_col_of_byte_offset(offset, regions);
+        let (row, col) = self.row_col_of_byte_offset(offset, perspectives, regions);
         if do_row {
             self.scroll_offset.row = row;
         }
@@ -294,12 +396,40 @@ impl View {
 
     pub fn adjust_block_size(&mut self) {
         (self.col_w, self.row_h) = match &self.kind {
+            // Inline ascii adds one more glyph (plus a small gap) after the digits
+            ViewKind::Hex(hex) if hex.inline_ascii => (hex.font_size * 3 - 2, hex.font_size),
             ViewKind::Hex(hex) => (hex.font_size * 2 - 2, hex.font_size),
             ViewKind::Dec(dec) => (dec.font_size * 3 - 6, dec.font_size),
             ViewKind::Text(data) => (data.font_size, data.line_spacing.max(1)),
             ViewKind::Block => (self.col_w, self.row_h),
+            ViewKind::Bits(bits) => (bits.font_size * 8 - 26, bits.font_size),
         }
     }
+    /// Change the font size by `delta` (clamped to [`ZOOM_FONT_SIZES`]), and adjust to it
+    pub(crate) fn zoom(&mut self, delta: i16, font: &Font) {
+        match &mut self.kind {
+            ViewKind::Hex(HexData { font_size, .. })
+            | ViewKind::Dec(HexData { font_size, .. })
+            | ViewKind::Text(TextData { font_size, .. })
+            | ViewKind::Bits(BitData { font_size, .. }) => {
+                let new = (i32::from(*font_size) + i32::from(delta)).clamp(
+                    i32::from(*ZOOM_FONT_SIZES.start()),
+                    i32::from(*ZOOM_FONT_SIZES.end()),
+                );
+                *font_size = u16::try_from(new).unwrap_or(*font_size);
+            }
+            ViewKind::Block => return,
+        }
+        #[expect(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "It's extremely unlikely line spacing is not between 0 and i16::MAX"
+        )]
+        if let ViewKind::Text(data) = &mut self.kind {
+            data.line_spacing = font.line_spacing(u32::from(data.font_size)) as u16;
+        }
+        self.adjust_state_to_kind();
+    }
     /// Adjust state after kind was changed
     pub fn adjust_state_to_kind(&mut self) {
         self.adjust_block_size();
@@ -318,6 +448,7 @@ impl View {
             ViewKind::Dec(_) => 3,
             ViewKind::Text { .. } => 1,
             ViewKind::Block => 1,
+            ViewKind::Bits(_) => 8,
         }
     }
     pub fn handle_text_entered(
@@ -326,38 +457,51 @@ impl View {
         edit_state: &mut EditState,
         preferences: &Preferences,
         data: &mut [u8],
+        perspectives: &PerspectiveMap,
     ) {
         if self.char_valid(unicode) {
+            let lowercase_hex = self.presentation.lowercase_hex;
             match &mut self.kind {
                 ViewKind::Hex(hex) => {
                     if !hex.edit_buf.dirty {
-                        let s = format!("{:02X}", data[edit_state.cursor]);
+                        let idx = swapped_idx(edit_state.cursor, hex.byte_swap_group, data.len());
+                        let s = if lowercase_hex {
+                            format!("{:02x}", data[idx])
+                        } else {
+                            format!("{:02X}", data[idx])
+                        };
                         hex.edit_buf.update_from_string(&s);
                     }
-                    if hex.edit_buf.enter_byte(unicode.to_ascii_uppercase() as u8)
+                    let digit = if lowercase_hex {
+                        unicode.to_ascii_lowercase()
+                    } else {
+                        unicode.to_ascii_uppercase()
+                    };
+                    if hex.edit_buf.enter_byte(digit as u8)
                         || preferences.quick_edit
                     {
-                        self.finish_editing(edit_state, data, preferences);
+                        self.finish_editing(edit_state, data, preferences, perspectives);
                     }
                 }
                 ViewKind::Dec(dec) => {
                     if !dec.edit_buf.dirty {
-                        let s = format!("{:03}", data[edit_state.cursor]);
+                        let idx = swapped_idx(edit_state.cursor, dec.byte_swap_group, data.len());
+                        let s = format!("{:03}", data[idx]);
                         dec.edit_buf.update_from_string(&s);
                     }
                     if dec.edit_buf.enter_byte(unicode.to_ascii_uppercase() as u8)
                         || preferences.quick_edit
                     {
-                        self.finish_editing(edit_state, data, preferences);
+                        self.finish_editing(edit_state, data, preferences, perspectives);
                     }
                 }
                 ViewKind::Text(text) => {
                     if text.edit_buf.enter_byte(unicode as u8) || preferences.quick_edit {
-                        self.finish_editing(edit_state, data, preferences);
+                        self.finish_editing(edit_state, data, preferences, perspectives);
                     }
                 }
-                // Block doesn't do any text input
-                ViewKind::Block => {}
+                // Block and bit views don't do any text input
+                ViewKind::Block | ViewKind::Bits(_) => {}
             }
         }
     }
@@ -384,7 +528,7 @@ impl View {
             ViewKind::Hex(_) => matches!(unicode, '0'..='9' | 'a'..='f'),
             ViewKind::Dec(_) => matches!(unicode, '0'..='9'),
             ViewKind::Text { .. } => unicode.is_ascii(),
-            ViewKind::Block => false,
+            ViewKind::Block | ViewKind::Bits(_) => false,
         }
     }
 
@@ -393,38 +537,62 @@ impl View {
         edit_state: &mut EditState,
         data: &mut [u8],
         preferences: &Preferences,
+        perspectives: &PerspectiveMap,
     ) {
         match &mut self.kind {
             ViewKind::Hex(hex) => {
+                let idx = swapped_idx(edit_state.cursor, hex.byte_swap_group, data.len());
                 match merge_hex_halves(hex.edit_buf.buf[0], hex.edit_buf.buf[1]) {
-                    Some(merged) => data[edit_state.cursor] = merged,
+                    Some(merged) => {
+                        edit_state.log_edit(idx, &[data[idx]], &[merged]);
+                        data[idx] = merged;
+                    }
                     None => per_msg!("finish_editing: Failed to merge hex halves"),
                 }
-                edit_state.widen_dirty_region(DamageRegion::Single(edit_state.cursor));
+                edit_state.widen_dirty_region(DamageRegion::Single(idx));
             }
             ViewKind::Dec(dec) => {
                 let s =
                     std::str::from_utf8(&dec.edit_buf.buf).expect("Invalid utf-8 in edit buffer");
                 match s.parse() {
                     Ok(num) => {
-                        data[edit_state.cursor] = num;
-                        edit_state.widen_dirty_region(DamageRegion::Single(edit_state.cursor));
+                        let idx = swapped_idx(edit_state.cursor, dec.byte_swap_group, data.len());
+                        edit_state.log_edit(idx, &[data[idx]], &[num]);
+                        data[idx] = num;
+                        edit_state.widen_dirty_region(DamageRegion::Single(idx));
                     }
                     Err(e) => msg_warn(&format!("Invalid value: {}", e)),
                 }
             }
             ViewKind::Text(text) => {
-                data[edit_state.cursor] = text.edit_buf.buf[0];
-                edit_state.widen_dirty_region(DamageRegion::Single(edit_state.cursor));
+                let idx = edit_state.cursor;
+                edit_state.log_edit(idx, &[data[idx]], &[text.edit_buf.buf[0]]);
+                data[idx] = text.edit_buf.buf[0];
+                edit_state.widen_dirty_region(DamageRegion::Single(idx));
             }
-            ViewKind::Block => {}
+            ViewKind::Block | ViewKind::Bits(_) => {}
         }
-        if edit_state.cursor + 1 < data.len() && !preferences.sticky_edit {
-            edit_state.step_cursor_forward()
+        let step = if preferences.edit_step_next_row {
+            perspectives.get(self.perspective).map_or(1, |per| per.cols)
+        } else {
+            preferences.edit_step.max(1)
+        };
+        if edit_state.cursor + step < data.len() && !preferences.sticky_edit {
+            edit_state.offset_cursor(step);
         }
         self.reset_edit_buf();
     }
 
+    /// The offset of the byte actually displayed at `idx`, taking byte swapping into account
+    pub fn physical_idx(&self, idx: usize, data_len: usize) -> usize {
+        match &self.kind {
+            ViewKind::Hex(hex) | ViewKind::Dec(hex) => {
+                swapped_idx(idx, hex.byte_swap_group, data_len)
+            }
+            ViewKind::Text(_) | ViewKind::Block | ViewKind::Bits(_) => idx,
+        }
+    }
+
     pub fn cancel_editing(&mut self) {
         self.reset_edit_buf();
     }
@@ -445,7 +613,7 @@ impl View {
         match &mut self.kind {
             ViewKind::Hex(data) | ViewKind::Dec(data) => Some(&mut data.edit_buf),
             ViewKind::Text(data) => Some(&mut data.edit_buf),
-            ViewKind::Block => None,
+            ViewKind::Block | ViewKind::Bits(_) => None,
         }
     }
 
@@ -543,6 +711,53 @@ fn test_scroll_impl_negative() {
     assert_eq!((whole, pixel), (0, -320));
 }
 
+#[test]
+fn test_smooth_scroll_ends_exactly() {
+    let mut view = View::new(ViewKind::Block, PerspectiveKey::null());
+    view.row_h = 10;
+    view.scroll_speed = 25;
+    let mut snapped = view.clone();
+    view.wheel_scroll(-3.0, true);
+    snapped.wheel_scroll(-3.0, false);
+    for _ in 0..100 {
+        view.update_smooth_scroll();
+    }
+    assert_eq!(view.smooth_scroll_remaining, 0);
+    assert_eq!(view.scroll_offset.row, snapped.scroll_offset.row);
+    assert_eq!(view.scroll_offset.pix_yoff, snapped.scroll_offset.pix_yoff);
+    assert_eq!((view.scroll_offset.row, view.scroll_offset.pix_yoff), (7, 5));
+}
+
+#[test]
+fn test_decode_old_layout() {
+    // Metafiles store structs as arrays, so fields can only be added at the end.
+    // This is how views were laid out before `data_offset` and `sync_scroll`.
+    #[derive(Serialize)]
+    struct OldView {
+        kind: ViewKind,
+        col_w: u16,
+        row_h: u16,
+        scroll_speed: i16,
+        bytes_per_block: u8,
+        perspective: PerspectiveKey,
+        presentation: Presentation,
+    }
+    let old = OldView {
+        kind: ViewKind::Block,
+        col_w: 4,
+        row_h: 6,
+        scroll_speed: 3,
+        bytes_per_block: 2,
+        perspective: PerspectiveKey::null(),
+        presentation: Presentation::default(),
+    };
+    let view: View = crate::test_util::reload_as(&old);
+    assert_eq!((view.col_w, view.row_h, view.scroll_speed), (4, 6, 3));
+    assert_eq!(view.bytes_per_block, 2);
+    assert_eq!(view.data_offset, 0);
+    assert!(view.sync_scroll);
+}
+
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct ScrollOffset {
     /// What column we are at
@@ -581,6 +796,9 @@ impl ScrollOffset {
 /// or get mouse positions higher than that.
 pub type ViewportScalar = i16;
 
+/// Font sizes that can be reached by zooming with ctrl+mouse wheel
+const ZOOM_FONT_SIZES: std::ops::RangeInclusive<u16> = 6..=48;
+
 #[derive(Debug, Default, Clone, Copy)]
 pub struct ViewportRect {
     pub x: ViewportScalar,
@@ -624,6 +842,35 @@ pub enum ViewKind {
     Dec(HexData),
     Text(TextData),
     Block,
+    /// Each byte as 8 individually clickable bits
+    Bits(BitData),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BitData {
+    pub font_size: u16,
+    /// Show the least significant bit first, instead of the most significant one
+    pub lsb_first: bool,
+}
+
+impl Default for BitData {
+    fn default() -> Self {
+        Self {
+            font_size: 14,
+            lsb_first: false,
+        }
+    }
+}
+
+impl BitData {
+    /// The bit number (0 = least significant) shown at glyph position `pos` of a block
+    pub fn bit_at(&self, pos: u8) -> u8 {
+        if self.lsb_first {
+            pos
+        } else {
+            7 - pos
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -651,11 +898,21 @@ pub struct HexData {
     #[serde(skip)]
     pub edit_buf: EditBuffer,
     pub font_size: u16,
+    /// Display the bytes of each group of this many bytes in reverse order (0 or 1 is off).
+    ///
+    /// This is display-only, editing still affects the byte that is displayed.
+    #[serde(default)]
+    pub byte_swap_group: u8,
+    /// Show the printable character of each byte after its digits (hex views only)
+    #[serde(default)]
+    pub inline_ascii: bool,
 }
 
 impl PartialEq for HexData {
     fn eq(&self, other: &Self) -> bool {
         self.font_size == other.font_size
+            && self.byte_swap_group == other.byte_swap_group
+            && self.inline_ascii == other.inline_ascii
     }
 }
 
@@ -666,10 +923,38 @@ impl Default for HexData {
         Self {
             edit_buf: Default::default(),
             font_size: 14,
+            byte_swap_group: 0,
+            inline_ascii: false,
         }
     }
 }
 
+/// Mirror `idx` within its (absolutely aligned) group of `group` bytes.
+///
+/// Incomplete groups at the end of the data are left as is.
+fn swapped_idx(idx: usize, group: u8, data_len: usize) -> usize {
+    let group = usize::from(group);
+    if group < 2 {
+        return idx;
+    }
+    let group_start = idx - idx % group;
+    if group_start + group > data_len {
+        return idx;
+    }
+    group_start + (group - 1 - idx % group)
+}
+
+#[test]
+fn test_swapped_idx() {
+    assert_eq!(swapped_idx(5, 0, 10), 5);
+    assert_eq!(swapped_idx(0, 2, 10), 1);
+    assert_eq!(swapped_idx(1, 2, 10), 0);
+    assert_eq!(swapped_idx(5, 4, 10), 6);
+    assert_eq!(swapped_idx(7, 4, 10), 4);
+    // Incomplete trailing group
+    assert_eq!(swapped_idx(9, 4, 10), 9);
+}
+
 impl TextData {
     #[expect(
         clippy::cast_possible_truncation,
@@ -709,6 +994,124 @@ impl TextKind {
             TextKind::Utf16Be => 2,
         }
     }
+
+    /// Decode a whole range of bytes as this kind of text.
+    ///
+    /// Undecodable bytes (and a trailing incomplete code unit) become U+FFFD.
+    pub(crate) fn decode(&self, bytes: &[u8]) -> String {
+        match self {
+            TextKind::Ascii => bytes
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii() {
+                        char::from(b)
+                    } else {
+                        char::REPLACEMENT_CHARACTER
+                    }
+                })
+                .collect(),
+            TextKind::Utf16Le | TextKind::Utf16Be => {
+                let chunks = bytes.chunks_exact(2);
+                let trailing = !chunks.remainder().is_empty();
+                let units = chunks.map(|pair| {
+                    let pair = [pair[0], pair[1]];
+                    if *self == TextKind::Utf16Le {
+                        u16::from_le_bytes(pair)
+                    } else {
+                        u16::from_be_bytes(pair)
+                    }
+                });
+                let mut s: String = char::decode_utf16(units)
+                    .map(|r| r.unwrap_or(char::REPLACEMENT_CHARACTER))
+                    .collect();
+                if trailing {
+                    s.push(char::REPLACEMENT_CHARACTER);
+                }
+                s
+            }
+        }
+    }
+}
+
+#[test]
+fn test_text_kind_decode() {
+    assert_eq!(TextKind::Ascii.decode(b"hi\xFF"), "hi\u{FFFD}");
+    assert_eq!(TextKind::Utf16Le.decode(b"h\0i\0"), "hi");
+    assert_eq!(TextKind::Utf16Be.decode(b"\0h\0i\0"), "hi\u{FFFD}");
+    // Unpaired surrogate
+    assert_eq!(TextKind::Utf16Le.decode(&[0x00, 0xD8]), "\u{FFFD}");
+}
+
+#[test]
+fn test_data_offset() {
+    let mut fx = crate::test_util::Fixture::new(Region { begin: 0, end: 99 }, 10);
+    fx.view.data_offset = 3;
+    let (view, pers, regions) = (&fx.view, &fx.perspectives, &fx.regions);
+    assert_eq!(view.byte_offset_of_row_col(0, 0, pers, regions), 3);
+    assert_eq!(view.row_col_of_byte_offset(25, pers, regions), (2, 2));
+    assert_eq!(view.byte_offset_of_row_col(2, 2, pers, regions), 25);
+    // The last 3 bytes of the last row are past the end of the region
+    assert!(fx.perspective().row_col_within_bound(9, 6, view.data_offset, regions));
+    assert!(!fx.perspective().row_col_within_bound(9, 7, view.data_offset, regions));
+}
+
+#[test]
+fn test_offset_of_pos_independent_of_block_size() {
+    let fx = crate::test_util::Fixture::new(Region { begin: 0, end: 999 }, 16);
+    let (perspectives, regions) = (&fx.perspectives, &fx.regions);
+    // Block view sized, hex view sized, and single pixel blocks
+    for (col_w, row_h) in [(4i16, 4i16), (26, 16), (1, 1)] {
+        let mut view = fx.view.clone();
+        view.col_w = col_w.unsigned_abs();
+        view.row_h = row_h.unsigned_abs();
+        view.viewport_rect = ViewportRect {
+            x: 10,
+            y: 20,
+            w: 16 * col_w,
+            h: 8 * row_h,
+        };
+        view.scroll_offset.row = 2;
+        let offset_at = |x, y| {
+            let (row, col) = view.row_col_offset_of_pos(x, y, perspectives, regions)?;
+            Some(view.byte_offset_of_row_col(row, col, perspectives, regions))
+        };
+        // Offset 37 is at row 2, column 5: the 6th block of the first visible row.
+        // Both its first and its last pixel have to map to it.
+        let (x, y) = (10 + 5 * col_w, 20);
+        assert_eq!(offset_at(x, y), Some(37));
+        assert_eq!(offset_at(x + col_w - 1, y + row_h - 1), Some(37));
+        assert_eq!(offset_at(10, 20), Some(32));
+        // The pixels past the right and bottom edges belong to the next view
+        assert_eq!(offset_at(10 + 16 * col_w, 20), None);
+        assert_eq!(offset_at(10, 20 + 8 * row_h), None);
+    }
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn test_center_on_offset_above_4gb() {
+    let offset = (1 << 32) + 12_345;
+    let region = Region {
+        begin: 0,
+        end: 1 << 33,
+    };
+    let mut fx = crate::test_util::Fixture::new(region, 48);
+    let view = &mut fx.view;
+    view.viewport_rect = ViewportRect {
+        x: 0,
+        y: 0,
+        w: 400,
+        h: 400,
+    };
+    view.center_on_offset(offset, &fx.perspectives, &fx.regions);
+    let per = &fx.perspectives[fx.per];
+    let (row, col) = per.row_col_of_byte_offset(offset, &fx.regions);
+    assert_eq!(per.byte_offset_of_row_col(row, col, &fx.regions), offset);
+    let top = view.offsets(&fx.perspectives, &fx.regions);
+    #[expect(clippy::cast_sign_loss, reason = "rows is positive for a non-empty viewport")]
+    let rows = view.rows() as usize;
+    assert!(top.row <= row && row < top.row + rows);
+    assert!(top.byte >= 1 << 32);
 }
 
 impl ViewportRect {
@@ -720,7 +1123,9 @@ impl ViewportRect {
         self.contains_pos(x, y).then_some((x - self.x, y - self.y))
     }
 
+    /// Whether `x`/`y` is inside the rect. The right and bottom edges belong to whatever
+    /// comes after, so adjacent views don't both claim the pixels on their border.
     pub fn contains_pos(&self, x: ViewportScalar, y: ViewportScalar) -> bool {
-        x >= self.x && y >= self.y && x <= self.x + self.w && y <= self.y + self.h
+        x >= self.x && y >= self.y && x < self.x + self.w && y < self.y + self.h
     }
 }
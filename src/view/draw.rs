@@ -6,7 +6,11 @@ use {
         dec_conv,
         gui::Gui,
         hex_conv,
-        meta::{region::Region, PerspectiveMap, RegionMap, ViewKey},
+        keybinds::KeyAction,
+        meta::{
+            perspective::Perspective, region::Region, region_tint_for_offset, PerspectiveMap,
+            RegionMap, ViewKey,
+        },
         view::ViewKind,
     },
     egui_sfml::sfml::{
@@ -19,8 +23,49 @@ use {
     either::Either,
     glu_sys::GLint,
     slotmap::Key,
+    std::fmt::Write,
 };
 
+/// Color of bytes that differ from the last loaded/saved data
+const MODIFIED_BYTE_COLOR: Color = Color::rgb(255, 128, 0);
+/// Color of bytes that differ from the reference file
+const REFERENCE_DIFF_COLOR: Color = Color::rgb(255, 64, 255);
+/// Color of bytes that differ between the two compared regions
+const REGION_DIFF_COLOR: Color = Color::rgb(64, 255, 255);
+/// Color of bytes that deviate from the loaded template
+const TEMPLATE_VIOLATION_COLOR: Color = Color::rgb(255, 48, 48);
+/// Background of bytes with the same value as the byte at the cursor
+const SAME_BYTE_COLOR: Color = Color::rgba(255, 255, 255, 36);
+
+/// The color the byte drawn at `idx` in `view` should have if it differs from what it's compared
+/// against
+fn diff_color(app: &App, view: &View, idx: usize) -> Option<Color> {
+    let idx = view.physical_idx(idx, app.data.len());
+    if app.edit_state.is_modified(idx, &app.data) {
+        Some(MODIFIED_BYTE_COLOR)
+    } else if app
+        .reference
+        .as_ref()
+        .is_some_and(|r| r.differs_at(idx, &app.data))
+    {
+        Some(REFERENCE_DIFF_COLOR)
+    } else if app
+        .region_comparison
+        .as_ref()
+        .is_some_and(|cmp| cmp.differs_at(idx, &app.data))
+    {
+        Some(REGION_DIFF_COLOR)
+    } else if app
+        .template
+        .as_ref()
+        .is_some_and(|t| t.violated_at(idx, &app.data))
+    {
+        Some(TEMPLATE_VIOLATION_COLOR)
+    } else {
+        None
+    }
+}
+
 pub fn draw_view(
     view: &View,
     app_perspectives: &PerspectiveMap,
@@ -35,7 +80,7 @@ pub fn draw_view(
     }
     let perspective = &app_perspectives[view.perspective];
     let region = &app_regions[perspective.region].region;
-    let mut idx = region.begin;
+    let mut idx = region.begin + view.data_offset;
     let start_row: usize = view.scroll_offset.row;
     idx += start_row * (perspective.cols * usize::from(view.bytes_per_block));
     #[expect(
@@ -74,7 +119,8 @@ pub fn draw_view(
             {
                 break 'rows;
             }
-            match app_data.get(idx..idx + view.bytes_per_block as usize) {
+            let phys_idx = view.physical_idx(idx, app_data.len());
+            match app_data.get(phys_idx..phys_idx + view.bytes_per_block as usize) {
                 Some(data) => {
                     let c = view
                         .presentation
@@ -255,6 +301,205 @@ fn draw_rect(vertices: &mut Vec<Vertex>, x: f32, y: f32, w: f32, h: f32, color:
     ]);
 }
 
+fn draw_region_tint(
+    vertices: &mut Vec<Vertex>,
+    x: f32,
+    y: f32,
+    view: &View,
+    regions: &RegionMap,
+    idx: usize,
+) {
+    if let Some([r, g, b, a]) = region_tint_for_offset(regions, idx) {
+        draw_rect(
+            vertices,
+            x,
+            y,
+            f32::from(view.col_w),
+            f32::from(view.row_h),
+            Color::rgba(r, g, b, a),
+        );
+    }
+}
+
+/// Subtly highlight `byte` if it has the same value as the byte at the cursor
+fn draw_same_byte_highlight(
+    vertices: &mut Vec<Vertex>,
+    x: f32,
+    y: f32,
+    view: &View,
+    byte: u8,
+    cursor_byte: Option<u8>,
+) {
+    if cursor_byte == Some(byte) {
+        draw_rect(
+            vertices,
+            x,
+            y,
+            f32::from(view.col_w),
+            f32::from(view.row_h),
+            SAME_BYTE_COLOR,
+        );
+    }
+}
+
+/// Draw a small marker in the top right corner of a commented byte
+fn draw_comment_marker(vertices: &mut Vec<Vertex>, x: f32, y: f32, view: &View) {
+    const SIZE: f32 = 3.0;
+    draw_rect(
+        vertices,
+        x + f32::from(view.col_w) - SIZE,
+        y,
+        SIZE,
+        SIZE,
+        Color::rgb(255, 220, 0),
+    );
+}
+
+/// Draw the guide lines of `perspective` in front of their columns
+fn draw_guides(vertices: &mut Vec<Vertex>, view: &View, perspective: &Perspective) {
+    let vr = &view.viewport_rect;
+    for &col in &perspective.guide_cols {
+        if col == 0 || col >= perspective.cols {
+            continue;
+        }
+        let Some(rel_cols) = col.checked_sub(view.scroll_offset.col) else { continue };
+        let Ok(rel_x) = i64::try_from(rel_cols * usize::from(view.col_w)) else { continue };
+        let x = i64::from(vr.x) + rel_x - i64::from(view.scroll_offset.pix_xoff);
+        if x < i64::from(vr.x) || x >= i64::from(vr.x) + i64::from(vr.w) {
+            continue;
+        }
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "x is within the viewport, which is small"
+        )]
+        let x = x as f32;
+        draw_rect(
+            vertices,
+            x,
+            f32::from(vr.y),
+            1.0,
+            f32::from(vr.h),
+            Color::rgba(90, 160, 255, 160),
+        );
+    }
+}
+
+/// Draw a faint separator above every row that starts a sector.
+///
+/// Returns the y positions of those rows along with their sector numbers, for labeling.
+/// Only supported with normal row order.
+fn draw_sector_separators(
+    vertices: &mut Vec<Vertex>,
+    view: &View,
+    perspective: &Perspective,
+    regions: &RegionMap,
+) -> Vec<(f32, usize)> {
+    let mut labels = Vec::new();
+    if perspective.sector_size == 0 || perspective.flip_row_order {
+        return labels;
+    }
+    let vr = &view.viewport_rect;
+    let start_row = view.scroll_offset.row;
+    #[expect(
+        clippy::cast_sign_loss,
+        reason = "rows() returning negative is a bug, should be positive."
+    )]
+    let view_rows = view.rows() as usize;
+    let end_row = (start_row + view_rows + 1).min(perspective.n_rows(regions));
+    for row in start_row..end_row {
+        let Some(sector) = perspective.sector_starting_in_row(row, regions) else { continue };
+        let Ok(rel_rows) = i64::try_from(row - start_row) else { continue };
+        let y = i64::from(vr.y) + rel_rows * i64::from(view.row_h)
+            - i64::from(view.scroll_offset.pix_yoff);
+        if y < i64::from(vr.y) || y >= i64::from(vr.y) + i64::from(vr.h) {
+            continue;
+        }
+        #[expect(
+            clippy::cast_precision_loss,
+            reason = "y is within the viewport, which is small"
+        )]
+        let y = y as f32;
+        draw_rect(
+            vertices,
+            f32::from(vr.x),
+            y,
+            f32::from(vr.w),
+            1.0,
+            Color::rgba(255, 255, 255, 60),
+        );
+        labels.push((y, sector));
+    }
+    labels
+}
+
+/// Draw a marker row right after the last row of data, so the end of the data
+/// can be told apart from rows that are just scrolled out of view
+fn draw_end_of_data_marker(
+    vertices: &mut Vec<Vertex>,
+    view: &View,
+    perspective: &Perspective,
+    regions: &RegionMap,
+) {
+    let vr = &view.viewport_rect;
+    let start_row = view.scroll_offset.row;
+    let n_rows = perspective.n_rows(regions);
+    #[expect(
+        clippy::cast_sign_loss,
+        reason = "rows() returning negative is a bug, should be positive."
+    )]
+    let view_rows = view.rows() as usize;
+    // With flipped row order, data rows are laid out from the bottom of the view upwards
+    let (marker_row, pix_yoff) = if perspective.flip_row_order {
+        let Some(rel) = n_rows.checked_sub(start_row) else { return };
+        let Some(row) = (start_row + view_rows).checked_sub(rel) else { return };
+        (row, -view.scroll_offset.pix_yoff)
+    } else {
+        (n_rows, view.scroll_offset.pix_yoff)
+    };
+    let Ok(marker_row) = i64::try_from(marker_row) else { return };
+    let Ok(start_row) = i64::try_from(start_row) else { return };
+    let row_h = i64::from(view.row_h);
+    let top = i64::from(vr.y) + (marker_row - start_row) * row_h - i64::from(pix_yoff);
+    let bottom = top + row_h;
+    // Clip to the viewport
+    let vtop = i64::from(vr.y);
+    let vbottom = vtop + i64::from(vr.h);
+    if bottom <= vtop || top >= vbottom {
+        return;
+    }
+    // The edge facing the data gets a brighter line
+    let edge = if perspective.flip_row_order {
+        bottom - 1
+    } else {
+        top
+    };
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "Values are clipped to the viewport, which is small"
+    )]
+    {
+        let (ctop, cbottom) = (top.max(vtop), bottom.min(vbottom));
+        draw_rect(
+            vertices,
+            f32::from(vr.x),
+            ctop as f32,
+            f32::from(vr.w),
+            (cbottom - ctop) as f32,
+            Color::rgba(255, 255, 255, 20),
+        );
+        if (vtop..vbottom).contains(&edge) {
+            draw_rect(
+                vertices,
+                f32::from(vr.x),
+                edge as f32,
+                f32::from(vr.w),
+                1.0,
+                Color::rgba(255, 255, 255, 90),
+            );
+        }
+    }
+}
+
 fn draw_rect_outline(
     vertices: &mut Vec<Vertex>,
     x: f32,
@@ -314,6 +559,11 @@ impl View {
         vertex_buffer.clear();
         let mut rs = RenderStates::default();
         let this = &app.meta_state.meta.views[key];
+        let cursor_byte = if app.preferences.highlight_same_byte {
+            app.data.get(app.edit_state.cursor).copied()
+        } else {
+            None
+        };
         match &this.view.kind {
             ViewKind::Hex(hex) => {
                 draw_view(
@@ -322,19 +572,43 @@ impl View {
                     &app.meta_state.meta.low.regions,
                     &app.data,
                     vertex_buffer,
-                    |vertex_buffer, x, y, data, idx, c| {
-                        if selected_or_find_result_contains(app.hex_ui.selection(), idx, gui) {
+                    |vertex_buffer, x, y, data, idx, mut c| {
+                        if let Some(diff_c) = diff_color(app, &this.view, idx) {
+                            c = diff_c;
+                        }
+                        draw_region_tint(
+                            vertex_buffer,
+                            x,
+                            y,
+                            &this.view,
+                            &app.meta_state.meta.low.regions,
+                            idx,
+                        );
+                        draw_same_byte_highlight(
+                            vertex_buffer,
+                            x,
+                            y,
+                            &this.view,
+                            data[0],
+                            cursor_byte,
+                        );
+                        let sel = app.hex_ui.selection();
+                        if let Some(hl) = highlight_color(sel, idx, gui, &this.view.presentation) {
                             draw_rect(
                                 vertex_buffer,
                                 x,
                                 y,
                                 f32::from(this.view.col_w),
                                 f32::from(this.view.row_h),
-                                this.view.presentation.sel_color,
+                                hl,
                             )
                         }
+                        if app.meta_state.meta.comments.contains_key(&idx) {
+                            draw_comment_marker(vertex_buffer, x, y, &this.view);
+                        }
                         let mut gx = x;
-                        for (i, mut d) in hex_conv::byte_to_hex_digits(data[0])
+                        let lowercase = this.view.presentation.lowercase_hex;
+                        for (i, mut d) in hex_conv::byte_to_hex_digits(data[0], lowercase)
                             .into_iter()
                             .enumerate()
                         {
@@ -352,6 +626,20 @@ impl View {
                             );
                             gx += f32::from(hex.font_size - 4);
                         }
+                        if hex.inline_ascii {
+                            let byte = data[0];
+                            let printable = byte.is_ascii_graphic() || byte == b' ';
+                            let ch = if printable { byte } else { b'.' };
+                            draw_glyph(
+                                font,
+                                hex.font_size.into(),
+                                vertex_buffer,
+                                gx + 4.0,
+                                y,
+                                ch.into(),
+                                c,
+                            );
+                        }
                         let extra_x = hex.edit_buf.cursor * (hex.font_size - 4);
                         if idx == app.edit_state.cursor {
                             draw_text_cursor(
@@ -375,17 +663,40 @@ impl View {
                     &app.meta_state.meta.low.regions,
                     &app.data,
                     vertex_buffer,
-                    |vertex_buffer, x, y, data, idx, c| {
-                        if selected_or_find_result_contains(app.hex_ui.selection(), idx, gui) {
+                    |vertex_buffer, x, y, data, idx, mut c| {
+                        if let Some(diff_c) = diff_color(app, &this.view, idx) {
+                            c = diff_c;
+                        }
+                        draw_region_tint(
+                            vertex_buffer,
+                            x,
+                            y,
+                            &this.view,
+                            &app.meta_state.meta.low.regions,
+                            idx,
+                        );
+                        draw_same_byte_highlight(
+                            vertex_buffer,
+                            x,
+                            y,
+                            &this.view,
+                            data[0],
+                            cursor_byte,
+                        );
+                        let sel = app.hex_ui.selection();
+                        if let Some(hl) = highlight_color(sel, idx, gui, &this.view.presentation) {
                             draw_rect(
                                 vertex_buffer,
                                 x,
                                 y,
                                 f32::from(this.view.col_w),
                                 f32::from(this.view.row_h),
-                                this.view.presentation.sel_color,
+                                hl,
                             )
                         }
+                        if app.meta_state.meta.comments.contains_key(&idx) {
+                            draw_comment_marker(vertex_buffer, x, y, &this.view);
+                        }
                         let mut gx = x;
                         for (i, mut d) in dec_conv::byte_to_dec_digits(data[0])
                             .into_iter()
@@ -428,17 +739,40 @@ impl View {
                     &app.meta_state.meta.low.regions,
                     &app.data,
                     vertex_buffer,
-                    |vertex_buffer, x, y, data, idx, c| {
-                        if selected_or_find_result_contains(app.hex_ui.selection(), idx, gui) {
+                    |vertex_buffer, x, y, data, idx, mut c| {
+                        if let Some(diff_c) = diff_color(app, &this.view, idx) {
+                            c = diff_c;
+                        }
+                        draw_region_tint(
+                            vertex_buffer,
+                            x,
+                            y,
+                            &this.view,
+                            &app.meta_state.meta.low.regions,
+                            idx,
+                        );
+                        draw_same_byte_highlight(
+                            vertex_buffer,
+                            x,
+                            y,
+                            &this.view,
+                            data[0],
+                            cursor_byte,
+                        );
+                        let sel = app.hex_ui.selection();
+                        if let Some(hl) = highlight_color(sel, idx, gui, &this.view.presentation) {
                             draw_rect(
                                 vertex_buffer,
                                 x,
                                 y,
                                 f32::from(this.view.col_w),
                                 f32::from(this.view.row_h),
-                                this.view.presentation.sel_color,
+                                hl,
                             )
                         }
+                        if app.meta_state.meta.comments.contains_key(&idx) {
+                            draw_comment_marker(vertex_buffer, x, y, &this.view);
+                        }
                         let raw_data = match text.text_kind {
                             crate::view::TextKind::Ascii => u32::from(data[0]),
                             crate::view::TextKind::Utf16Le => {
@@ -481,6 +815,9 @@ impl View {
                     &app.data,
                     vertex_buffer,
                     |vertex_buffer, x, y, _byte, idx, mut c| {
+                        if let Some(diff_c) = diff_color(app, &this.view, idx) {
+                            c = diff_c;
+                        }
                         if selected_or_find_result_contains(app.hex_ui.selection(), idx, gui) {
                             c = invert_color(c);
                         }
@@ -506,6 +843,116 @@ impl View {
                     },
                 );
             }
+            ViewKind::Bits(bits) => {
+                draw_view(
+                    &this.view,
+                    &app.meta_state.meta.low.perspectives,
+                    &app.meta_state.meta.low.regions,
+                    &app.data,
+                    vertex_buffer,
+                    |vertex_buffer, x, y, data, idx, mut c| {
+                        if let Some(diff_c) = diff_color(app, &this.view, idx) {
+                            c = diff_c;
+                        }
+                        draw_region_tint(
+                            vertex_buffer,
+                            x,
+                            y,
+                            &this.view,
+                            &app.meta_state.meta.low.regions,
+                            idx,
+                        );
+                        draw_same_byte_highlight(
+                            vertex_buffer,
+                            x,
+                            y,
+                            &this.view,
+                            data[0],
+                            cursor_byte,
+                        );
+                        let sel = app.hex_ui.selection();
+                        if let Some(hl) = highlight_color(sel, idx, gui, &this.view.presentation) {
+                            draw_rect(
+                                vertex_buffer,
+                                x,
+                                y,
+                                f32::from(this.view.col_w),
+                                f32::from(this.view.row_h),
+                                hl,
+                            )
+                        }
+                        if app.meta_state.meta.comments.contains_key(&idx) {
+                            draw_comment_marker(vertex_buffer, x, y, &this.view);
+                        }
+                        let mut gx = x;
+                        for pos in 0..8 {
+                            let set = data[0] & (1 << bits.bit_at(pos)) != 0;
+                            // Dim the unset bits, so the set ones stand out
+                            let (glyph, color) = if set {
+                                (b'1', c)
+                            } else {
+                                (b'0', Color::rgba(c.r, c.g, c.b, 90))
+                            };
+                            draw_glyph(
+                                font,
+                                bits.font_size.into(),
+                                vertex_buffer,
+                                gx,
+                                y,
+                                glyph.into(),
+                                color,
+                            );
+                            gx += f32::from(bits.font_size - 4);
+                        }
+                        if idx == app.edit_state.cursor {
+                            draw_rect_outline(
+                                vertex_buffer,
+                                x,
+                                y,
+                                f32::from(this.view.col_w),
+                                f32::from(this.view.row_h),
+                                cursor_color(
+                                    app.hex_ui.focused_view == Some(key),
+                                    app.hex_ui.cursor_flash_timer(),
+                                    &this.view.presentation,
+                                ),
+                                -2.0,
+                            );
+                        }
+                    },
+                );
+                rs.set_texture(Some(font.texture(bits.font_size.into())));
+            }
+        }
+        let mut sector_texts = Vec::new();
+        if let Some(per) = app.meta_state.meta.low.perspectives.get(this.view.perspective) {
+            draw_guides(vertex_buffer, &this.view, per);
+            let labels = draw_sector_separators(
+                vertex_buffer,
+                &this.view,
+                per,
+                &app.meta_state.meta.low.regions,
+            );
+            for (y, sector) in labels {
+                let mut text = Text::new(&format!("#{}", sector), font, 10);
+                text.set_position((f32::from(this.view.viewport_rect.x) + 2.0, y + 1.0));
+                let bounds = text.global_bounds();
+                draw_rect(
+                    vertex_buffer,
+                    bounds.left - 1.0,
+                    bounds.top - 1.0,
+                    bounds.width + 2.0,
+                    bounds.height + 2.0,
+                    Color::rgba(32, 32, 32, 200),
+                );
+                sector_texts.push(text);
+            }
+            draw_end_of_data_marker(
+                vertex_buffer,
+                &this.view,
+                per,
+                &app.meta_state.meta.low.regions,
+            );
         }
         draw_rect_outline(
             vertex_buffer,
@@ -540,7 +987,20 @@ impl View {
         }
         let mut overlay_text = None;
         if app.hex_ui.show_alt_overlay {
-            let mut text = Text::new(&this.name, font, 16);
+            let rect = &this.view.viewport_rect;
+            let mut info = this.name.clone();
+            let low = &app.meta_state.meta.low;
+            if let Some(per) = low.perspectives.get(this.view.perspective) {
+                let _ = write!(
+                    info,
+                    "\nperspective: {}\n{} cols x {} rows",
+                    per.name,
+                    per.cols,
+                    per.n_rows(&low.regions)
+                );
+            }
+            let _ = write!(info, "\n{}x{} px", rect.w, rect.h);
+            let mut text = Text::new(&info, font, 14);
             text.set_position((
                 f32::from(this.view.viewport_rect.x),
                 f32::from(this.view.viewport_rect.y),
@@ -562,12 +1022,58 @@ impl View {
                 glu_sys::glDisable(glu_sys::GL_SCISSOR_TEST);
             }
         }
+        for text in &sector_texts {
+            window.draw(text);
+        }
         if let Some(text) = overlay_text {
             window.draw(&text);
         }
     }
 }
 
+impl View {
+    /// Draw a legend of the keys for getting around views in the corner of the hex interface.
+    ///
+    /// Shown along with the view info overlay while alt is held.
+    pub fn draw_alt_legend(
+        app: &App,
+        window: &mut RenderWindow,
+        vertex_buffer: &mut Vec<Vertex>,
+        font: &Font,
+    ) {
+        let mut legend = String::from(
+            "Tab/Shift+Tab: next/previous view\n\
+             F1/F2: view/edit mode\n\
+             F5-F9: layouts, views, perspectives, regions, bookmarks\n\
+             Alt+Left/Right: back/forward in cursor history",
+        );
+        for action in KeyAction::ALL {
+            if let Some(combo) = app.cfg.keybinds.get(action) {
+                let _ = write!(legend, "\n{}: {}", action.name(), combo);
+            }
+        }
+        let mut text = Text::new(&legend, font, 14);
+        let bounds = text.local_bounds();
+        let rect = &app.hex_ui.hex_iface_rect;
+        text.set_position((
+            f32::from(rect.x + rect.w) - bounds.width - 8.0,
+            f32::from(rect.y + rect.h) - bounds.height - 8.0,
+        ));
+        let bounds = text.global_bounds();
+        vertex_buffer.clear();
+        draw_rect(
+            vertex_buffer,
+            bounds.left - 4.0,
+            bounds.top - 4.0,
+            bounds.width + 8.0,
+            bounds.height + 8.0,
+            Color::rgba(32, 32, 32, 220),
+        );
+        window.draw_primitives(vertex_buffer, PrimitiveType::QUADS, &RenderStates::default());
+        window.draw(&text);
+    }
+}
+
 fn rect_to_gl_viewport(x: i16, y: i16, w: i16, h: i16, viewport_h: i16) -> (i32, i32, i32, i32) {
     (
         GLint::from(x),
@@ -592,7 +1098,25 @@ fn selected_or_find_result_contains(
     idx: usize,
     app_ui: &Gui,
 ) -> bool {
-    selected(app_selection, idx) || find_result_contains(app_ui, idx)
+    selected(app_selection, idx)
+        || find_result_contains(app_ui, idx)
+        || app_ui.file_diff_result_window.highlight_contains(idx)
+}
+
+/// Background color of a selected, found or diff highlighted byte, if it's any of those
+fn highlight_color(
+    app_selection: Option<Region>,
+    idx: usize,
+    app_ui: &Gui,
+    presentation: &Presentation,
+) -> Option<Color> {
+    if selected(app_selection, idx) || app_ui.file_diff_result_window.highlight_contains(idx) {
+        Some(presentation.sel_color)
+    } else if find_result_contains(app_ui, idx) {
+        Some(presentation.find_result_color)
+    } else {
+        None
+    }
 }
 
 fn find_result_contains(app_ui: &Gui, idx: usize) -> bool {